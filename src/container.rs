@@ -1,20 +1,29 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
-use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use std::net::{SocketAddr, IpAddr};
+use std::collections::{HashMap, BTreeMap};
 
-use grpcio::{ChannelBuilder, EnvBuilder, Environment};
+use grpcio::{Channel, ChannelBuilder, EnvBuilder, Environment, CallOption, ClientDuplexSender,
+             WriteFlags};
 use grpcio::Error;
+use futures::{Sink, Stream};
+use futures::sink::Wait;
 use protobuf::{Message as ProtoMessage, MessageStatic};
 use mio::{Poll, Registration, SetReadiness, Ready, Token, PollOpt, Events};
+use uuid::Uuid;
 
-use heartbeat::Server as HeartbeatServer;
+use heartbeat::{Server as HeartbeatServer, epoch_of};
+use tls::ClientTls;
+use rpc_server::RegisterError;
 use super::registry_proto_grpc::*;
 use super::registry_proto::*;
-use super::ServiceId;
+use super::{ServiceId, InstanceId};
 
 const SHUTDOWN_TOKEN: Token = Token(0);
 const HEARTBEAT_TOKEN: Token = Token(1);
+const STREAM_TOKEN: Token = Token(2);
 
 #[derive(Clone, Copy, Debug)]
 struct Meta {
@@ -68,6 +77,18 @@ where
     executor: E,
     rpc_server_addr: SocketAddr,
     heartbeat_interval: Duration,
+    tls: Option<ClientTls>,
+    heartbeat_port: Option<u16>,
+    ephemeral_ports: bool,
+    advertised_host: Option<IpAddr>,
+    instance_id: InstanceId,
+    registry_epoch: u32,
+    // epoch carried by the latest heartbeat, written by the heartbeat server.
+    seen_epoch: Arc<Mutex<Option<u32>>>,
+    rejection: Arc<Mutex<Option<RegisterError>>>,
+    // set by a registry in push mode.
+    lease_ttl: Option<Duration>,
+    session_stream: bool,
 
     thread_handle: Option<JoinHandle<()>>,
 
@@ -88,23 +109,262 @@ struct Inner {
 
     service_id: ServiceId,
     service_meta: String,
+    service_labels: BTreeMap<String, String>,
+    named_ports: BTreeMap<String, u16>,
+    advertised_host: Option<IpAddr>,
+    instance_id: InstanceId,
 
     rpc_server_addr: SocketAddr,
     heartbeat_interval: Duration,
+    tls: Option<ClientTls>,
+    registry_epoch: u32,
+    seen_epoch: Arc<Mutex<Option<u32>>>,
+    rejection: Arc<Mutex<Option<RegisterError>>>,
+    lease_ttl: Option<Duration>,
 }
 
 impl Inner {
-    fn re_register(&self) -> Result<ReRegisterResponse, RpcError> {
+    fn re_register(&self) -> Result<(), RpcError> {
         let mut req = ReRegisterRequest::new();
         req.heartbeat_port = u32::from(self.heartbeat_port);
         req.service_port = u32::from(self.service_port);
         req.service_id = self.service_id.0;
         req.meta = self.service_meta.clone();
+        req.set_labels(
+            self.service_labels
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        );
+        req.set_ports(
+            self.named_ports
+                .iter()
+                .map(|(k, v)| (k.clone(), u32::from(*v)))
+                .collect(),
+        );
+        req.set_advertised_host(advertised(self.advertised_host));
+        req.set_instance_id(self.instance_id.0.clone());
+
+        let ch = connect(&self.env, self.rpc_server_addr, &self.tls);
+        let client = RegisterClient::new(ch);
+        let rsp = client.re_register(&req)?;
+        match register_error(&rsp) {
+            Some(e) => Err(RpcError::Rejected(e)),
+            None => Ok(()),
+        }
+    }
+
+    fn try_re_register(&self) -> bool {
+        match self.re_register() {
+            Ok(()) => {
+                info!("re_register succeed");
+                *self.rejection.lock().unwrap() = None;
+                true
+            }
+            Err(RpcError::Rejected(e)) => {
+                if e.is_unreachable() {
+                    warn!(
+                        "registry can not reach heartbeat port {}, check the advertised host \
+                         and the firewall, reason: {:?}",
+                        self.heartbeat_port,
+                        e
+                    );
+                } else {
+                    warn!("re_register refused reason: {:?}", e);
+                }
+                *self.rejection.lock().unwrap() = Some(e);
+                false
+            }
+            Err(e) => {
+                warn!("re_register failed reason: {:?}", e);
+                false
+            }
+        }
+    }
+
+    // push mode, re-register if the registry forgot us.
+    fn keep_alive(&mut self, ttl: Duration) {
+        let mut req = KeepAliveRequest::new();
+        req.set_service_id(self.service_id.0);
+        req.set_instance_id(self.instance_id.0.clone());
+        req.set_advertised_host(advertised(self.advertised_host));
+
+        let ch = connect(&self.env, self.rpc_server_addr, &self.tls);
+        let client = RegisterClient::new(ch);
+        let opt = CallOption::default().timeout(ttl / 3);
+        match client.keep_alive_opt(&req, opt) {
+            Ok(ref rsp) if rsp.succeed => trace!("lease renewed"),
+            Ok(rsp) => {
+                warn!("lease lost, begin to re_register");
+                if self.try_re_register() {
+                    self.registry_epoch = rsp.registry_epoch;
+                }
+            }
+            Err(e) => warn!("keep_alive failed reason: {:?}", e),
+        }
+    }
 
-        let addr = format!("{}", self.rpc_server_addr);
-        let ch = ChannelBuilder::new(Arc::clone(&self.env)).connect(&addr);
+    fn open_session(&self, readiness: &SetReadiness) -> Result<SessionStream, RpcError> {
+        let mut req = SessionRequest::new();
+        req.set_service_id(self.service_id.0);
+        req.set_instance_id(self.instance_id.0.clone());
+        req.set_meta(self.service_meta.clone());
+        req.set_labels(
+            self.service_labels
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        );
+        req.set_service_port(u32::from(self.service_port));
+        req.set_ports(
+            self.named_ports
+                .iter()
+                .map(|(k, v)| (k.clone(), u32::from(*v)))
+                .collect(),
+        );
+        req.set_advertised_host(advertised(self.advertised_host));
+
+        let ch = connect(&self.env, self.rpc_server_addr, &self.tls);
         let client = RegisterClient::new(ch);
-        client.re_register(&req).map_err(From::from)
+        let (sender, receiver) = client.open_session()?;
+        let mut sender = sender.wait();
+        sender.send((req, WriteFlags::default()))?;
+        sender.flush()?;
+
+        // the responses are read on another thread, which wakes the container
+        // thread up and exits once the stream ends.
+        let (tx, rx) = mpsc::channel();
+        let readiness = readiness.clone();
+        thread::Builder::new()
+            .name("session_reader".to_string())
+            .spawn(move || {
+                for rsp in receiver.wait() {
+                    match rsp {
+                        Ok(rsp) => {
+                            // the stream was given up, the registry ends it soon.
+                            if tx.send(rsp).is_err() {
+                                return;
+                            }
+                        }
+                        Err(e) => {
+                            warn!("session stream broke reason: {:?}", e);
+                            break;
+                        }
+                    }
+                    readiness.set_readiness(Ready::readable()).unwrap();
+                }
+                drop(tx);
+                readiness.set_readiness(Ready::readable()).unwrap();
+            })
+            .unwrap();
+        let now = Instant::now();
+        Ok(SessionStream {
+            sender: sender,
+            responses: rx,
+            seen: now,
+            pinged: now,
+        })
+    }
+
+    // false once the stream is over, a new one is opened.
+    fn read_session(&mut self, stream: &mut SessionStream) -> bool {
+        loop {
+            let mut rsp = match stream.responses.try_recv() {
+                Ok(rsp) => rsp,
+                Err(TryRecvError::Empty) => return true,
+                Err(TryRecvError::Disconnected) => {
+                    warn!("session stream closed by the registry");
+                    return false;
+                }
+            };
+            stream.seen = Instant::now();
+            match rsp.get_command() {
+                SessionCommand::PONG => trace!("receive pong"),
+                SessionCommand::ACCEPTED => {
+                    info!("session stream accepted");
+                    self.registry_epoch = rsp.registry_epoch;
+                    self.lease_ttl = lease_ttl(rsp.lease_ttl_ms);
+                    *self.rejection.lock().unwrap() = None;
+                }
+                SessionCommand::REFUSED => {
+                    let e = RegisterError::from_kind(rsp.get_error(), rsp.take_msg());
+                    warn!("session stream refused reason: {:?}", e);
+                    *self.rejection.lock().unwrap() = Some(e);
+                    return false;
+                }
+                SessionCommand::EVICTED => {
+                    warn!("evicted by the registry, open a new session stream");
+                    return false;
+                }
+            }
+        }
+    }
+
+    // ping a few times per ttl, false if the registry is gone.
+    fn ping(&self, stream: &mut SessionStream) -> bool {
+        let ttl = self.lease_ttl.unwrap_or(self.heartbeat_interval);
+        if stream.seen.elapsed() >= ttl {
+            warn!("registry was silent for {:?}, open a new session stream", ttl);
+            return false;
+        }
+        if stream.pinged.elapsed() < ttl / 3 {
+            return true;
+        }
+        stream.pinged = Instant::now();
+        let sent = stream
+            .sender
+            .send((SessionRequest::new(), WriteFlags::default()))
+            .and_then(|_| stream.sender.flush());
+        if let Err(e) = sent {
+            warn!("ping failed reason: {:?}", e);
+            return false;
+        }
+        true
+    }
+
+    // another instance owns the service id, retrying does not help until it is gone.
+    fn gave_up(&self) -> bool {
+        self.rejection
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(false, |e| e.is_conflict())
+    }
+
+    // a heartbeat from a restarted registry, which restored us from its snapshot.
+    fn check_epoch(&mut self) {
+        let seen = *self.seen_epoch.lock().unwrap();
+        if let Some(epoch) = seen {
+            if epoch != self.registry_epoch {
+                info!(
+                    "registry restarted, epoch {} -> {}, begin to re_register",
+                    self.registry_epoch,
+                    epoch
+                );
+                if self.try_re_register() {
+                    self.registry_epoch = epoch;
+                }
+            }
+        }
+    }
+}
+
+// the session stream replaces the heartbeats, see `Container::session_stream`.
+struct SessionStream {
+    sender: Wait<ClientDuplexSender<SessionRequest>>,
+    responses: Receiver<SessionResponse>,
+    // the latest response, pongs included.
+    seen: Instant,
+    pinged: Instant,
+}
+
+impl SessionStream {
+    // the registry ends its responses once it read the end of the stream, which
+    // lets the reader go.
+    fn close(&mut self) {
+        if let Err(e) = self.sender.close() {
+            warn!("close session stream failed reason: {:?}", e);
+        }
     }
 }
 
@@ -112,6 +372,50 @@ impl Inner {
 pub enum RpcError {
     RpcErr(Error),
     ServerCrashed,
+    // the registry forgot the session because we were too slow.
+    SessionExpired,
+    Rejected(RegisterError),
+    // the executor failed to run on the port, or could not tell the port it bound
+    // when asked for port 0.
+    RunFailed(u16),
+}
+
+fn connect(env: &Arc<Environment>, addr: SocketAddr, tls: &Option<ClientTls>) -> Channel {
+    let addr = format!("{}", addr);
+    let builder = ChannelBuilder::new(Arc::clone(env));
+    match *tls {
+        Some(ref tls) => builder.secure_connect(&addr, tls.credentials()),
+        None => builder.connect(&addr),
+    }
+}
+
+fn named_ports<E: Executor>(executor: &E) -> HashMap<String, u32> {
+    executor
+        .named_ports()
+        .into_iter()
+        .map(|(k, v)| (k, u32::from(v)))
+        .collect()
+}
+
+fn advertised(host: Option<IpAddr>) -> String {
+    host.map(|h| format!("{}", h)).unwrap_or_default()
+}
+
+// 0 if the registry sets no lease.
+fn lease_ttl(ms: u32) -> Option<Duration> {
+    if ms == 0 {
+        None
+    } else {
+        Some(Duration::from_millis(u64::from(ms)))
+    }
+}
+
+fn register_error(rsp: &ReRegisterResponse) -> Option<RegisterError> {
+    if rsp.succeed {
+        return None;
+    }
+    let msg = rsp.get_msg().to_string();
+    Some(RegisterError::from_kind(rsp.get_error(), msg))
 }
 
 impl From<Error> for RpcError {
@@ -125,7 +429,29 @@ pub trait Executor {
     fn meta(&self) -> String {
         "".to_string()
     }
+    fn labels(&self) -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
+    // the port the executor would like to serve on, the registry decides whether
+    // it is used, see `rpc_server::PortAllocator`.
+    fn port(&self) -> Option<u16> {
+        None
+    }
     fn run(&mut self, port: u16) -> bool;
+    // run on `port` and return the port really bound. only an executor overriding
+    // it can tell the port the os chose for port 0, the default refuses to run then.
+    fn run_on(&mut self, port: u16) -> Option<u16> {
+        if port == 0 {
+            warn!("executor can not tell the port it binds, refuse to run on port 0");
+            return None;
+        }
+        if self.run(port) { Some(port) } else { None }
+    }
+    // ports served besides the service port, bound by the executor itself and
+    // read once it runs, e.g. `admin` or `metrics`.
+    fn named_ports(&self) -> BTreeMap<String, u16> {
+        BTreeMap::new()
+    }
     fn stop(&mut self) {}
 }
 
@@ -147,7 +473,12 @@ where
         let (shutdown_registration, shutdown_set_readiness) = Registration::new2();
         let (heartbeat_registration, heartbeat_set_readiness) = Registration::new2();
         let tmp = heartbeat_set_readiness.clone();
-        let f = move |req| {
+        let seen_epoch = Arc::new(Mutex::new(None));
+        let seen = Arc::clone(&seen_epoch);
+        let f = move |req: P| {
+            if let Some(epoch) = epoch_of(&req) {
+                *seen.lock().unwrap() = Some(epoch);
+            }
             tmp.set_readiness(Ready::readable()).unwrap();
             gen_rsp(req)
         };
@@ -159,6 +490,16 @@ where
             executor: executor,
             rpc_server_addr: rpc_server_addr,
             heartbeat_interval: heartbeat_interval,
+            tls: None,
+            heartbeat_port: None,
+            ephemeral_ports: false,
+            advertised_host: None,
+            instance_id: InstanceId(Uuid::new_v4().hyphenated().to_string()),
+            registry_epoch: 0,
+            seen_epoch: seen_epoch,
+            rejection: Arc::new(Mutex::new(None)),
+            lease_ttl: None,
+            session_stream: false,
 
             shutdown_registration: shutdown_registration,
             shutdown_set_readiness: shutdown_set_readiness,
@@ -168,13 +509,76 @@ where
         }
     }
 
+    // talk to a registry serving over tls, must be set before `start`.
+    pub fn tls(mut self, tls: ClientTls) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    // the port the heartbeat server would like to listen on, must be set before `start`.
+    pub fn heartbeat_port(mut self, port: u16) -> Self {
+        self.heartbeat_port = Some(port);
+        self
+    }
+
+    // bind port 0 for both the executor and the heartbeat server whatever the
+    // registry proposes, then report the ports bound, so no retry is needed on
+    // a busy host. the executor must implement `run_on`.
+    pub fn ephemeral_ports(mut self) -> Self {
+        self.ephemeral_ports = true;
+        self
+    }
+
+    // the ip other services reach us on, e.g. the public address behind a nat,
+    // the registry uses the address we connect from if it is not set. the
+    // registry must allow the host, see `RegistryBuilder::advertised_hosts`.
+    pub fn advertised_host(mut self, host: IpAddr) -> Self {
+        self.advertised_host = Some(host);
+        self
+    }
+
+    // keep a single session stream open to the registry instead of serving
+    // heartbeats, for hosts which only allow outbound connections. no port is
+    // negotiated, the executor runs on `Executor::port` or any port. the stream
+    // is opened again if the registry is silent for `heartbeat_interval`, or
+    // for the ttl it sent. the registry must serve them, see
+    // `RegistryBuilder::session_streams`.
+    pub fn session_stream(mut self) -> Self {
+        self.session_stream = true;
+        self
+    }
+
+    // keep the instance id of an earlier run, e.g. read back from a file, so the
+    // registry updates our entry instead of adding another one. must be set
+    // before `start`.
+    pub fn with_instance_id(mut self, id: InstanceId) -> Self {
+        self.instance_id = id;
+        self
+    }
+
+    // sent with every registration, so the registry recognises us when we come back.
+    pub fn instance_id(&self) -> &InstanceId {
+        &self.instance_id
+    }
+
+    // why the registry refused the latest re-registration or session stream, cleared
+    // once one succeeds.
+    // the container stops re-registering after a conflict.
+    pub fn rejection(&self) -> Option<RegisterError> {
+        self.rejection.lock().unwrap().clone()
+    }
+
     fn register_service(&self) -> Result<RegisterResponse, RpcError> {
-        let addr = format!("{}", self.rpc_server_addr);
-        let ch = ChannelBuilder::new(Arc::clone(&self.rpc_env)).connect(&addr);
+        let ch = connect(&self.rpc_env, self.rpc_server_addr, &self.tls);
         let client = RegisterClient::new(ch);
         let mut req = RegisterRequest::new();
         req.set_service_id(self.executor.service_id().0);
         req.set_meta(self.executor.meta());
+        req.set_labels(self.executor.labels().into_iter().collect());
+        req.set_service_port(u32::from(self.executor.port().unwrap_or(0)));
+        req.set_heartbeat_port(u32::from(self.heartbeat_port.unwrap_or(0)));
+        req.set_advertised_host(advertised(self.advertised_host));
+        req.set_instance_id(self.instance_id.0.clone());
         client.register(&req).map_err(|e| e.into())
     }
 
@@ -183,13 +587,33 @@ where
         req.service_succeed = self.meta.has_service_port();
         req.heartbeat_succeed = self.meta.has_heartbeat_port();
         req.session_id = session_id;
+        req.service_port = u32::from(self.meta.service_port.unwrap_or(0));
+        req.heartbeat_port = u32::from(self.meta.heartbeat_port.unwrap_or(0));
+        req.set_ports(named_ports(&self.executor));
 
-        let addr = format!("{}", self.rpc_server_addr);
-        let ch = ChannelBuilder::new(Arc::clone(&self.rpc_env)).connect(&addr);
+        let ch = connect(&self.rpc_env, self.rpc_server_addr, &self.tls);
         let client = RegisterClient::new(ch);
         client.report_status(&req).map_err(|e| e.into())
     }
 
+    fn deregister_service(&self) -> Result<DeregisterResponse, RpcError> {
+        let mut req = DeregisterRequest::new();
+        req.heartbeat_port = u32::from(self.meta.heartbeat_port.unwrap());
+        req.service_port = u32::from(self.meta.service_port.unwrap());
+        req.service_id = self.executor.service_id().0;
+        req.meta = self.executor.meta();
+        req.set_labels(self.executor.labels().into_iter().collect());
+        req.set_ports(named_ports(&self.executor));
+        req.set_advertised_host(advertised(self.advertised_host));
+        req.set_instance_id(self.instance_id.0.clone());
+
+        let ch = connect(&self.rpc_env, self.rpc_server_addr, &self.tls);
+        let client = RegisterClient::new(ch);
+        // do not block drop for too long if the registry is unreachable.
+        let opt = CallOption::default().timeout(self.heartbeat_interval);
+        client.deregister_opt(&req, opt).map_err(From::from)
+    }
+
     fn register_registration(&mut self, poll: &Poll) {
         poll.register(
             &self.shutdown_registration,
@@ -207,27 +631,50 @@ where
 
     fn register_and_run(&mut self) -> Result<(), RpcError> {
         let rsp = self.register_service()?;
+        self.registry_epoch = rsp.registry_epoch;
+        self.lease_ttl = lease_ttl(rsp.lease_ttl_ms);
         let mut service_port = rsp.service_port as u16;
         let mut heartbeat_port = rsp.heartbeat_port as u16;
 
         while !self.meta.has_both_port() {
-            if !self.meta.has_service_port() && self.executor.run(service_port) {
-                self.meta.set_service_port(service_port);
+            if self.ephemeral_ports {
+                service_port = 0;
+                heartbeat_port = 0;
+            }
+
+            if !self.meta.has_service_port() {
+                match self.executor.run_on(service_port) {
+                    Some(0) => {
+                        self.executor.stop();
+                        self.meta.clean();
+                        return Err(RpcError::RunFailed(0));
+                    }
+                    Some(port) => self.meta.set_service_port(port),
+                    // another port is proposed, unless the os was to choose one.
+                    None if service_port == 0 => {
+                        self.meta.clean();
+                        return Err(RpcError::RunFailed(0));
+                    }
+                    None => {}
+                }
             }
 
             if !self.meta.has_heartbeat_port() &&
                 self.heartbeat_server.start(heartbeat_port).is_ok()
             {
-                self.meta.set_heartbeat_port(heartbeat_port);
+                let port = self.heartbeat_server.local_addr().unwrap().port();
+                self.meta.set_heartbeat_port(port);
             }
 
             let status_rsp = self.report_status(rsp.session_id)
                 .and_then(|rsp| {
                     if rsp.succeed {
                         Ok(rsp)
-                    } else {
+                    } else if rsp.stale_session {
                         //indicate server crashed before we report our status,
                         Err(RpcError::ServerCrashed)
+                    } else {
+                        Err(RpcError::SessionExpired)
                     }
                 })
                 .map_err(|e| {
@@ -246,52 +693,91 @@ where
     }
 
     pub fn start(&mut self) -> Result<(), RpcError> {
+        if self.session_stream {
+            return self.start_session();
+        }
         self.register_and_run()?;
 
         let poll = Poll::new().unwrap();
         self.register_registration(&poll);
-        let inner = Inner {
+        let inner = self.inner();
+        let handle = thread::Builder::new()
+            .name("container".to_string())
+            .spawn(move || Self::begin_loop(poll, inner))
+            .unwrap();
+        self.thread_handle = Some(handle);
+        Ok(())
+    }
+
+    // the stream is opened by the container thread, which keeps trying until the
+    // registry accepts the service.
+    fn start_session(&mut self) -> Result<(), RpcError> {
+        let port = self.executor.port().unwrap_or(0);
+        match self.executor.run_on(port) {
+            // the registry refuses port 0, the stream would be opened forever.
+            Some(0) => {
+                self.executor.stop();
+                return Err(RpcError::RunFailed(0));
+            }
+            Some(port) => self.meta.set_service_port(port),
+            None => return Err(RpcError::RunFailed(port)),
+        }
+
+        let poll = Poll::new().unwrap();
+        self.register_registration(&poll);
+        let inner = self.inner();
+        let handle = thread::Builder::new()
+            .name("container".to_string())
+            .spawn(move || Self::session_loop(poll, inner))
+            .unwrap();
+        self.thread_handle = Some(handle);
+        Ok(())
+    }
+
+    fn inner(&self) -> Inner {
+        Inner {
             heartbeat_set_readiness: self.heartbeat_set_readiness.clone(),
             env: Arc::clone(&self.rpc_env),
 
             service_port: self.meta.service_port.unwrap(),
-            heartbeat_port: self.meta.heartbeat_port.unwrap(),
+            // no heartbeat port on a session stream.
+            heartbeat_port: self.meta.heartbeat_port.unwrap_or(0),
             service_id: self.executor.service_id(),
             service_meta: self.executor.meta(),
+            service_labels: self.executor.labels(),
+            named_ports: self.executor.named_ports(),
+            advertised_host: self.advertised_host,
+            instance_id: self.instance_id.clone(),
             rpc_server_addr: self.rpc_server_addr,
             heartbeat_interval: self.heartbeat_interval,
-        };
-
-        let handle = thread::Builder::new()
-            .name("container".to_string())
-            .spawn(move || Self::begin_loop(poll, inner))
-            .unwrap();
-        self.thread_handle = Some(handle);
-        Ok(())
+            tls: self.tls.clone(),
+            registry_epoch: self.registry_epoch,
+            seen_epoch: Arc::clone(&self.seen_epoch),
+            rejection: Arc::clone(&self.rejection),
+            lease_ttl: self.lease_ttl,
+        }
     }
 
-    fn begin_loop(poll: Poll, inner: Inner) {
+    fn begin_loop(poll: Poll, mut inner: Inner) {
         let mut events = Events::with_capacity(4);
+        // no heartbeat comes in push mode, renew the lease a few times per ttl instead.
+        let timeout = inner.lease_ttl.map_or(inner.heartbeat_interval, |ttl| ttl / 3);
         loop {
-            let num = poll.poll(&mut events, Some(inner.heartbeat_interval))
-                .unwrap();
+            let num = poll.poll(&mut events, Some(timeout)).unwrap();
             if num == 0 {
-                //indicate registry server did not touch us for heartbeat_interval time
-                warn!("lost connection to server, begin to re_register");
-                match inner.re_register() {
-                    Ok(rsp) => {
-                        if rsp.succeed {
-                            info!("re_register succeed");
-                        } else {
-                            warn!("re_register failed reason: {:?}", rsp.msg);
-                            thread::sleep(Duration::from_secs(1));
-                        }
-                    }
-                    Err(e) => {
-                        warn!("re_register failed reason: {:?}", e);
+                if let Some(ttl) = inner.lease_ttl {
+                    inner.keep_alive(ttl);
+                } else {
+                    //indicate registry server did not touch us for heartbeat_interval time
+                    warn!("lost connection to server, begin to re_register");
+                    if !inner.try_re_register() && !inner.gave_up() {
                         thread::sleep(Duration::from_secs(1));
                     }
                 }
+                if inner.gave_up() {
+                    error!("service id is taken by another instance, stop re_register");
+                    return;
+                }
             }
             for event in &events {
                 if event.token() == SHUTDOWN_TOKEN && event.readiness().is_readable() {
@@ -303,10 +789,61 @@ where
                         .heartbeat_set_readiness
                         .set_readiness(Ready::empty())
                         .unwrap();
+                    inner.check_epoch();
+                    if inner.gave_up() {
+                        error!("service id is taken by another instance, stop re_register");
+                        return;
+                    }
                 }
             }
         }
     }
+
+    fn session_loop(poll: Poll, mut inner: Inner) {
+        let (registration, readiness) = Registration::new2();
+        poll.register(&registration, STREAM_TOKEN, Ready::readable(), PollOpt::edge())
+            .unwrap();
+        let mut events = Events::with_capacity(4);
+        let mut stream = None;
+        loop {
+            if stream.is_none() {
+                match inner.open_session(&readiness) {
+                    Ok(s) => stream = Some(s),
+                    Err(e) => warn!("open session stream failed reason: {:?}", e),
+                }
+            }
+            let timeout = inner.lease_ttl.map_or(inner.heartbeat_interval, |ttl| ttl / 3);
+            poll.poll(&mut events, Some(timeout)).unwrap();
+            for event in &events {
+                if event.token() == SHUTDOWN_TOKEN && event.readiness().is_readable() {
+                    info!("receive stop signal");
+                    if let Some(mut s) = stream.take() {
+                        s.close();
+                        // the registry drops the service once it read the end of the
+                        // stream, then ends its responses.
+                        while s.responses.recv_timeout(inner.heartbeat_interval).is_ok() {}
+                    }
+                    return;
+                } else if event.token() == STREAM_TOKEN && event.readiness().is_readable() {
+                    readiness.set_readiness(Ready::empty()).unwrap();
+                }
+            }
+            let alive = match stream {
+                Some(ref mut s) => inner.read_session(s) && inner.ping(s),
+                None => true,
+            };
+            if !alive {
+                if let Some(mut s) = stream.take() {
+                    s.close();
+                }
+                if inner.gave_up() {
+                    error!("service id is taken by another instance, stop opening session streams");
+                    return;
+                }
+                thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
 }
 
 impl<P, Q, E> Drop for Container<P, Q, E>
@@ -320,8 +857,15 @@ where
             .set_readiness(Ready::readable())
             .unwrap();
         if let Some(h) = self.thread_handle.take() {
-            self.executor.stop();
             h.join().unwrap();
+            // tell the registry before stopping the executor, so no more traffic come in.
+            // the container thread closed the session stream, which does the same.
+            if !self.session_stream {
+                if let Err(e) = self.deregister_service() {
+                    warn!("deregister failed reason: {:?}", e);
+                }
+            }
+            self.executor.stop();
         }
     }
 }
@@ -0,0 +1,44 @@
+use protobuf::{CodedOutputStream, Message};
+
+// the registry epoch travels with the heartbeat request as an extra field of
+// the user's message, receivers which know nothing about it keep it as an
+// unknown field, so the payload is still a valid `P`.
+const EPOCH_FIELD_NUMBER: u32 = 536_870_911;
+
+pub fn append_epoch(payload: &mut Vec<u8>, epoch: u32) {
+    let mut os = CodedOutputStream::vec(payload);
+    // writing to a vec never fails.
+    os.write_uint32(EPOCH_FIELD_NUMBER, epoch).unwrap();
+    os.flush().unwrap();
+}
+
+// the epoch of the registry which sent the heartbeat request `p`, `None` if
+// it was not sent by a registry.
+pub fn epoch_of<P: Message>(p: &P) -> Option<u32> {
+    p.get_unknown_fields()
+        .get(EPOCH_FIELD_NUMBER)
+        .and_then(|values| values.varint.last())
+        .map(|v| *v as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use protobuf::Message;
+    use protobuf::core::parse_from_bytes;
+
+    use heartbeat::heartbeat_proto::HeartbeatRequest;
+    use super::{append_epoch, epoch_of};
+
+    #[test]
+    fn test_epoch() {
+        let mut req = HeartbeatRequest::new();
+        req.set_msg(7);
+        let mut payload = req.write_to_bytes().unwrap();
+        assert_eq!(epoch_of(&parse_from_bytes::<HeartbeatRequest>(&payload).unwrap()), None);
+
+        append_epoch(&mut payload, 0xdead_beef);
+        let req = parse_from_bytes::<HeartbeatRequest>(&payload).unwrap();
+        assert_eq!(req.get_msg(), 7);
+        assert_eq!(epoch_of(&req), Some(0xdead_beef));
+    }
+}
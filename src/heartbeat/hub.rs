@@ -1,5 +1,6 @@
 use std::io;
 use std::fmt;
+use std::cmp;
 use std::net::SocketAddr;
 use std::time::Duration;
 use std::sync::{Arc, Mutex};
@@ -20,6 +21,7 @@ use uuid::Uuid;
 use worker::future::{Runner, Worker, Scheduler, BoxFuture};
 
 use super::Error;
+use super::epoch::append_epoch;
 use super::timer::{Timer, TimerHandle};
 
 type Cb<Q> = Box<Fn(Uuid, Result<Q, Error>) + Send + 'static>;
@@ -29,6 +31,8 @@ pub struct TargetBuilder<P, Q> {
     uuid: Uuid,
     interval: Option<Duration>,
     timeout: Option<Duration>,
+    max_failures: Option<u32>,
+    epoch: Option<u32>,
     request: Option<P>,
     cb: Option<Cb<Q>>,
 }
@@ -44,6 +48,8 @@ where
             uuid: Uuid::new_v4(),
             interval: None,
             timeout: None,
+            max_failures: None,
+            epoch: None,
             request: None,
             cb: None,
         }
@@ -64,6 +70,18 @@ where
         self
     }
 
+    // overrides the hub's `max_failures` for this target.
+    pub fn max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = Some(cmp::max(1, max_failures));
+        self
+    }
+
+    // send the registry epoch along with every heartbeat request, see `epoch_of`.
+    pub fn epoch(mut self, epoch: u32) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
     pub fn cb<F>(mut self, cb: F) -> Self
     where
         F: Fn(Uuid, Result<Q, Error>) + Send + 'static,
@@ -95,6 +113,9 @@ where
             uuid: self.uuid,
             interval: self.interval,
             timeout: self.timeout,
+            max_failures: self.max_failures,
+            failures: 0,
+            epoch: self.epoch,
             payload: payload,
             cb: self.cb,
             _marker: PhantomData,
@@ -107,6 +128,10 @@ pub struct Target<P, Q> {
     uuid: Uuid,
     interval: Option<Duration>,
     timeout: Option<Duration>,
+    max_failures: Option<u32>,
+    // consecutive failed heartbeats.
+    failures: u32,
+    epoch: Option<u32>,
     payload: Option<Vec<u8>>,
     cb: Option<Cb<Q>>,
     _marker: PhantomData<P>,
@@ -133,6 +158,18 @@ where
         self.timeout
     }
 
+    pub fn get_max_failures(&self) -> Option<u32> {
+        self.max_failures
+    }
+
+    pub fn get_failures(&self) -> u32 {
+        self.failures
+    }
+
+    pub fn get_epoch(&self) -> Option<u32> {
+        self.epoch
+    }
+
     pub fn get_payload(&self) -> &Option<Vec<u8>> {
         &self.payload
     }
@@ -142,6 +179,7 @@ where
             addr: self.addr,
             uuid: self.uuid,
             timeout: self.timeout,
+            epoch: self.epoch,
             payload: self.payload.clone(),
         }
     }
@@ -157,6 +195,7 @@ struct HeartbeatTask {
     addr: SocketAddr,
     uuid: Uuid,
     timeout: Option<Duration>,
+    epoch: Option<u32>,
     payload: Option<Vec<u8>>,
 }
 
@@ -175,7 +214,11 @@ where
         task: HeartbeatTask,
         handle: &Handle,
     ) -> impl Future<Item = Q, Error = Error> {
-        let payload = task.payload.unwrap_or_else(|| self.payload.clone()).into();
+        let mut payload = task.payload.unwrap_or_else(|| self.payload.clone());
+        if let Some(epoch) = task.epoch {
+            append_epoch(&mut payload, epoch);
+        }
+        let payload = payload.into();
         let base = TcpStream::connect(&task.addr, handle)
             .and_then(move |stream| {
                 let frame = Framed::new(stream);
@@ -250,6 +293,7 @@ struct Inner<P, Q> {
     scheduler: Scheduler<HeartbeatTask>,
     timer_handle: TimerHandle,
     interval: Duration,
+    max_failures: u32,
     cb: Option<Cb<Q>>,
 }
 
@@ -257,6 +301,7 @@ pub struct HubBuilder<P, Q> {
     cb: Option<Cb<Q>>,
     timeout: Duration,
     interval: Duration,
+    max_failures: u32,
     request: P,
 }
 
@@ -270,6 +315,7 @@ where
             cb: None,
             timeout: Duration::from_secs(5),
             interval: Duration::from_secs(1),
+            max_failures: 1,
             request: request,
         }
     }
@@ -284,6 +330,13 @@ where
         self
     }
 
+    // a target is removed after `max_failures` consecutive failed heartbeats,
+    // before that it is retried every interval, 0 is treated as 1.
+    pub fn max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = cmp::max(1, max_failures);
+        self
+    }
+
     pub fn cb<F>(mut self, cb: F) -> Self
     where
         F: Fn(Uuid, Result<Q, Error>) + Send + 'static,
@@ -338,6 +391,7 @@ where
             scheduler: scheduler,
             timer_handle: timer_handle,
             interval: self.interval,
+            max_failures: self.max_failures,
             cb: self.cb,
         };
 
@@ -381,11 +435,25 @@ where
                 }
                 Message::HeartbeatResponse(uuid, res) => {
                     let mut targets = inner.handle.targets.lock().unwrap();
-                    if let Some(target) = targets.remove(&uuid) {
+                    if let Some(mut target) = targets.remove(&uuid) {
                         let is_ok = res.is_ok();
+                        if is_ok {
+                            target.failures = 0;
+                        } else {
+                            target.failures += 1;
+                        }
                         target.cb.as_ref().map(|cb| cb(uuid, res.clone()));
                         inner.cb.as_ref().map(|cb| cb(uuid, res));
-                        if is_ok {
+                        let max_failures = target.max_failures.unwrap_or(inner.max_failures);
+                        if target.failures < max_failures {
+                            if !is_ok {
+                                warn!(
+                                    "heartbeat to {:?} failed {}/{} times, retry later",
+                                    target,
+                                    target.failures,
+                                    max_failures
+                                );
+                            }
                             let sender = inner.handle.sender.clone();
                             let f = move || {
                                 let msg = Message::WakeupTarget(uuid);
@@ -400,7 +468,11 @@ where
                             }
                             targets.insert(uuid, target);
                         } else {
-                            warn!("heartbeat to {:?} failed!, remove target", target);
+                            warn!(
+                                "heartbeat to {:?} failed {} times, remove target",
+                                target,
+                                target.failures
+                            );
                         }
                     }
                 }
@@ -450,6 +522,23 @@ where
         let mut targets = self.targets.lock().unwrap();
         Ok(targets.remove(&id))
     }
+
+    // heartbeat the target on another address from the next request on, false
+    // if there is no such target. a request in flight still goes to the old one.
+    pub fn retarget(&self, id: Uuid, addr: SocketAddr) -> Result<bool, Error> {
+        if !self.valid.load(Ordering::SeqCst) {
+            return Err(Error::HubStopped);
+        }
+        let mut targets = self.targets.lock().unwrap();
+        Ok(match targets.get_mut(&id) {
+            Some(target) => {
+                target.addr = addr;
+                target.failures = 0;
+                true
+            }
+            None => false,
+        })
+    }
 }
 
 impl<P, Q> Clone for HubHandle<P, Q> {
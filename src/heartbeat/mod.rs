@@ -4,10 +4,12 @@ use std::sync::Arc;
 mod server;
 mod hub;
 mod timer;
+mod epoch;
 pub mod heartbeat_proto;
 
 pub use self::server::Server;
 pub use self::hub::{Target, TargetBuilder, Hub, HubBuilder, HubHandle};
+pub use self::epoch::epoch_of;
 
 #[derive(Debug, Clone)]
 pub enum Error {
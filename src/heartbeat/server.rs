@@ -1,10 +1,12 @@
 use std::io;
 use std::sync::Arc;
 use std::sync::mpsc;
-use std::net::{SocketAddr, SocketAddrV4};
+use std::net::{SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::thread::{self, JoinHandle};
 
 use bytes::BytesMut;
+use libc::EAFNOSUPPORT;
+use net2::TcpBuilder;
 use tokio_core::reactor::{Core, Handle};
 use tokio_core::net::TcpListener;
 use tokio_io::codec::length_delimited::Framed;
@@ -20,6 +22,7 @@ pub struct Server<P, Q> {
     name: String,
     shutdown_sender: Option<Sender<()>>,
     thread_handle: Option<JoinHandle<()>>,
+    local_addr: Option<SocketAddr>,
     handler: Handler<P, Q>,
 }
 
@@ -94,10 +97,12 @@ where
             name: n.into(),
             shutdown_sender: None,
             thread_handle: None,
+            local_addr: None,
             handler: Arc::new(f),
         }
     }
 
+    // port 0 binds any free port, which is then given by `local_addr`.
     pub fn start(&mut self, port: u16) -> io::Result<()> {
         if self.shutdown_sender.is_some() {
             return Err(io::Error::new(io::ErrorKind::Other, "server was started"));
@@ -110,9 +115,11 @@ where
             move || {
                 let core = Core::new().unwrap();
                 let handle = core.handle();
-                match Self::create_listener(handle, port) {
-                    Ok(listener) => {
-                        succeed_tx.send(Ok(())).unwrap();
+                let listener = Self::create_listener(handle, port)
+                    .and_then(|l| l.local_addr().map(|addr| (l, addr)));
+                match listener {
+                    Ok((listener, addr)) => {
+                        succeed_tx.send(Ok(addr)).unwrap();
                         info!("begin serve");
                         serve(shutdown_rx, &handler, listener, core);
                         info!("finish serve");
@@ -125,20 +132,48 @@ where
             },
         )?;
 
-        succeed_rx.recv().unwrap().map(|_| {
+        succeed_rx.recv().unwrap().map(|addr| {
             assert!(self.shutdown_sender.is_none());
             assert!(self.thread_handle.is_none());
             self.shutdown_sender = Some(shutdown_tx);
             self.thread_handle = Some(thread_handle);
+            self.local_addr = Some(addr);
         })
     }
 
+    // the address the server listens on, `None` before it is started.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    // `[::]` accepts ipv4 peers as well once `IPV6_V6ONLY` is cleared, whatever
+    // `net.ipv6.bindv6only` says, the registry reaches the container by whichever
+    // family the container registered with. hosts without ipv6 fall back to
+    // `0.0.0.0`, any other failure is returned.
     fn create_listener(handle: Handle, port: u16) -> io::Result<TcpListener> {
-        let addr = SocketAddr::V4(SocketAddrV4::new("0.0.0.0".parse().unwrap(), port));
-        TcpListener::bind(&addr, &handle)
+        let addr = SocketAddr::V6(SocketAddrV6::new("::".parse().unwrap(), port, 0, 0));
+        let listener = TcpBuilder::new_v6().and_then(|builder| {
+            builder.only_v6(false)?;
+            builder.reuse_address(true)?;
+            builder.bind(&addr)?;
+            builder.listen(1024)
+        });
+        match listener {
+            Ok(listener) => TcpListener::from_listener(listener, &addr, &handle),
+            Err(ref e) if no_ipv6(e) => {
+                warn!("bind {} failed, reason: {:?}, fall back to ipv4", addr, e);
+                let addr = SocketAddr::V4(SocketAddrV4::new("0.0.0.0".parse().unwrap(), port));
+                TcpListener::bind(&addr, &handle)
+            }
+            Err(e) => Err(e),
+        }
     }
 }
 
+fn no_ipv6(e: &io::Error) -> bool {
+    e.kind() == io::ErrorKind::AddrNotAvailable || e.raw_os_error() == Some(EAFNOSUPPORT)
+}
+
 impl<P, Q> Drop for Server<P, Q> {
     fn drop(&mut self) {
         info!("begin to drop");
@@ -10,19 +10,27 @@ extern crate tokio_io;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+extern crate serde_json;
 extern crate bytes;
 extern crate uuid;
 extern crate mio;
+extern crate net2;
+extern crate libc;
 extern crate grpcio;
 extern crate protobuf;
+extern crate rand;
 extern crate worker;
 
 use std::net::{SocketAddr, IpAddr};
+use std::collections::BTreeMap;
 
 pub mod heartbeat;
 pub mod container;
 pub mod registry;
 pub mod rpc_server;
+pub mod resolver;
+pub mod selector;
+pub mod tls;
 
 mod registry_proto;
 mod registry_proto_grpc;
@@ -43,14 +51,41 @@ impl From<u64> for ServiceId {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+// identify one instance of a service across re-registrations, it is chosen by the
+// container. empty for the containers which do not send one.
+#[derive(Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Debug, Default)]
+#[derive(Serialize, Deserialize)]
+pub struct InstanceId(pub String);
+
+impl InstanceId {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for InstanceId {
+    fn from(s: String) -> Self {
+        InstanceId(s)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
 #[derive(Serialize, Deserialize)]
 pub struct Service {
     sid: ServiceId,
+    // snapshots written before instance ids were added have none.
+    #[serde(default)]
+    instance_id: InstanceId,
     meta: String,
+    // snapshots written before labels were added have none.
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
     host: IpAddr,
     service_port: u16,
     heartbeat_port: u16,
+    // served besides the service port, e.g. `admin` or `metrics`.
+    #[serde(default)]
+    ports: BTreeMap<String, u16>,
 }
 
 impl Service {
@@ -58,17 +93,107 @@ impl Service {
         SocketAddr::new(self.host, self.service_port)
     }
 
-    pub fn heartbeat_addr(&self) -> SocketAddr {
-        SocketAddr::new(self.host, self.heartbeat_port)
+    // none for a service on a session stream, which serves no heartbeats.
+    pub fn heartbeat_addr(&self) -> Option<SocketAddr> {
+        if self.heartbeat_port == 0 {
+            return None;
+        }
+        Some(SocketAddr::new(self.host, self.heartbeat_port))
     }
 
     pub fn service_id(&self) -> ServiceId {
         self.sid
     }
 
+    pub fn instance_id(&self) -> &InstanceId {
+        &self.instance_id
+    }
+
     pub fn meta(&self) -> &str {
         &self.meta
     }
+
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    pub fn label(&self, key: &str) -> Option<&str> {
+        self.labels.get(key).map(|v| v.as_str())
+    }
+
+    pub fn ports(&self) -> &BTreeMap<String, u16> {
+        &self.ports
+    }
+
+    // the address of the named port `name`.
+    pub fn addr(&self, name: &str) -> Option<SocketAddr> {
+        self.ports.get(name).map(|port| SocketAddr::new(self.host, *port))
+    }
+}
+
+// a service turns `Suspect` after a failed heartbeat and back to `Healthy` after a
+// successful one, it is `Down` and removed from the registry once the failure
+// threshold is hit.
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+pub enum Health {
+    Healthy,
+    Suspect,
+    Down,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Health::Healthy
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ServiceEvent {
+    Added(Service),
+    Dropped(Service),
+    HealthChanged(Service, Health),
+    // an instance came back with other addresses or ports, the old service and the
+    // new one.
+    Updated(Service, Service),
+}
+
+impl ServiceEvent {
+    pub fn service(&self) -> &Service {
+        match *self {
+            ServiceEvent::Added(ref s) |
+            ServiceEvent::Dropped(ref s) |
+            ServiceEvent::HealthChanged(ref s, _) |
+            ServiceEvent::Updated(_, ref s) => s,
+        }
+    }
+
+    pub fn is_added(&self) -> bool {
+        match *self {
+            ServiceEvent::Added(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_dropped(&self) -> bool {
+        match *self {
+            ServiceEvent::Dropped(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_health_changed(&self) -> bool {
+        match *self {
+            ServiceEvent::HealthChanged(_, _) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_updated(&self) -> bool {
+        match *self {
+            ServiceEvent::Updated(_, _) => true,
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
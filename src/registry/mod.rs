@@ -0,0 +1,1247 @@
+use std::cmp;
+use std::mem;
+use std::thread::{self, JoinHandle};
+use std::net::{SocketAddr, IpAddr, Ipv4Addr};
+use std::path::PathBuf;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::mpsc::{self, Sender, Receiver, RecvTimeoutError};
+
+use protobuf::{Message as ProtoMessage, MessageStatic};
+use grpcio::{Error as GrpcError, Server as GrpcServer};
+use futures::sync::mpsc::{self as future_mpsc, UnboundedSender, UnboundedReceiver};
+use uuid::Uuid;
+
+use heartbeat::{Hub, HubHandle, TargetBuilder, Error as HeartbeatError};
+use super::{Service, ServiceId, InstanceId, ServiceEvent, Health, rpc_server};
+use rpc_server::{Sessions, PortAllocator, Sequential, RegisterError, Responder, Command,
+                 StreamEvent};
+use selector::Selector;
+use tls::ServerTls;
+
+mod table;
+mod persist;
+mod probe;
+
+use self::table::{ServiceTable, ServiceDetail};
+use self::persist::Snapshot;
+use self::probe::Prober;
+
+type ServiceDetails = Arc<Mutex<ServiceTable>>;
+// the service of every accepted session stream, with the sender of its commands.
+type StreamMap = HashMap<u64, (Uuid, UnboundedSender<Command>)>;
+
+#[derive(Debug, Clone)]
+pub enum DropReason {
+    // the container deregistered itself.
+    Graceful,
+    HeartbeatFailed(HeartbeatError),
+    // another instance with the same service id took over, see `ConflictPolicy`.
+    Replaced(Service),
+    // the container did not renew the lease in time, see `Mode::Push`.
+    LeaseExpired,
+    // the session stream broke before the container closed it.
+    StreamBroken,
+}
+
+impl DropReason {
+    pub fn is_graceful(&self) -> bool {
+        match *self {
+            DropReason::Graceful => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_heartbeat_failed(&self) -> bool {
+        match *self {
+            DropReason::HeartbeatFailed(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_replaced(&self) -> bool {
+        match *self {
+            DropReason::Replaced(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_lease_expired(&self) -> bool {
+        match *self {
+            DropReason::LeaseExpired => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_stream_broken(&self) -> bool {
+        match *self {
+            DropReason::StreamBroken => true,
+            _ => false,
+        }
+    }
+}
+
+// how the registry finds out a service is gone. containers on a session stream
+// ping within the ttl given to `RegistryBuilder::session_streams` whatever the mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    // the hub sends heartbeats to the containers, the default.
+    Pull,
+    // the containers renew a lease within the ttl through the grpc server, which
+    // works for containers behind a firewall. the hub is not used.
+    Push(Duration),
+}
+
+// what to do when a service registers under a service id which is taken by
+// another instance, i.e. one with a different instance id, or a different
+// heartbeat address if the container sends none. an instance which registers
+// again, e.g. restarted on other ports, always takes over its old entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    // keep all the instances, the default.
+    AllowMultiple,
+    // drop the older instances.
+    ReplaceOlder,
+    // refuse the newer instance, the container is told why.
+    Reject,
+}
+
+// `cause` is the heartbeat error which made the service `Suspect` or `Down`,
+// it is `None` when the service recovered, its lease expired or its session
+// stream broke.
+#[derive(Debug, Clone)]
+pub struct HealthChange {
+    pub from: Health,
+    pub to: Health,
+    pub cause: Option<HeartbeatError>,
+}
+
+impl HealthChange {
+    fn new(from: Health, to: Health, cause: Option<HeartbeatError>) -> Self {
+        HealthChange {
+            from: from,
+            to: to,
+            cause: cause,
+        }
+    }
+}
+
+pub struct Registry<P, Q>
+where
+    P: ProtoMessage,
+    Q: MessageStatic,
+{
+    handle: RegistryHandle,
+    sender: Sender<Message<Q>>,
+    grpc_server: Option<GrpcServer>,
+    sessions: Sessions,
+    hub: Option<Hub<P, Q>>,
+    thread_handle: Option<JoinHandle<()>>,
+    dispatcher_handle: Option<JoinHandle<()>>,
+    // only in push mode or with session streams, it stops once the sender is dropped.
+    lease_reaper: Option<(Sender<()>, JoinHandle<()>)>,
+}
+
+// query the service table from other threads, e.g. in the callbacks.
+#[derive(Clone)]
+pub struct RegistryHandle {
+    services: ServiceDetails,
+}
+
+struct Inner<P, Q>
+where
+    P: ProtoMessage,
+    Q: MessageStatic,
+{
+    services: ServiceDetails,
+    sender: Sender<Message<Q>>,
+    receiver: Receiver<Message<Q>>,
+    hub_handle: HubHandle<P, Q>,
+    watchers: Vec<UnboundedSender<ServiceEvent>>,
+    snapshot: Option<Snapshot>,
+    max_failures: u32,
+    epoch: u32,
+    conflict_policy: ConflictPolicy,
+    max_instances: Option<usize>,
+    mode: Mode,
+    stream_ttl: Option<Duration>,
+    streams: StreamMap,
+    callbacks: Sender<Callback>,
+}
+
+// how the registry tells a service is alive.
+enum Liveness {
+    Heartbeat,
+    Lease(Duration),
+}
+
+// the callbacks run one by one on the dispatcher thread, in the order the
+// changes were made, so they never hold the service table lock.
+struct Handles {
+    service_available_handle: Box<Fn(Service) + Send + 'static>,
+    service_droped_handle: Box<Fn(Service, DropReason) + Send + 'static>,
+    health_changed_handle: Box<Fn(Service, HealthChange) + Send + 'static>,
+    service_updated_handle: Box<Fn(Service, Service) + Send + 'static>,
+}
+
+enum Callback {
+    Available(Service),
+    Droped(Service, DropReason),
+    HealthChanged(Service, HealthChange),
+    Updated(Service, Service),
+}
+
+pub struct RegistryBuilder<P, Q>
+where
+    P: ProtoMessage,
+    Q: MessageStatic,
+{
+    server_addr: SocketAddr,
+    hub: Hub<P, Q>,
+    snapshot_path: Option<PathBuf>,
+    max_failures: u32,
+    session_ttl: Duration,
+    port_allocator: Arc<PortAllocator>,
+    advertised_hosts: Option<Vec<IpAddr>>,
+    conflict_policy: ConflictPolicy,
+    max_instances: Option<usize>,
+    probe_timeout: Duration,
+    mode: Mode,
+    stream_ttl: Option<Duration>,
+    tls: Option<ServerTls>,
+    service_available_handle: Box<Fn(Service) + Send + 'static>,
+    service_droped_handle: Box<Fn(Service, DropReason) + Send + 'static>,
+    health_changed_handle: Box<Fn(Service, HealthChange) + Send + 'static>,
+    service_updated_handle: Box<Fn(Service, Service) + Send + 'static>,
+}
+
+impl<P, Q> RegistryBuilder<P, Q>
+where
+    P: ProtoMessage,
+    Q: MessageStatic,
+{
+    // listen on `0.0.0.0:server_port`.
+    pub fn new(server_port: u16, hub: Hub<P, Q>) -> Self {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), server_port);
+        RegistryBuilder::bind(addr, hub)
+    }
+
+    // listen on any address, e.g. `[::]:port` for ipv6 or `127.0.0.1:port`.
+    pub fn bind(server_addr: SocketAddr, hub: Hub<P, Q>) -> Self {
+        RegistryBuilder {
+            server_addr: server_addr,
+            hub: hub,
+            snapshot_path: None,
+            max_failures: 1,
+            session_ttl: Duration::from_secs(Sessions::DEFAULT_TTL_SECS),
+            port_allocator: Arc::new(Sequential::default()),
+            advertised_hosts: None,
+            conflict_policy: ConflictPolicy::AllowMultiple,
+            max_instances: None,
+            probe_timeout: Duration::from_secs(1),
+            mode: Mode::Pull,
+            stream_ttl: None,
+            tls: None,
+            service_available_handle: Box::new(|_: Service| {}),
+            service_droped_handle: Box::new(|_: Service, _: DropReason| {}),
+            health_changed_handle: Box::new(|_: Service, _: HealthChange| {}),
+            service_updated_handle: Box::new(|_: Service, _: Service| {}),
+        }
+    }
+
+    pub fn service_available_handle<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Service) + Send + 'static,
+    {
+        self.service_available_handle = Box::new(f);
+        self
+    }
+
+    pub fn service_droped_handle<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Service, DropReason) + Send + 'static,
+    {
+        self.service_droped_handle = Box::new(f);
+        self
+    }
+
+    // called for every health transition, including the final one to `Down`
+    // which is followed by the drop handle.
+    pub fn health_changed_handle<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Service, HealthChange) + Send + 'static,
+    {
+        self.health_changed_handle = Box::new(f);
+        self
+    }
+
+    // called with the old service and the new one when an instance comes back
+    // with other addresses or ports, it keeps its entry and is not dropped.
+    pub fn service_updated_handle<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Service, Service) + Send + 'static,
+    {
+        self.service_updated_handle = Box::new(f);
+        self
+    }
+
+    // keep the service table in a snapshot file, services in an existing snapshot
+    // are added back to the hub when the registry starts, those which are gone
+    // will be dropped once their heartbeats fail.
+    pub fn snapshot<T: Into<PathBuf>>(mut self, path: T) -> Self {
+        self.snapshot_path = Some(path.into());
+        self
+    }
+
+    // a service is dropped after `max_failures` consecutive failed heartbeats,
+    // 0 is treated as 1.
+    pub fn max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = cmp::max(1, max_failures);
+        self
+    }
+
+    // a container which has not finished the port negotiation within `ttl`,
+    // counted from its last request, has to register again.
+    pub fn session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = ttl;
+        self
+    }
+
+    // decide the ports proposed to the containers, ports taken by the services on
+    // the same host are never proposed. `Sequential` with the default ranges is used
+    // if it is not set, a container fails to register once a range is exhausted.
+    pub fn port_allocator<A: PortAllocator + 'static>(mut self, allocator: A) -> Self {
+        self.port_allocator = Arc::new(allocator);
+        self
+    }
+
+    // the hosts a container may advertise instead of the address it connects
+    // from, requests advertising others are rejected. only the address it
+    // connects from is accepted if it is not set.
+    pub fn advertised_hosts<I: IntoIterator<Item = IpAddr>>(mut self, hosts: I) -> Self {
+        self.advertised_hosts = Some(hosts.into_iter().collect());
+        self
+    }
+
+    pub fn conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.conflict_policy = policy;
+        self
+    }
+
+    // refuse a service once its service id has `max` instances, no limit by default.
+    pub fn max_instances(mut self, max: usize) -> Self {
+        self.max_instances = Some(max);
+        self
+    }
+
+    // a container which re-registers is refused if its heartbeat port can not be
+    // connected within `timeout`. it is probed by a small pool of threads once it
+    // is admitted, the registry goes on meanwhile.
+    pub fn probe_timeout(mut self, timeout: Duration) -> Self {
+        self.probe_timeout = timeout;
+        self
+    }
+
+    // `Mode::Pull` by default, the containers follow the mode of the registry.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    // serve session streams, see `Container::session_stream`, a container on one
+    // which does not ping within `ttl` is dropped. they are refused by default.
+    pub fn session_streams(mut self, ttl: Duration) -> Self {
+        self.stream_ttl = Some(ttl);
+        self
+    }
+
+    // serve over tls, containers must connect with a matching `ClientTls`.
+    pub fn tls(mut self, tls: ServerTls) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    pub fn build(self) -> Result<Registry<P, Q>, GrpcError> {
+        let (tx, rx) = mpsc::channel();
+        // create grpc server;
+        // grpc was droped before the loop routine, so it is safe to unwrap.
+        let register_handle = {
+            let sender = tx.clone();
+            move |service| sender.send(Message::Register(service)).unwrap()
+        };
+        let services: ServiceDetails = Default::default();
+        let (policy, max_instances) = (self.conflict_policy, self.max_instances);
+        let re_register_handle = {
+            let sender = tx.clone();
+            let services = Arc::clone(&services);
+            // the heartbeat port is never connected in push mode.
+            let prober = if self.mode == Mode::Pull {
+                let sender = Mutex::new(tx.clone());
+                Some(Prober::new(self.probe_timeout, move |service, responder, res| match res {
+                    // the registry may have stopped meanwhile.
+                    Ok(()) => {
+                        let msg = Message::ReRegister(service, responder);
+                        let _ = sender.lock().unwrap().send(msg);
+                    }
+                    Err(e) => {
+                        warn!("re_register service:{:?} failed, reason: {:?}", service, e);
+                        let _ = responder.send(Err(e));
+                    }
+                }))
+            } else {
+                None
+            };
+            move |service: Service, responder: Responder| {
+                // a known service which only lost the heartbeats for a while is not
+                // probed, the others are admitted before the probe.
+                let known = {
+                    let lock = services.lock().unwrap();
+                    let known = lock.contains(&service);
+                    if !known {
+                        if let Err(e) = admit(&lock, &service, policy, max_instances) {
+                            warn!("re_register service:{:?} failed, reason: {:?}", service, e);
+                            let _ = responder.send(Err(e));
+                            return;
+                        }
+                    }
+                    known
+                };
+                match prober {
+                    Some(ref prober) if !known => prober.probe(service, responder),
+                    _ => sender.send(Message::ReRegister(service, responder)).unwrap(),
+                }
+            }
+        };
+        let watch_handle = {
+            let sender = tx.clone();
+            move |watcher| sender.send(Message::Watch(watcher)).unwrap()
+        };
+        let deregister_handle = {
+            let sender = tx.clone();
+            move |service| sender.send(Message::Deregister(service)).unwrap()
+        };
+        let used_ports = {
+            let services = Arc::clone(&services);
+            move |host| services.lock().unwrap().ports_on(host)
+        };
+        let admission = {
+            let services = Arc::clone(&services);
+            move |service: &Service| {
+                let lock = services.lock().unwrap();
+                admit(&lock, service, policy, max_instances).map(|_| ())
+            }
+        };
+        let mut sessions = Sessions::new(self.session_ttl)
+            .allocator(self.port_allocator)
+            .used_ports(used_ports)
+            .admission(admission);
+        if self.stream_ttl.is_some() {
+            // the hook is shared by the grpc threads.
+            let sender = Mutex::new(tx.clone());
+            sessions = sessions.streams(move |event| {
+                sender.lock().unwrap().send(Message::Stream(event)).unwrap()
+            });
+        }
+        if let Some(hosts) = self.advertised_hosts {
+            sessions = sessions.advertised_hosts(hosts);
+        }
+        if let Mode::Push(ttl) = self.mode {
+            let services = Arc::clone(&services);
+            sessions = sessions.leases(ttl, move |sid, instance_id, host| {
+                let expires = Instant::now() + ttl;
+                services.lock().unwrap().renew(sid, instance_id, host, expires)
+            });
+        }
+        // services on a session stream hold a lease in any mode.
+        let lease_ttl = match (self.mode, self.stream_ttl) {
+            (Mode::Pull, stream_ttl) => stream_ttl,
+            (Mode::Push(ttl), None) => Some(ttl),
+            (Mode::Push(ttl), Some(stream_ttl)) => Some(cmp::min(ttl, stream_ttl)),
+        };
+        let lease_reaper = lease_ttl.map(|ttl| {
+            let sender = tx.clone();
+            let (stop_tx, stop_rx) = mpsc::channel();
+            let handle = thread::Builder::new()
+                .name("lease_reaper".to_string())
+                .spawn(move || lease_loop(sender, ttl, stop_rx))
+                .unwrap();
+            (stop_tx, handle)
+        });
+        let mut grpc_server = rpc_server::create_grpc_server_on(
+            self.server_addr,
+            self.tls.as_ref(),
+            sessions.clone(),
+            register_handle,
+            re_register_handle,
+            watch_handle,
+            deregister_handle,
+        )?;
+        grpc_server.start();
+
+        let snapshot = self.snapshot_path.map(Snapshot::new);
+        if let Some(ref snapshot) = snapshot {
+            match snapshot.load() {
+                Ok(services) => {
+                    info!(
+                        "restore {} services from snapshot {:?}",
+                        services.len(),
+                        snapshot.path()
+                    );
+                    // queued before the loop routine starts, restored services which
+                    // register again are rejected as duplicates.
+                    for service in services {
+                        tx.send(Message::Register(service)).unwrap();
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "load snapshot {:?} failed, reason: {:?}, start with an empty table",
+                        snapshot.path(),
+                        e
+                    );
+                }
+            }
+        }
+
+        let (callbacks, callback_receiver) = mpsc::channel();
+        let handles = Handles {
+            service_available_handle: self.service_available_handle,
+            service_droped_handle: self.service_droped_handle,
+            health_changed_handle: self.health_changed_handle,
+            service_updated_handle: self.service_updated_handle,
+        };
+        let dispatcher_handle = thread::Builder::new()
+            .name("registry_dispatcher".to_string())
+            .spawn(move || dispatch_loop(callback_receiver, handles))
+            .unwrap();
+
+        let hub = self.hub;
+        let inner = Inner {
+            services: Arc::clone(&services),
+            sender: tx.clone(),
+            receiver: rx,
+            hub_handle: hub.get_handle(),
+            watchers: Vec::new(),
+            snapshot: snapshot,
+            max_failures: self.max_failures,
+            epoch: sessions.epoch(),
+            conflict_policy: policy,
+            max_instances: max_instances,
+            mode: self.mode,
+            stream_ttl: self.stream_ttl,
+            streams: HashMap::new(),
+            callbacks: callbacks,
+        };
+
+        let thread_handle = thread::Builder::new()
+            .name("registry_notifier".to_string())
+            .spawn(move || Registry::<P, Q>::begin_loop(inner))
+            .unwrap();
+
+        Ok(Registry {
+            handle: RegistryHandle { services: services },
+            sender: tx,
+            grpc_server: Some(grpc_server),
+            sessions: sessions,
+            hub: Some(hub),
+            thread_handle: Some(thread_handle),
+            dispatcher_handle: Some(dispatcher_handle),
+            lease_reaper: lease_reaper,
+        })
+    }
+}
+
+impl<P, Q> Registry<P, Q>
+where
+    P: ProtoMessage,
+    Q: MessageStatic,
+{
+    pub fn new<F1, F2>(
+        server_port: u16,
+        hub: Hub<P, Q>,
+        service_available_handle: F1,
+        service_droped_handle: F2,
+    ) -> Result<Self, GrpcError>
+    where
+        F1: Fn(Service) + Send + 'static,
+        F2: Fn(Service, DropReason) + Send + 'static,
+    {
+        RegistryBuilder::new(server_port, hub)
+            .service_available_handle(service_available_handle)
+            .service_droped_handle(service_droped_handle)
+            .build()
+    }
+
+    // changes every time the registry restarts, containers compare it with the one
+    // sent along with the heartbeats to find out a restart.
+    pub fn epoch(&self) -> u32 {
+        self.sessions.epoch()
+    }
+
+    // containers which are negotiating ports with the registry.
+    pub fn pending_sessions(&self) -> usize {
+        self.sessions.pending()
+    }
+
+    pub fn get_handle(&self) -> RegistryHandle {
+        self.handle.clone()
+    }
+
+    pub fn get_all_services(&self) -> Vec<Service> {
+        self.handle.get_all_services()
+    }
+
+    pub fn get_services_by_id(&self, sid: ServiceId) -> Vec<Service> {
+        self.handle.get_services_by_id(sid)
+    }
+
+    pub fn get_service_by_addr(&self, addr: SocketAddr) -> Option<Service> {
+        self.handle.get_service_by_addr(addr)
+    }
+
+    pub fn get_service_by_instance_id(&self, id: &InstanceId) -> Option<Service> {
+        self.handle.get_service_by_instance_id(id)
+    }
+
+    pub fn get_services_by_meta(&self, meta: &str) -> Vec<Service> {
+        self.handle.get_services_by_meta(meta)
+    }
+
+    pub fn get_service_health(&self, service: &Service) -> Option<Health> {
+        self.handle.get_service_health(service)
+    }
+
+    pub fn get_services_by_selector(&self, selector: &Selector) -> Vec<Service> {
+        self.handle.get_services_by_selector(selector)
+    }
+
+    pub fn filter_services<F>(&self, f: F) -> Vec<Service>
+    where
+        F: Fn(&Service) -> bool,
+    {
+        self.handle.filter_services(f)
+    }
+
+    // the returned stream starts with an `Added` event for every service in the table,
+    // followed by a `HealthChanged` event if the service is not healthy.
+    pub fn watch(&self) -> UnboundedReceiver<ServiceEvent> {
+        let (tx, rx) = future_mpsc::unbounded();
+        self.sender.send(Message::Watch(tx)).unwrap();
+        rx
+    }
+
+    fn begin_loop(mut inner: Inner<P, Q>) {
+        loop {
+            match inner.receiver.recv().unwrap() {
+                Message::Register(service) => {
+                    // the container does not wait for an answer, failures were logged.
+                    let _ = Self::add_service(service, &mut inner);
+                }
+                Message::ReRegister(service, responder) => {
+                    let res = Self::re_register(service, &mut inner);
+                    // the container may have stopped waiting.
+                    let _ = responder.send(res);
+                }
+                Message::Heartbeat(uuid, res) => Self::handle_heartbeat(uuid, res, &mut inner),
+                Message::Deregister(service) => Self::remove_service(service, &mut inner),
+                Message::Watch(watcher) => Self::add_watcher(watcher, &mut inner),
+                Message::CheckLeases => Self::expire_leases(&mut inner),
+                Message::Stream(event) => Self::handle_stream(event, &mut inner),
+                Message::Stop => break,
+            }
+        }
+    }
+
+    fn handle_heartbeat(uuid: Uuid, res: Result<Q, HeartbeatError>, inner: &mut Inner<P, Q>) {
+        let mut lock = inner.services.lock().unwrap();
+        // the service may have been deregistered while the heartbeat was in flight.
+        let (service, from, failures) = match lock.get_mut(&uuid) {
+            Some(detail) => {
+                if res.is_ok() {
+                    detail.failures = 0;
+                } else {
+                    detail.failures += 1;
+                }
+                (detail.service.clone(), detail.health, detail.failures)
+            }
+            None => return,
+        };
+        let e = match res {
+            Ok(_) => {
+                if from == Health::Suspect {
+                    info!("service:{:?} recovered", service);
+                    lock.get_mut(&uuid).unwrap().health = Health::Healthy;
+                    let change = HealthChange::new(from, Health::Healthy, None);
+                    notify_health_changed(&mut inner.watchers, &inner.callbacks, service, change);
+                }
+                return;
+            }
+            Err(e) => e,
+        };
+
+        if failures < inner.max_failures {
+            warn!(
+                "heartbeat to service:{:?} failed {}/{} times, reason:{:?}",
+                service,
+                failures,
+                inner.max_failures,
+                e
+            );
+            if from == Health::Healthy {
+                lock.get_mut(&uuid).unwrap().health = Health::Suspect;
+                let change = HealthChange::new(from, Health::Suspect, Some(e));
+                notify_health_changed(&mut inner.watchers, &inner.callbacks, service, change);
+            }
+            return;
+        }
+
+        // the hub removed the target by itself, it shares the same threshold.
+        let detail = lock.remove(&uuid).unwrap();
+        close_streams(&mut inner.streams, uuid);
+        warn!(
+            "heartbeat to service:{:?} failed, reason:{:?}, remove this service",
+            detail,
+            e
+        );
+        save_snapshot(inner.snapshot.as_ref(), lock);
+        let change = HealthChange::new(from, Health::Down, Some(e.clone()));
+        notify_health_changed(&mut inner.watchers, &inner.callbacks, service, change);
+        notify_watchers(
+            &mut inner.watchers,
+            ServiceEvent::Dropped(detail.service.clone()),
+        );
+        let reason = DropReason::HeartbeatFailed(e);
+        dispatch(&inner.callbacks, Callback::Droped(detail.service, reason));
+    }
+
+    fn add_watcher(watcher: UnboundedSender<ServiceEvent>, inner: &mut Inner<P, Q>) {
+        let details = inner.services.lock().unwrap().details();
+        for detail in details {
+            let health = detail.health;
+            if watcher.unbounded_send(ServiceEvent::Added(detail.service.clone())).is_err() {
+                return;
+            }
+            if health != Health::Healthy {
+                let event = ServiceEvent::HealthChanged(detail.service, health);
+                if watcher.unbounded_send(event).is_err() {
+                    return;
+                }
+            }
+        }
+        inner.watchers.push(watcher);
+    }
+
+    fn remove_service(service: Service, inner: &mut Inner<P, Q>) {
+        let mut lock = inner.services.lock().unwrap();
+        let detail = match lock.find(&service).and_then(|uuid| lock.remove(&uuid)) {
+            Some(detail) => detail,
+            None => {
+                warn!(
+                    "deregister service:{:?} failed, it is not in the service table",
+                    service
+                );
+                return;
+            }
+        };
+        if inner.hub_handle.remove_target(detail.uuid).is_err() {
+            info!("remove target from hub failed because hub was destroyed");
+        }
+        close_streams(&mut inner.streams, detail.uuid);
+        info!("service:{:?} deregistered", detail);
+        save_snapshot(inner.snapshot.as_ref(), lock);
+        notify_watchers(
+            &mut inner.watchers,
+            ServiceEvent::Dropped(detail.service.clone()),
+        );
+        dispatch(
+            &inner.callbacks,
+            Callback::Droped(detail.service, DropReason::Graceful),
+        );
+    }
+
+    // the service was admitted and probed by the grpc handler.
+    fn re_register(service: Service, inner: &mut Inner<P, Q>) -> Result<(), RegisterError> {
+        // a known service which only lost the heartbeats for a while.
+        if inner.services.lock().unwrap().contains(&service) {
+            return Ok(());
+        }
+        Self::add_service(service, inner)
+    }
+
+    fn add_service(service: Service, inner: &mut Inner<P, Q>) -> Result<(), RegisterError> {
+        let liveness = match (inner.mode, inner.stream_ttl) {
+            (Mode::Push(ttl), _) => Liveness::Lease(ttl),
+            (Mode::Pull, _) if service.heartbeat_addr().is_some() => Liveness::Heartbeat,
+            // restored from a snapshot, the container opens its session stream again.
+            (Mode::Pull, Some(ttl)) => Liveness::Lease(ttl),
+            (Mode::Pull, None) => {
+                warn!("service:{:?} has no heartbeat port, ignore it", service);
+                return Err(RegisterError::Unreachable(
+                    format!("service:{:?} has no heartbeat port", service.sid),
+                ));
+            }
+        };
+        Self::insert_service(service, liveness, inner).map(|_| ())
+    }
+
+    // the entry of the service, which may have been in the table.
+    fn insert_service(
+        service: Service,
+        liveness: Liveness,
+        inner: &mut Inner<P, Q>,
+    ) -> Result<Uuid, RegisterError> {
+        let mut lock = inner.services.lock().unwrap();
+        if let Some(uuid) = lock.find(&service) {
+            info!("service:{:?} had been in the service table", service);
+            return Ok(uuid);
+        }
+        // checked again, the table may have changed since the service was admitted.
+        let admitted = admit(&lock, &service, inner.conflict_policy, inner.max_instances);
+        let (returning, mut replaced) = match admitted {
+            Ok(admitted) => admitted,
+            Err(e) => {
+                warn!("add service:{:?} failed, reason: {:?}", service, e);
+                return Err(e);
+            }
+        };
+        let leased = match liveness {
+            Liveness::Heartbeat => false,
+            Liveness::Lease(_) => true,
+        };
+        let returning = match returning {
+            Some(uuid) => {
+                if Self::take_over(uuid, &service, leased, &lock, inner)? {
+                    Some(uuid)
+                } else {
+                    replaced.push(uuid);
+                    None
+                }
+            }
+            None => None,
+        };
+        let (uuid, expires) = match (returning, liveness) {
+            (Some(uuid), Liveness::Heartbeat) => (uuid, None),
+            (Some(uuid), Liveness::Lease(ttl)) => (uuid, Some(Instant::now() + ttl)),
+            (None, Liveness::Heartbeat) => match Self::add_target(heartbeat_addr(&service), inner) {
+                Some(uuid) => (uuid, None),
+                None => {
+                    info!("add target to hub failed because hub was destroyed");
+                    return Err(RegisterError::Unavailable);
+                }
+            },
+            (None, Liveness::Lease(ttl)) => (Uuid::new_v4(), Some(Instant::now() + ttl)),
+        };
+        for old in replaced {
+            let detail = lock.remove(&old).unwrap();
+            if inner.hub_handle.remove_target(old).is_err() {
+                info!("remove target from hub failed because hub was destroyed");
+            }
+            close_streams(&mut inner.streams, old);
+            info!("service:{:?} replaced by {:?}", detail, service);
+            notify_watchers(
+                &mut inner.watchers,
+                ServiceEvent::Dropped(detail.service.clone()),
+            );
+            let reason = DropReason::Replaced(service.clone());
+            dispatch(&inner.callbacks, Callback::Droped(detail.service, reason));
+        }
+        if returning.is_some() {
+            // nobody is told the instance left, it keeps its uuid. the hub counts the
+            // failures of a retargeted heartbeat port from 0 again, so does the table,
+            // or it would drop the entry while the hub keeps the target.
+            let mut detail = lock.get(&uuid).unwrap().clone();
+            info!("service:{:?} came back as {:?}", detail.service, service);
+            let retargeted = !leased && detail.service.heartbeat_addr() != service.heartbeat_addr();
+            let from = detail.health;
+            let old = mem::replace(&mut detail.service, service.clone());
+            detail.expires = expires;
+            if retargeted {
+                detail.failures = 0;
+                detail.health = Health::Healthy;
+            }
+            lock.insert(detail);
+            save_snapshot(inner.snapshot.as_ref(), lock);
+            if old != service {
+                let event = ServiceEvent::Updated(old.clone(), service.clone());
+                notify_watchers(&mut inner.watchers, event);
+                dispatch(&inner.callbacks, Callback::Updated(old, service.clone()));
+            }
+            if retargeted && from != Health::Healthy {
+                let change = HealthChange::new(from, Health::Healthy, None);
+                notify_health_changed(&mut inner.watchers, &inner.callbacks, service, change);
+            }
+            return Ok(uuid);
+        }
+        let mut detail = ServiceDetail::new(service.clone(), uuid);
+        detail.expires = expires;
+        lock.insert(detail);
+        save_snapshot(inner.snapshot.as_ref(), lock);
+        notify_watchers(&mut inner.watchers, ServiceEvent::Added(service.clone()));
+        dispatch(&inner.callbacks, Callback::Available(service));
+        Ok(uuid)
+    }
+
+    // whether the instance may take over its entry `uuid` in place, the hub target
+    // follows its heartbeat port. one moving between heartbeats and a lease, or
+    // whose target the hub just gave up, gets a new entry.
+    fn take_over(
+        uuid: Uuid,
+        service: &Service,
+        leased: bool,
+        table: &ServiceTable,
+        inner: &Inner<P, Q>,
+    ) -> Result<bool, RegisterError> {
+        let detail = table.get(&uuid).unwrap();
+        if detail.expires.is_some() != leased {
+            return Ok(false);
+        }
+        if leased || detail.service.heartbeat_addr() == service.heartbeat_addr() {
+            return Ok(true);
+        }
+        match inner.hub_handle.retarget(uuid, heartbeat_addr(service)) {
+            Ok(retargeted) => Ok(retargeted),
+            Err(_) => {
+                info!("retarget in hub failed because hub was destroyed");
+                Err(RegisterError::Unavailable)
+            }
+        }
+    }
+
+    fn add_target(addr: SocketAddr, inner: &Inner<P, Q>) -> Option<Uuid> {
+        let sender = inner.sender.clone();
+        let f = move |uuid, res| {
+            let msg = Message::Heartbeat(uuid, res);
+            sender.send(msg).unwrap();
+        };
+        let target = TargetBuilder::new(&addr)
+            .max_failures(inner.max_failures)
+            .epoch(inner.epoch)
+            .cb(f)
+            .build()
+            .unwrap();
+        inner.hub_handle.add_target(target).ok()
+    }
+
+    fn expire_leases(inner: &mut Inner<P, Q>) {
+        let mut lock = inner.services.lock().unwrap();
+        let expired = lock.expired(Instant::now());
+        if expired.is_empty() {
+            return;
+        }
+        for uuid in expired {
+            let detail = lock.remove(&uuid).unwrap();
+            close_streams(&mut inner.streams, uuid);
+            warn!("lease of service:{:?} expired, remove this service", detail);
+            let change = HealthChange::new(detail.health, Health::Down, None);
+            notify_health_changed(
+                &mut inner.watchers,
+                &inner.callbacks,
+                detail.service.clone(),
+                change,
+            );
+            notify_watchers(
+                &mut inner.watchers,
+                ServiceEvent::Dropped(detail.service.clone()),
+            );
+            dispatch(
+                &inner.callbacks,
+                Callback::Droped(detail.service, DropReason::LeaseExpired),
+            );
+        }
+        save_snapshot(inner.snapshot.as_ref(), lock);
+    }
+
+    fn handle_stream(event: StreamEvent, inner: &mut Inner<P, Q>) {
+        // the events only come if session streams are served.
+        let ttl = match inner.stream_ttl {
+            Some(ttl) => ttl,
+            None => return,
+        };
+        match event {
+            StreamEvent::Opened(id, service, commands) => {
+                match Self::insert_service(service, Liveness::Lease(ttl), inner) {
+                    Ok(uuid) => {
+                        // the container came back on a new stream before the old one
+                        // was closed.
+                        close_streams(&mut inner.streams, uuid);
+                        renew_stream(&inner.services, uuid, ttl);
+                        let _ = commands.unbounded_send(Command::Accepted(ttl));
+                        inner.streams.insert(id, (uuid, commands));
+                    }
+                    Err(e) => {
+                        let _ = commands.unbounded_send(Command::Refused(e));
+                    }
+                }
+            }
+            StreamEvent::Pinged(id) => {
+                if let Some(&(uuid, _)) = inner.streams.get(&id) {
+                    renew_stream(&inner.services, uuid, ttl);
+                }
+            }
+            StreamEvent::Closed(id, graceful) => Self::remove_stream(id, graceful, inner),
+        }
+    }
+
+    fn remove_stream(id: u64, graceful: bool, inner: &mut Inner<P, Q>) {
+        // the service may have been dropped already, its stream was forgotten then.
+        let uuid = match inner.streams.remove(&id) {
+            Some((uuid, _)) => uuid,
+            None => return,
+        };
+        let mut lock = inner.services.lock().unwrap();
+        let detail = match lock.remove(&uuid) {
+            Some(detail) => detail,
+            None => return,
+        };
+        save_snapshot(inner.snapshot.as_ref(), lock);
+        let reason = if graceful {
+            info!("service:{:?} closed its session stream", detail);
+            DropReason::Graceful
+        } else {
+            warn!("session stream of service:{:?} broke, remove this service", detail);
+            let change = HealthChange::new(detail.health, Health::Down, None);
+            notify_health_changed(
+                &mut inner.watchers,
+                &inner.callbacks,
+                detail.service.clone(),
+                change,
+            );
+            DropReason::StreamBroken
+        };
+        notify_watchers(
+            &mut inner.watchers,
+            ServiceEvent::Dropped(detail.service.clone()),
+        );
+        dispatch(&inner.callbacks, Callback::Droped(detail.service, reason));
+    }
+}
+
+// every message on a session stream extends the lease of its service.
+fn renew_stream(services: &ServiceDetails, uuid: Uuid, ttl: Duration) {
+    if let Some(detail) = services.lock().unwrap().get_mut(&uuid) {
+        detail.expires = Some(Instant::now() + ttl);
+    }
+}
+
+// the commands sent down the streams of the dropped service, the container
+// opens a new stream if it is still alive.
+fn close_streams(streams: &mut StreamMap, uuid: Uuid) {
+    streams.retain(|_, &mut (u, ref commands)| {
+        if u != uuid {
+            return true;
+        }
+        // the container may have closed the stream meanwhile.
+        let _ = commands.unbounded_send(Command::Evicted);
+        false
+    });
+}
+
+// ask the registry loop to expire the leases, until `stop` is dropped.
+fn lease_loop<Q>(sender: Sender<Message<Q>>, ttl: Duration, stop: Receiver<()>) {
+    let interval = cmp::max(ttl / 4, Duration::from_millis(10));
+    loop {
+        match stop.recv_timeout(interval) {
+            Err(RecvTimeoutError::Timeout) => {}
+            _ => return,
+        }
+        if sender.send(Message::CheckLeases).is_err() {
+            return;
+        }
+    }
+}
+
+// the entry of the instance itself if it came back, and the entries of the other
+// instances `service` replaces, or why it is refused.
+fn admit(
+    table: &ServiceTable,
+    service: &Service,
+    policy: ConflictPolicy,
+    max_instances: Option<usize>,
+) -> Result<(Option<Uuid>, Vec<Uuid>), RegisterError> {
+    let instances = table.details_by_id(service.sid);
+    let mut returning = None;
+    let mut replaced = Vec::new();
+    for detail in &instances {
+        let same_instance = if service.instance_id.is_empty() {
+            detail.service.heartbeat_addr() == service.heartbeat_addr()
+        } else {
+            detail.service.instance_id == service.instance_id
+        };
+        if same_instance && returning.is_none() {
+            returning = Some(detail.uuid);
+        } else if same_instance || policy == ConflictPolicy::ReplaceOlder {
+            replaced.push(detail.uuid);
+        } else if policy == ConflictPolicy::Reject {
+            return Err(RegisterError::Conflict(format!(
+                "service id {:?} is taken by {}",
+                service.sid,
+                detail.service.service_addr()
+            )));
+        }
+    }
+    let mut taken_over = replaced.clone();
+    taken_over.extend(returning);
+    let remaining = instances.len() - taken_over.len();
+    if let Some(max) = max_instances {
+        if remaining >= max {
+            return Err(RegisterError::QuotaExceeded(format!(
+                "service id {:?} has {} instances",
+                service.sid,
+                remaining
+            )));
+        }
+    }
+    // named ports are bound by the executor itself, they can not be moved like the
+    // allocated ones.
+    let used = table.ports_on_except(service.host, &taken_over);
+    if let Some(port) = service.ports.values().find(|port| used.contains(port)) {
+        return Err(RegisterError::Conflict(
+            format!("port {} is taken on {}", port, service.host),
+        ));
+    }
+    Ok((returning, replaced))
+}
+
+// only services checked by heartbeats come here, they all have a heartbeat port.
+fn heartbeat_addr(service: &Service) -> SocketAddr {
+    service.heartbeat_addr().expect("heartbeat port")
+}
+
+// the table is copied under the lock and written after the lock is released, so
+// lookups through `RegistryHandle` never wait for the fsync. only the registry loop
+// changes the table, the snapshots are still written in order.
+fn save_snapshot(snapshot: Option<&Snapshot>, table: MutexGuard<ServiceTable>) {
+    let snapshot = match snapshot {
+        Some(snapshot) => snapshot,
+        None => return,
+    };
+    let services = table.services();
+    drop(table);
+    if let Err(e) = snapshot.save(services) {
+        warn!(
+            "save snapshot {:?} failed, reason: {:?}",
+            snapshot.path(),
+            e
+        );
+    }
+}
+
+fn notify_health_changed(
+    watchers: &mut Vec<UnboundedSender<ServiceEvent>>,
+    callbacks: &Sender<Callback>,
+    service: Service,
+    change: HealthChange,
+) {
+    notify_watchers(
+        watchers,
+        ServiceEvent::HealthChanged(service.clone(), change.to),
+    );
+    dispatch(callbacks, Callback::HealthChanged(service, change));
+}
+
+// the dispatcher only stops after the registry loop, unless a callback panicked.
+fn dispatch(callbacks: &Sender<Callback>, callback: Callback) {
+    if callbacks.send(callback).is_err() {
+        warn!("dispatch callback failed because the dispatcher was gone");
+    }
+}
+
+// returns once the registry loop exits and drops its sender.
+fn dispatch_loop(receiver: Receiver<Callback>, handles: Handles) {
+    for callback in receiver {
+        match callback {
+            Callback::Available(service) => (handles.service_available_handle)(service),
+            Callback::Droped(service, reason) => (handles.service_droped_handle)(service, reason),
+            Callback::HealthChanged(service, change) => {
+                (handles.health_changed_handle)(service, change)
+            }
+            Callback::Updated(old, service) => (handles.service_updated_handle)(old, service),
+        }
+    }
+}
+
+// watchers whose receiving side was dropped are removed here.
+fn notify_watchers(watchers: &mut Vec<UnboundedSender<ServiceEvent>>, event: ServiceEvent) {
+    watchers.retain(|w| w.unbounded_send(event.clone()).is_ok());
+}
+
+enum Message<Q> {
+    Register(Service),
+    ReRegister(Service, Responder),
+    Heartbeat(Uuid, Result<Q, HeartbeatError>),
+    Watch(UnboundedSender<ServiceEvent>),
+    Deregister(Service),
+    CheckLeases,
+    Stream(StreamEvent),
+    Stop,
+}
+
+impl<P, Q> Drop for Registry<P, Q>
+where
+    P: ProtoMessage,
+    Q: MessageStatic,
+{
+    fn drop(&mut self) {
+        self.grpc_server.take().unwrap();
+        self.hub.take().unwrap();
+        if let Some((stop, handle)) = self.lease_reaper.take() {
+            drop(stop);
+            handle.join().unwrap();
+        }
+        self.sender.send(Message::Stop).unwrap();
+        self.thread_handle.take().unwrap().join().unwrap();
+        // callbacks queued before the stop are still delivered.
+        if self.dispatcher_handle.take().unwrap().join().is_err() {
+            warn!("a registry callback panicked");
+        }
+    }
+}
+
+impl RegistryHandle {
+    pub fn get_all_services(&self) -> Vec<Service> {
+        let lock = self.services.lock().unwrap();
+        lock.services()
+    }
+
+    pub fn get_services_by_id(&self, sid: ServiceId) -> Vec<Service> {
+        let lock = self.services.lock().unwrap();
+        lock.get_by_id(sid)
+    }
+
+    pub fn get_service_by_addr(&self, addr: SocketAddr) -> Option<Service> {
+        let lock = self.services.lock().unwrap();
+        lock.get_by_addr(&addr)
+    }
+
+    // the instance keeps its id when it registers again, e.g. after the registry
+    // lost it, its address may change though.
+    pub fn get_service_by_instance_id(&self, id: &InstanceId) -> Option<Service> {
+        let lock = self.services.lock().unwrap();
+        lock.get_by_instance_id(id)
+    }
+
+    pub fn get_services_by_meta(&self, meta: &str) -> Vec<Service> {
+        let lock = self.services.lock().unwrap();
+        lock.get_by_meta(meta)
+    }
+
+    pub fn get_service_health(&self, service: &Service) -> Option<Health> {
+        let lock = self.services.lock().unwrap();
+        lock.find(service)
+            .and_then(|uuid| lock.get(&uuid))
+            .map(|sd| sd.health)
+    }
+
+    pub fn get_services_by_selector(&self, selector: &Selector) -> Vec<Service> {
+        self.filter_services(|s| selector.matches(s.labels()))
+    }
+
+    pub fn filter_services<F>(&self, f: F) -> Vec<Service>
+    where
+        F: Fn(&Service) -> bool,
+    {
+        let lock = self.services.lock().unwrap();
+        lock.filter(f)
+    }
+}
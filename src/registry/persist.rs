@@ -0,0 +1,108 @@
+use std::io::{self, BufReader, BufWriter, Write};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use serde_json;
+
+use super::super::Service;
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct SnapshotData {
+    services: Vec<Service>,
+}
+
+// on-disk snapshot of the service table, the whole table is rewritten on every
+// change, it goes to a temporary file first and is then renamed over the old one,
+// so a crash in the middle of a write never leaves a truncated snapshot behind.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    path: PathBuf,
+}
+
+impl Snapshot {
+    pub fn new<T: Into<PathBuf>>(path: T) -> Self {
+        Snapshot { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // a missing snapshot file is treated as an empty table.
+    pub fn load(&self) -> io::Result<Vec<Service>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let data: SnapshotData = serde_json::from_reader(BufReader::new(file))?;
+        Ok(data.services)
+    }
+
+    pub fn save(&self, services: Vec<Service>) -> io::Result<()> {
+        let tmp = self.tmp_path();
+        {
+            let mut writer = BufWriter::new(File::create(&tmp)?);
+            let data = SnapshotData { services: services };
+            serde_json::to_writer(&mut writer, &data)?;
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+        fs::rename(&tmp, &self.path)
+    }
+
+    fn tmp_path(&self) -> PathBuf {
+        let mut name = self.path
+            .file_name()
+            .map(|s| s.to_os_string())
+            .unwrap_or_default();
+        name.push(".tmp");
+        self.path.with_file_name(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::net::IpAddr;
+
+    use super::Snapshot;
+    use super::super::super::{Service, ServiceId};
+
+    fn service(sid: u64, port: u16) -> Service {
+        Service {
+            sid: ServiceId(sid),
+            instance_id: Default::default(),
+            meta: "meta".to_string(),
+            labels: Default::default(),
+            host: "127.0.0.1".parse::<IpAddr>().unwrap(),
+            service_port: port,
+            heartbeat_port: port + 5_000,
+            ports: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let path = env::temp_dir().join("service_registry_test_snapshot.json");
+        let _ = fs::remove_file(&path);
+        let snapshot = Snapshot::new(path.clone());
+        assert_eq!(snapshot.load().unwrap(), vec![]);
+
+        let services = vec![service(1, 20_000), service(2, 20_001)];
+        snapshot.save(services.clone()).unwrap();
+        assert_eq!(snapshot.load().unwrap(), services);
+
+        snapshot.save(vec![]).unwrap();
+        assert_eq!(snapshot.load().unwrap(), vec![]);
+
+        File::create(&path)
+            .unwrap()
+            .write_all(b"not a snapshot")
+            .unwrap();
+        assert!(snapshot.load().is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,161 @@
+use std::net::{SocketAddr, TcpStream};
+use std::collections::HashMap;
+use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{self, Sender, Receiver};
+use std::thread;
+
+use rpc_server::{RegisterError, Responder};
+use super::super::Service;
+
+// a fleet re-registering after a restart is probed by these threads only.
+const PROBE_THREADS: usize = 4;
+
+type Waiters = Arc<Mutex<HashMap<SocketAddr, Vec<(Service, Responder)>>>>;
+type Done = Arc<Fn(Service, Responder, Result<(), RegisterError>) + Send + Sync + 'static>;
+
+// probes the heartbeat ports of re-registering services on a few threads, the
+// services waiting on the same port share one probe. `done` gets the result of
+// every service, the threads exit once all the clones are dropped.
+#[derive(Clone)]
+pub struct Prober {
+    jobs: Sender<SocketAddr>,
+    waiters: Waiters,
+    done: Done,
+}
+
+impl Prober {
+    pub fn new<F>(timeout: Duration, done: F) -> Self
+    where
+        F: Fn(Service, Responder, Result<(), RegisterError>) + Send + Sync + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        let jobs = Arc::new(Mutex::new(rx));
+        let waiters: Waiters = Default::default();
+        let done: Done = Arc::new(done);
+        for _ in 0..PROBE_THREADS {
+            let jobs = Arc::clone(&jobs);
+            let waiters = Arc::clone(&waiters);
+            let done = Arc::clone(&done);
+            thread::Builder::new()
+                .name("registry_probe".to_string())
+                .spawn(move || probe_loop(&jobs, &waiters, timeout, &done))
+                .unwrap();
+        }
+        Prober {
+            jobs: tx,
+            waiters: waiters,
+            done: done,
+        }
+    }
+
+    pub fn probe(&self, service: Service, responder: Responder) {
+        let addr = match service.heartbeat_addr() {
+            Some(addr) => addr,
+            None => {
+                let msg = format!("service:{:?} has no heartbeat port", service.sid);
+                return (self.done)(service, responder, Err(RegisterError::Unreachable(msg)));
+            }
+        };
+        let mut waiters = self.waiters.lock().unwrap();
+        let probing = waiters.contains_key(&addr);
+        waiters
+            .entry(addr)
+            .or_insert_with(Vec::new)
+            .push((service, responder));
+        // the threads only exit once this sender is dropped.
+        if !probing {
+            self.jobs.send(addr).unwrap();
+        }
+    }
+}
+
+fn probe_loop(
+    jobs: &Mutex<Receiver<SocketAddr>>,
+    waiters: &Waiters,
+    timeout: Duration,
+    done: &Done,
+) {
+    loop {
+        let addr = match jobs.lock().unwrap().recv() {
+            Ok(addr) => addr,
+            Err(_) => return,
+        };
+        let res = connect(addr, timeout);
+        // the services queued while it was probed are answered as well.
+        let waiting = waiters.lock().unwrap().remove(&addr).unwrap_or_default();
+        for (service, responder) in waiting {
+            done(service, responder, res.clone());
+        }
+    }
+}
+
+// the hub would drop a service whose heartbeat port can not be connected anyway.
+fn connect(addr: SocketAddr, timeout: Duration) -> Result<(), RegisterError> {
+    match TcpStream::connect_timeout(&addr, timeout) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(RegisterError::Unreachable(
+            format!("connect to {} failed: {}", addr, e),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::time::Duration;
+    use std::sync::mpsc;
+    use std::sync::Mutex;
+
+    use futures::Future;
+    use futures::sync::oneshot;
+
+    use super::Prober;
+    use super::super::super::{Service, ServiceId};
+
+    fn service(heartbeat_port: u16) -> Service {
+        Service {
+            sid: ServiceId(1),
+            instance_id: Default::default(),
+            meta: String::new(),
+            labels: Default::default(),
+            host: "127.0.0.1".parse().unwrap(),
+            service_port: 20_000,
+            heartbeat_port: heartbeat_port,
+            ports: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_prober() {
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        let prober = Prober::new(Duration::from_millis(200), move |s: Service, r, res| {
+            r.send(res.clone()).unwrap();
+            tx.lock().unwrap().send((s.heartbeat_port, res.is_ok())).unwrap();
+        });
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let unreachable = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+
+        let mut responses = Vec::new();
+        for &port in &[port, port, unreachable, 0] {
+            let (responder, response) = oneshot::channel();
+            prober.probe(service(port), responder);
+            responses.push(response);
+        }
+        let results = responses
+            .into_iter()
+            .map(|r| r.wait().unwrap().is_ok())
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![true, true, false, false]);
+        let mut done = rx.iter().take(4).collect::<Vec<_>>();
+        done.sort();
+        let mut expected = vec![(port, true), (port, true), (unreachable, false), (0, false)];
+        expected.sort();
+        assert_eq!(done, expected);
+    }
+}
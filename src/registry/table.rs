@@ -0,0 +1,371 @@
+use std::net::{SocketAddr, IpAddr};
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use uuid::Uuid;
+
+use super::super::{Service, ServiceId, InstanceId, Health};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ServiceDetail {
+    pub service: Service,
+    pub uuid: Uuid,
+    pub health: Health,
+    // consecutive failed heartbeats.
+    pub failures: u32,
+    // when the lease lapses, only in push mode or on a session stream.
+    pub expires: Option<Instant>,
+}
+
+impl ServiceDetail {
+    pub fn new(service: Service, uuid: Uuid) -> Self {
+        ServiceDetail {
+            service: service,
+            uuid: uuid,
+            health: Health::Healthy,
+            failures: 0,
+            expires: None,
+        }
+    }
+}
+
+// service table with secondary indexes on service id, service addr, meta and
+// instance id, all indexes must be updated together with `details`.
+#[derive(Default, Debug)]
+pub struct ServiceTable {
+    details: HashMap<Uuid, ServiceDetail>,
+    ids: HashMap<ServiceId, HashSet<Uuid>>,
+    addrs: HashMap<SocketAddr, HashSet<Uuid>>,
+    metas: HashMap<String, HashSet<Uuid>>,
+    // services without an instance id are not indexed.
+    instances: HashMap<InstanceId, HashSet<Uuid>>,
+}
+
+impl ServiceTable {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.details.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.details.is_empty()
+    }
+
+    pub fn contains(&self, service: &Service) -> bool {
+        self.find(service).is_some()
+    }
+
+    pub fn find(&self, service: &Service) -> Option<Uuid> {
+        self.addrs.get(&service.service_addr()).and_then(|uuids| {
+            uuids
+                .iter()
+                .filter_map(|uuid| self.details.get(uuid))
+                .find(|sd| sd.service == *service)
+                .map(|sd| sd.uuid)
+        })
+    }
+
+    pub fn get(&self, uuid: &Uuid) -> Option<&ServiceDetail> {
+        self.details.get(uuid)
+    }
+
+    // `service` must not be changed through the returned reference, the indexes
+    // would not be updated.
+    pub fn get_mut(&mut self, uuid: &Uuid) -> Option<&mut ServiceDetail> {
+        self.details.get_mut(uuid)
+    }
+
+    pub fn insert(&mut self, detail: ServiceDetail) -> Option<ServiceDetail> {
+        let uuid = detail.uuid;
+        let old = self.remove(&uuid);
+        self.ids
+            .entry(detail.service.sid)
+            .or_insert_with(HashSet::new)
+            .insert(uuid);
+        self.metas
+            .entry(detail.service.meta.clone())
+            .or_insert_with(HashSet::new)
+            .insert(uuid);
+        self.addrs
+            .entry(detail.service.service_addr())
+            .or_insert_with(HashSet::new)
+            .insert(uuid);
+        if !detail.service.instance_id.is_empty() {
+            self.instances
+                .entry(detail.service.instance_id.clone())
+                .or_insert_with(HashSet::new)
+                .insert(uuid);
+        }
+        self.details.insert(uuid, detail);
+        old
+    }
+
+    pub fn remove(&mut self, uuid: &Uuid) -> Option<ServiceDetail> {
+        let detail = match self.details.remove(uuid) {
+            Some(detail) => detail,
+            None => return None,
+        };
+        {
+            let service = &detail.service;
+            remove_from_index(&mut self.ids, &service.sid, uuid);
+            remove_from_index(&mut self.metas, &service.meta, uuid);
+            remove_from_index(&mut self.addrs, &service.service_addr(), uuid);
+            remove_from_index(&mut self.instances, &service.instance_id, uuid);
+        }
+        Some(detail)
+    }
+
+    pub fn details(&self) -> Vec<ServiceDetail> {
+        self.details.values().cloned().collect()
+    }
+
+    pub fn services(&self) -> Vec<Service> {
+        self.details.values().map(|sd| sd.service.clone()).collect()
+    }
+
+    pub fn get_by_id(&self, sid: ServiceId) -> Vec<Service> {
+        self.collect(self.ids.get(&sid))
+    }
+
+    // all the instances registered under `sid`.
+    pub fn details_by_id(&self, sid: ServiceId) -> Vec<ServiceDetail> {
+        self.ids
+            .get(&sid)
+            .map(|uuids| {
+                uuids
+                    .iter()
+                    .filter_map(|uuid| self.details.get(uuid))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_else(Vec::new)
+    }
+
+    // extend the lease of the instance on `host`, false if it is unknown.
+    pub fn renew(
+        &mut self,
+        sid: ServiceId,
+        instance_id: &InstanceId,
+        host: IpAddr,
+        expires: Instant,
+    ) -> bool {
+        let uuid = self.instances.get(instance_id).and_then(|uuids| {
+            uuids
+                .iter()
+                .find(|uuid| {
+                    let service = &self.details[*uuid].service;
+                    service.sid == sid && service.host == host
+                })
+                .cloned()
+        });
+        match uuid {
+            Some(uuid) => {
+                self.details.get_mut(&uuid).unwrap().expires = Some(expires);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // services whose lease lapsed before `now`.
+    pub fn expired(&self, now: Instant) -> Vec<Uuid> {
+        self.details
+            .values()
+            .filter(|sd| sd.expires.map_or(false, |t| t <= now))
+            .map(|sd| sd.uuid)
+            .collect()
+    }
+
+    // the instance registers once per service id, see `ConflictPolicy`.
+    pub fn get_by_instance_id(&self, instance_id: &InstanceId) -> Option<Service> {
+        self.instances
+            .get(instance_id)
+            .and_then(|uuids| uuids.iter().filter_map(|uuid| self.details.get(uuid)).next())
+            .map(|sd| sd.service.clone())
+    }
+
+    pub fn get_by_meta(&self, meta: &str) -> Vec<Service> {
+        self.collect(self.metas.get(meta))
+    }
+
+    // several services may share a service addr if only their heartbeat ports differ,
+    // in that case any one of them is returned.
+    pub fn get_by_addr(&self, addr: &SocketAddr) -> Option<Service> {
+        self.addrs
+            .get(addr)
+            .and_then(|uuids| uuids.iter().filter_map(|uuid| self.details.get(uuid)).next())
+            .map(|sd| sd.service.clone())
+    }
+
+    // both the service ports and the heartbeat ports taken on `host`.
+    pub fn ports_on(&self, host: IpAddr) -> HashSet<u16> {
+        self.ports_on_except(host, &[])
+    }
+
+    // the ports taken on `host` by the entries besides `except`, named ports
+    // included.
+    pub fn ports_on_except(&self, host: IpAddr, except: &[Uuid]) -> HashSet<u16> {
+        let mut ports = HashSet::new();
+        let details = self.details
+            .values()
+            .filter(|sd| sd.service.host == host && !except.contains(&sd.uuid));
+        for sd in details {
+            ports.insert(sd.service.service_port);
+            ports.insert(sd.service.heartbeat_port);
+            ports.extend(sd.service.ports.values().cloned());
+        }
+        ports
+    }
+
+    pub fn filter<F>(&self, f: F) -> Vec<Service>
+    where
+        F: Fn(&Service) -> bool,
+    {
+        self.details
+            .values()
+            .filter(|sd| f(&sd.service))
+            .map(|sd| sd.service.clone())
+            .collect()
+    }
+
+    fn collect(&self, uuids: Option<&HashSet<Uuid>>) -> Vec<Service> {
+        uuids
+            .map(|uuids| {
+                uuids
+                    .iter()
+                    .filter_map(|uuid| self.details.get(uuid))
+                    .map(|sd| sd.service.clone())
+                    .collect()
+            })
+            .unwrap_or_else(Vec::new)
+    }
+}
+
+fn remove_from_index<K>(index: &mut HashMap<K, HashSet<Uuid>>, key: &K, uuid: &Uuid)
+where
+    K: ::std::hash::Hash + Eq,
+{
+    let empty = match index.get_mut(key) {
+        Some(uuids) => {
+            uuids.remove(uuid);
+            uuids.is_empty()
+        }
+        None => false,
+    };
+    if empty {
+        index.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::time::{Duration, Instant};
+
+    use uuid::Uuid;
+
+    use super::{ServiceTable, ServiceDetail};
+    use super::super::super::{Service, ServiceId, InstanceId};
+
+    fn service(sid: u64, meta: &str, host: &str, port: u16) -> Service {
+        Service {
+            sid: ServiceId(sid),
+            instance_id: Default::default(),
+            meta: meta.to_string(),
+            labels: Default::default(),
+            host: host.parse::<IpAddr>().unwrap(),
+            service_port: port,
+            heartbeat_port: port + 5_000,
+            ports: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_service_table_index() {
+        let mut table = ServiceTable::new();
+        let mut a = service(1, "a", "127.0.0.1", 20_000);
+        a.instance_id = InstanceId("a".to_string());
+        let mut b = service(1, "b", "127.0.0.1", 20_001);
+        b.ports.insert("admin".to_string(), 23_000);
+        let c = service(2, "a", "127.0.0.2", 20_000);
+        let (ua, ub, uc) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        table.insert(ServiceDetail::new(a.clone(), ua));
+        table.insert(ServiceDetail::new(b.clone(), ub));
+        table.insert(ServiceDetail::new(c.clone(), uc));
+        assert_eq!(table.len(), 3);
+        assert!(table.contains(&a));
+        assert_eq!(table.find(&b), Some(ub));
+
+        let mut ids = table.get_by_id(ServiceId(1));
+        ids.sort_by_key(|s| s.service_port);
+        assert_eq!(ids, vec![a.clone(), b.clone()]);
+        assert_eq!(table.get_by_id(ServiceId(3)), vec![]);
+        assert_eq!(table.details_by_id(ServiceId(2)), vec![ServiceDetail::new(c.clone(), uc)]);
+        assert!(table.details_by_id(ServiceId(3)).is_empty());
+
+        assert_eq!(table.get_by_meta("b"), vec![b.clone()]);
+        assert_eq!(table.get_by_addr(&c.service_addr()), Some(c.clone()));
+        assert_eq!(table.get_by_instance_id(&a.instance_id), Some(a.clone()));
+        assert_eq!(table.get_by_instance_id(&b.instance_id), None);
+        assert_eq!(table.filter(|s| s.host == c.host), vec![c.clone()]);
+        let ports = table.ports_on(a.host);
+        assert_eq!(ports, [20_000, 20_001, 23_000, 25_000, 25_001].iter().cloned().collect());
+        let ports = table.ports_on_except(a.host, &[ub]);
+        assert_eq!(ports, [20_000, 25_000].iter().cloned().collect());
+
+        // an entry updated in place is indexed by its new service.
+        let mut moved = a.clone();
+        moved.service_port = 20_002;
+        assert!(table.insert(ServiceDetail::new(moved.clone(), ua)).is_some());
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get_by_addr(&a.service_addr()), None);
+        assert_eq!(table.get_by_instance_id(&a.instance_id), Some(moved.clone()));
+        let a = moved;
+
+        let removed = table.remove(&ua).unwrap();
+        assert_eq!(removed.service, a);
+        assert!(!table.contains(&a));
+        assert_eq!(table.get_by_id(ServiceId(1)), vec![b.clone()]);
+        assert_eq!(table.get_by_meta("a"), vec![c.clone()]);
+        assert_eq!(table.get_by_addr(&a.service_addr()), None);
+        assert_eq!(table.get_by_instance_id(&a.instance_id), None);
+        assert!(table.remove(&ua).is_none());
+
+        table.remove(&ub);
+        table.remove(&uc);
+        assert!(table.is_empty());
+        assert!(table.ids.is_empty());
+        assert!(table.metas.is_empty());
+        assert!(table.addrs.is_empty());
+        assert!(table.instances.is_empty());
+    }
+
+    #[test]
+    fn test_service_table_leases() {
+        let mut table = ServiceTable::new();
+        let mut a = service(1, "a", "127.0.0.1", 20_000);
+        a.instance_id = InstanceId("a".to_string());
+        let b = service(1, "b", "127.0.0.1", 20_001);
+        let (ua, ub) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Instant::now();
+        let mut detail = ServiceDetail::new(a.clone(), ua);
+        detail.expires = Some(now);
+        table.insert(detail);
+        table.insert(ServiceDetail::new(b.clone(), ub));
+        assert_eq!(table.expired(now), vec![ua]);
+
+        let later = now + Duration::from_secs(1);
+        assert!(table.renew(ServiceId(1), &a.instance_id, a.host, later));
+        assert!(table.expired(now).is_empty());
+        assert_eq!(table.expired(later), vec![ua]);
+        // unknown instances, services without an instance id, and other hosts.
+        assert!(!table.renew(ServiceId(2), &a.instance_id, a.host, later));
+        assert!(!table.renew(ServiceId(1), &InstanceId("c".to_string()), a.host, later));
+        assert!(!table.renew(ServiceId(1), &b.instance_id, b.host, later));
+        let other = "127.0.0.2".parse::<IpAddr>().unwrap();
+        assert!(!table.renew(ServiceId(1), &a.instance_id, other, later));
+    }
+}
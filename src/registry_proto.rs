@@ -26,6 +26,11 @@ pub struct RegisterRequest {
     // message fields
     pub service_id: u64,
     pub meta: ::std::string::String,
+    pub labels: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub service_port: u32,
+    pub heartbeat_port: u32,
+    pub advertised_host: ::std::string::String,
+    pub instance_id: ::std::string::String,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -105,6 +110,153 @@ impl RegisterRequest {
     fn mut_meta_for_reflect(&mut self) -> &mut ::std::string::String {
         &mut self.meta
     }
+
+    // repeated .register.RegisterRequest.LabelsEntry labels = 3;
+
+    pub fn clear_labels(&mut self) {
+        self.labels.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_labels(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.labels = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_labels(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.labels
+    }
+
+    // Take field
+    pub fn take_labels(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.labels, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_labels(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.labels
+    }
+
+    fn get_labels_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.labels
+    }
+
+    fn mut_labels_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.labels
+    }
+
+    // uint32 service_port = 4;
+
+    pub fn clear_service_port(&mut self) {
+        self.service_port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service_port(&mut self, v: u32) {
+        self.service_port = v;
+    }
+
+    pub fn get_service_port(&self) -> u32 {
+        self.service_port
+    }
+
+    fn get_service_port_for_reflect(&self) -> &u32 {
+        &self.service_port
+    }
+
+    fn mut_service_port_for_reflect(&mut self) -> &mut u32 {
+        &mut self.service_port
+    }
+
+    // uint32 heartbeat_port = 5;
+
+    pub fn clear_heartbeat_port(&mut self) {
+        self.heartbeat_port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_heartbeat_port(&mut self, v: u32) {
+        self.heartbeat_port = v;
+    }
+
+    pub fn get_heartbeat_port(&self) -> u32 {
+        self.heartbeat_port
+    }
+
+    fn get_heartbeat_port_for_reflect(&self) -> &u32 {
+        &self.heartbeat_port
+    }
+
+    fn mut_heartbeat_port_for_reflect(&mut self) -> &mut u32 {
+        &mut self.heartbeat_port
+    }
+
+    // string advertised_host = 6;
+
+    pub fn clear_advertised_host(&mut self) {
+        self.advertised_host.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_advertised_host(&mut self, v: ::std::string::String) {
+        self.advertised_host = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_advertised_host(&mut self) -> &mut ::std::string::String {
+        &mut self.advertised_host
+    }
+
+    // Take field
+    pub fn take_advertised_host(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.advertised_host, ::std::string::String::new())
+    }
+
+    pub fn get_advertised_host(&self) -> &str {
+        &self.advertised_host
+    }
+
+    fn get_advertised_host_for_reflect(&self) -> &::std::string::String {
+        &self.advertised_host
+    }
+
+    fn mut_advertised_host_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.advertised_host
+    }
+
+    // string instance_id = 7;
+
+    pub fn clear_instance_id(&mut self) {
+        self.instance_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_instance_id(&mut self, v: ::std::string::String) {
+        self.instance_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_instance_id(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
+
+    // Take field
+    pub fn take_instance_id(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.instance_id, ::std::string::String::new())
+    }
+
+    pub fn get_instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    fn get_instance_id_for_reflect(&self) -> &::std::string::String {
+        &self.instance_id
+    }
+
+    fn mut_instance_id_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
 }
 
 impl ::protobuf::Message for RegisterRequest {
@@ -126,6 +278,29 @@ impl ::protobuf::Message for RegisterRequest {
                 2 => {
                     ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.meta)?;
                 },
+                3 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.labels)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.service_port = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.heartbeat_port = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.advertised_host)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.instance_id)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -144,6 +319,19 @@ impl ::protobuf::Message for RegisterRequest {
         if !self.meta.is_empty() {
             my_size += ::protobuf::rt::string_size(2, &self.meta);
         }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(3, &self.labels);
+        if self.service_port != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.service_port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.heartbeat_port != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.heartbeat_port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.advertised_host.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.advertised_host);
+        }
+        if !self.instance_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.instance_id);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -156,6 +344,19 @@ impl ::protobuf::Message for RegisterRequest {
         if !self.meta.is_empty() {
             os.write_string(2, &self.meta)?;
         }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(3, &self.labels, os)?;
+        if self.service_port != 0 {
+            os.write_uint32(4, self.service_port)?;
+        }
+        if self.heartbeat_port != 0 {
+            os.write_uint32(5, self.heartbeat_port)?;
+        }
+        if !self.advertised_host.is_empty() {
+            os.write_string(6, &self.advertised_host)?;
+        }
+        if !self.instance_id.is_empty() {
+            os.write_string(7, &self.instance_id)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -210,6 +411,31 @@ impl ::protobuf::MessageStatic for RegisterRequest {
                     RegisterRequest::get_meta_for_reflect,
                     RegisterRequest::mut_meta_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                    "labels",
+                    RegisterRequest::get_labels_for_reflect,
+                    RegisterRequest::mut_labels_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "service_port",
+                    RegisterRequest::get_service_port_for_reflect,
+                    RegisterRequest::mut_service_port_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "heartbeat_port",
+                    RegisterRequest::get_heartbeat_port_for_reflect,
+                    RegisterRequest::mut_heartbeat_port_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "advertised_host",
+                    RegisterRequest::get_advertised_host_for_reflect,
+                    RegisterRequest::mut_advertised_host_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "instance_id",
+                    RegisterRequest::get_instance_id_for_reflect,
+                    RegisterRequest::mut_instance_id_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<RegisterRequest>(
                     "RegisterRequest",
                     fields,
@@ -224,6 +450,11 @@ impl ::protobuf::Clear for RegisterRequest {
     fn clear(&mut self) {
         self.clear_service_id();
         self.clear_meta();
+        self.clear_labels();
+        self.clear_service_port();
+        self.clear_heartbeat_port();
+        self.clear_advertised_host();
+        self.clear_instance_id();
         self.unknown_fields.clear();
     }
 }
@@ -246,6 +477,8 @@ pub struct RegisterResponse {
     pub heartbeat_port: u32,
     pub service_port: u32,
     pub session_id: u64,
+    pub registry_epoch: u32,
+    pub lease_ttl_ms: u32,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -337,6 +570,52 @@ impl RegisterResponse {
     fn mut_session_id_for_reflect(&mut self) -> &mut u64 {
         &mut self.session_id
     }
+
+    // uint32 registry_epoch = 4;
+
+    pub fn clear_registry_epoch(&mut self) {
+        self.registry_epoch = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_registry_epoch(&mut self, v: u32) {
+        self.registry_epoch = v;
+    }
+
+    pub fn get_registry_epoch(&self) -> u32 {
+        self.registry_epoch
+    }
+
+    fn get_registry_epoch_for_reflect(&self) -> &u32 {
+        &self.registry_epoch
+    }
+
+    fn mut_registry_epoch_for_reflect(&mut self) -> &mut u32 {
+        &mut self.registry_epoch
+    }
+
+    // uint32 lease_ttl_ms = 5;
+
+    pub fn clear_lease_ttl_ms(&mut self) {
+        self.lease_ttl_ms = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_lease_ttl_ms(&mut self, v: u32) {
+        self.lease_ttl_ms = v;
+    }
+
+    pub fn get_lease_ttl_ms(&self) -> u32 {
+        self.lease_ttl_ms
+    }
+
+    fn get_lease_ttl_ms_for_reflect(&self) -> &u32 {
+        &self.lease_ttl_ms
+    }
+
+    fn mut_lease_ttl_ms_for_reflect(&mut self) -> &mut u32 {
+        &mut self.lease_ttl_ms
+    }
 }
 
 impl ::protobuf::Message for RegisterResponse {
@@ -369,6 +648,20 @@ impl ::protobuf::Message for RegisterResponse {
                     let tmp = is.read_uint64()?;
                     self.session_id = tmp;
                 },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.registry_epoch = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.lease_ttl_ms = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -390,6 +683,12 @@ impl ::protobuf::Message for RegisterResponse {
         if self.session_id != 0 {
             my_size += ::protobuf::rt::value_size(3, self.session_id, ::protobuf::wire_format::WireTypeVarint);
         }
+        if self.registry_epoch != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.registry_epoch, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.lease_ttl_ms != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.lease_ttl_ms, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -405,6 +704,12 @@ impl ::protobuf::Message for RegisterResponse {
         if self.session_id != 0 {
             os.write_uint64(3, self.session_id)?;
         }
+        if self.registry_epoch != 0 {
+            os.write_uint32(4, self.registry_epoch)?;
+        }
+        if self.lease_ttl_ms != 0 {
+            os.write_uint32(5, self.lease_ttl_ms)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -464,6 +769,16 @@ impl ::protobuf::MessageStatic for RegisterResponse {
                     RegisterResponse::get_session_id_for_reflect,
                     RegisterResponse::mut_session_id_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "registry_epoch",
+                    RegisterResponse::get_registry_epoch_for_reflect,
+                    RegisterResponse::mut_registry_epoch_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "lease_ttl_ms",
+                    RegisterResponse::get_lease_ttl_ms_for_reflect,
+                    RegisterResponse::mut_lease_ttl_ms_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<RegisterResponse>(
                     "RegisterResponse",
                     fields,
@@ -479,6 +794,8 @@ impl ::protobuf::Clear for RegisterResponse {
         self.clear_heartbeat_port();
         self.clear_service_port();
         self.clear_session_id();
+        self.clear_registry_epoch();
+        self.clear_lease_ttl_ms();
         self.unknown_fields.clear();
     }
 }
@@ -501,6 +818,9 @@ pub struct StatusRequest {
     pub heartbeat_succeed: bool,
     pub service_succeed: bool,
     pub session_id: u64,
+    pub service_port: u32,
+    pub heartbeat_port: u32,
+    pub ports: ::std::collections::HashMap<::std::string::String, u32>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -592,6 +912,85 @@ impl StatusRequest {
     fn mut_session_id_for_reflect(&mut self) -> &mut u64 {
         &mut self.session_id
     }
+
+    // uint32 service_port = 4;
+
+    pub fn clear_service_port(&mut self) {
+        self.service_port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service_port(&mut self, v: u32) {
+        self.service_port = v;
+    }
+
+    pub fn get_service_port(&self) -> u32 {
+        self.service_port
+    }
+
+    fn get_service_port_for_reflect(&self) -> &u32 {
+        &self.service_port
+    }
+
+    fn mut_service_port_for_reflect(&mut self) -> &mut u32 {
+        &mut self.service_port
+    }
+
+    // uint32 heartbeat_port = 5;
+
+    pub fn clear_heartbeat_port(&mut self) {
+        self.heartbeat_port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_heartbeat_port(&mut self, v: u32) {
+        self.heartbeat_port = v;
+    }
+
+    pub fn get_heartbeat_port(&self) -> u32 {
+        self.heartbeat_port
+    }
+
+    fn get_heartbeat_port_for_reflect(&self) -> &u32 {
+        &self.heartbeat_port
+    }
+
+    fn mut_heartbeat_port_for_reflect(&mut self) -> &mut u32 {
+        &mut self.heartbeat_port
+    }
+
+    // repeated .register.StatusRequest.PortsEntry ports = 6;
+
+    pub fn clear_ports(&mut self) {
+        self.ports.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ports(&mut self, v: ::std::collections::HashMap<::std::string::String, u32>) {
+        self.ports = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ports(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u32> {
+        &mut self.ports
+    }
+
+    // Take field
+    pub fn take_ports(&mut self) -> ::std::collections::HashMap<::std::string::String, u32> {
+        ::std::mem::replace(&mut self.ports, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_ports(&self) -> &::std::collections::HashMap<::std::string::String, u32> {
+        &self.ports
+    }
+
+    fn get_ports_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, u32> {
+        &self.ports
+    }
+
+    fn mut_ports_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u32> {
+        &mut self.ports
+    }
 }
 
 impl ::protobuf::Message for StatusRequest {
@@ -624,11 +1023,28 @@ impl ::protobuf::Message for StatusRequest {
                     let tmp = is.read_uint64()?;
                     self.session_id = tmp;
                 },
-                _ => {
-                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
-                },
-            };
-        }
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.service_port = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.heartbeat_port = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(wire_type, is, &mut self.ports)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
         ::std::result::Result::Ok(())
     }
 
@@ -645,6 +1061,13 @@ impl ::protobuf::Message for StatusRequest {
         if self.session_id != 0 {
             my_size += ::protobuf::rt::value_size(3, self.session_id, ::protobuf::wire_format::WireTypeVarint);
         }
+        if self.service_port != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.service_port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.heartbeat_port != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.heartbeat_port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(6, &self.ports);
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -660,6 +1083,13 @@ impl ::protobuf::Message for StatusRequest {
         if self.session_id != 0 {
             os.write_uint64(3, self.session_id)?;
         }
+        if self.service_port != 0 {
+            os.write_uint32(4, self.service_port)?;
+        }
+        if self.heartbeat_port != 0 {
+            os.write_uint32(5, self.heartbeat_port)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(6, &self.ports, os)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -719,6 +1149,21 @@ impl ::protobuf::MessageStatic for StatusRequest {
                     StatusRequest::get_session_id_for_reflect,
                     StatusRequest::mut_session_id_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "service_port",
+                    StatusRequest::get_service_port_for_reflect,
+                    StatusRequest::mut_service_port_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "heartbeat_port",
+                    StatusRequest::get_heartbeat_port_for_reflect,
+                    StatusRequest::mut_heartbeat_port_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(
+                    "ports",
+                    StatusRequest::get_ports_for_reflect,
+                    StatusRequest::mut_ports_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<StatusRequest>(
                     "StatusRequest",
                     fields,
@@ -734,6 +1179,9 @@ impl ::protobuf::Clear for StatusRequest {
         self.clear_heartbeat_succeed();
         self.clear_service_succeed();
         self.clear_session_id();
+        self.clear_service_port();
+        self.clear_heartbeat_port();
+        self.clear_ports();
         self.unknown_fields.clear();
     }
 }
@@ -757,6 +1205,7 @@ pub struct StatusResponse {
     pub heartbeat_port: u32,
     pub service_port: u32,
     pub session_id: u64,
+    pub stale_session: bool,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -871,6 +1320,29 @@ impl StatusResponse {
     fn mut_session_id_for_reflect(&mut self) -> &mut u64 {
         &mut self.session_id
     }
+
+    // bool stale_session = 5;
+
+    pub fn clear_stale_session(&mut self) {
+        self.stale_session = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_stale_session(&mut self, v: bool) {
+        self.stale_session = v;
+    }
+
+    pub fn get_stale_session(&self) -> bool {
+        self.stale_session
+    }
+
+    fn get_stale_session_for_reflect(&self) -> &bool {
+        &self.stale_session
+    }
+
+    fn mut_stale_session_for_reflect(&mut self) -> &mut bool {
+        &mut self.stale_session
+    }
 }
 
 impl ::protobuf::Message for StatusResponse {
@@ -910,6 +1382,13 @@ impl ::protobuf::Message for StatusResponse {
                     let tmp = is.read_uint64()?;
                     self.session_id = tmp;
                 },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.stale_session = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -934,6 +1413,9 @@ impl ::protobuf::Message for StatusResponse {
         if self.session_id != 0 {
             my_size += ::protobuf::rt::value_size(4, self.session_id, ::protobuf::wire_format::WireTypeVarint);
         }
+        if self.stale_session != false {
+            my_size += 2;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -952,6 +1434,9 @@ impl ::protobuf::Message for StatusResponse {
         if self.session_id != 0 {
             os.write_uint64(4, self.session_id)?;
         }
+        if self.stale_session != false {
+            os.write_bool(5, self.stale_session)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1016,6 +1501,11 @@ impl ::protobuf::MessageStatic for StatusResponse {
                     StatusResponse::get_session_id_for_reflect,
                     StatusResponse::mut_session_id_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "stale_session",
+                    StatusResponse::get_stale_session_for_reflect,
+                    StatusResponse::mut_stale_session_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<StatusResponse>(
                     "StatusResponse",
                     fields,
@@ -1032,6 +1522,7 @@ impl ::protobuf::Clear for StatusResponse {
         self.clear_heartbeat_port();
         self.clear_service_port();
         self.clear_session_id();
+        self.clear_stale_session();
         self.unknown_fields.clear();
     }
 }
@@ -1055,6 +1546,10 @@ pub struct ReRegisterRequest {
     pub service_port: u32,
     pub service_id: u64,
     pub meta: ::std::string::String,
+    pub labels: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub ports: ::std::collections::HashMap<::std::string::String, u32>,
+    pub advertised_host: ::std::string::String,
+    pub instance_id: ::std::string::String,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -1180,6 +1675,140 @@ impl ReRegisterRequest {
     fn mut_meta_for_reflect(&mut self) -> &mut ::std::string::String {
         &mut self.meta
     }
+
+    // repeated .register.ReRegisterRequest.LabelsEntry labels = 5;
+
+    pub fn clear_labels(&mut self) {
+        self.labels.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_labels(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.labels = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_labels(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.labels
+    }
+
+    // Take field
+    pub fn take_labels(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.labels, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_labels(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.labels
+    }
+
+    fn get_labels_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.labels
+    }
+
+    fn mut_labels_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.labels
+    }
+
+    // repeated .register.ReRegisterRequest.PortsEntry ports = 6;
+
+    pub fn clear_ports(&mut self) {
+        self.ports.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ports(&mut self, v: ::std::collections::HashMap<::std::string::String, u32>) {
+        self.ports = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ports(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u32> {
+        &mut self.ports
+    }
+
+    // Take field
+    pub fn take_ports(&mut self) -> ::std::collections::HashMap<::std::string::String, u32> {
+        ::std::mem::replace(&mut self.ports, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_ports(&self) -> &::std::collections::HashMap<::std::string::String, u32> {
+        &self.ports
+    }
+
+    fn get_ports_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, u32> {
+        &self.ports
+    }
+
+    fn mut_ports_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u32> {
+        &mut self.ports
+    }
+
+    // string advertised_host = 7;
+
+    pub fn clear_advertised_host(&mut self) {
+        self.advertised_host.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_advertised_host(&mut self, v: ::std::string::String) {
+        self.advertised_host = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_advertised_host(&mut self) -> &mut ::std::string::String {
+        &mut self.advertised_host
+    }
+
+    // Take field
+    pub fn take_advertised_host(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.advertised_host, ::std::string::String::new())
+    }
+
+    pub fn get_advertised_host(&self) -> &str {
+        &self.advertised_host
+    }
+
+    fn get_advertised_host_for_reflect(&self) -> &::std::string::String {
+        &self.advertised_host
+    }
+
+    fn mut_advertised_host_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.advertised_host
+    }
+
+    // string instance_id = 8;
+
+    pub fn clear_instance_id(&mut self) {
+        self.instance_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_instance_id(&mut self, v: ::std::string::String) {
+        self.instance_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_instance_id(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
+
+    // Take field
+    pub fn take_instance_id(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.instance_id, ::std::string::String::new())
+    }
+
+    pub fn get_instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    fn get_instance_id_for_reflect(&self) -> &::std::string::String {
+        &self.instance_id
+    }
+
+    fn mut_instance_id_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
 }
 
 impl ::protobuf::Message for ReRegisterRequest {
@@ -1215,6 +1844,18 @@ impl ::protobuf::Message for ReRegisterRequest {
                 4 => {
                     ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.meta)?;
                 },
+                5 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.labels)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(wire_type, is, &mut self.ports)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.advertised_host)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.instance_id)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1239,6 +1880,14 @@ impl ::protobuf::Message for ReRegisterRequest {
         if !self.meta.is_empty() {
             my_size += ::protobuf::rt::string_size(4, &self.meta);
         }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(5, &self.labels);
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(6, &self.ports);
+        if !self.advertised_host.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.advertised_host);
+        }
+        if !self.instance_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(8, &self.instance_id);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1257,6 +1906,14 @@ impl ::protobuf::Message for ReRegisterRequest {
         if !self.meta.is_empty() {
             os.write_string(4, &self.meta)?;
         }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(5, &self.labels, os)?;
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(6, &self.ports, os)?;
+        if !self.advertised_host.is_empty() {
+            os.write_string(7, &self.advertised_host)?;
+        }
+        if !self.instance_id.is_empty() {
+            os.write_string(8, &self.instance_id)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1321,6 +1978,26 @@ impl ::protobuf::MessageStatic for ReRegisterRequest {
                     ReRegisterRequest::get_meta_for_reflect,
                     ReRegisterRequest::mut_meta_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                    "labels",
+                    ReRegisterRequest::get_labels_for_reflect,
+                    ReRegisterRequest::mut_labels_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(
+                    "ports",
+                    ReRegisterRequest::get_ports_for_reflect,
+                    ReRegisterRequest::mut_ports_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "advertised_host",
+                    ReRegisterRequest::get_advertised_host_for_reflect,
+                    ReRegisterRequest::mut_advertised_host_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "instance_id",
+                    ReRegisterRequest::get_instance_id_for_reflect,
+                    ReRegisterRequest::mut_instance_id_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<ReRegisterRequest>(
                     "ReRegisterRequest",
                     fields,
@@ -1337,6 +2014,10 @@ impl ::protobuf::Clear for ReRegisterRequest {
         self.clear_service_port();
         self.clear_service_id();
         self.clear_meta();
+        self.clear_labels();
+        self.clear_ports();
+        self.clear_advertised_host();
+        self.clear_instance_id();
         self.unknown_fields.clear();
     }
 }
@@ -1358,6 +2039,7 @@ pub struct ReRegisterResponse {
     // message fields
     pub succeed: bool,
     pub msg: ::std::string::String,
+    pub error: RegisterErrorKind,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -1437,6 +2119,29 @@ impl ReRegisterResponse {
     fn mut_msg_for_reflect(&mut self) -> &mut ::std::string::String {
         &mut self.msg
     }
+
+    // .register.RegisterErrorKind error = 3;
+
+    pub fn clear_error(&mut self) {
+        self.error = RegisterErrorKind::NONE;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_error(&mut self, v: RegisterErrorKind) {
+        self.error = v;
+    }
+
+    pub fn get_error(&self) -> RegisterErrorKind {
+        self.error
+    }
+
+    fn get_error_for_reflect(&self) -> &RegisterErrorKind {
+        &self.error
+    }
+
+    fn mut_error_for_reflect(&mut self) -> &mut RegisterErrorKind {
+        &mut self.error
+    }
 }
 
 impl ::protobuf::Message for ReRegisterResponse {
@@ -1458,6 +2163,13 @@ impl ::protobuf::Message for ReRegisterResponse {
                 2 => {
                     ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.msg)?;
                 },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_enum()?;
+                    self.error = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1476,6 +2188,9 @@ impl ::protobuf::Message for ReRegisterResponse {
         if !self.msg.is_empty() {
             my_size += ::protobuf::rt::string_size(2, &self.msg);
         }
+        if self.error != RegisterErrorKind::NONE {
+            my_size += ::protobuf::rt::enum_size(3, self.error);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1488,6 +2203,9 @@ impl ::protobuf::Message for ReRegisterResponse {
         if !self.msg.is_empty() {
             os.write_string(2, &self.msg)?;
         }
+        if self.error != RegisterErrorKind::NONE {
+            os.write_enum(3, self.error.value())?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1542,6 +2260,11 @@ impl ::protobuf::MessageStatic for ReRegisterResponse {
                     ReRegisterResponse::get_msg_for_reflect,
                     ReRegisterResponse::mut_msg_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<RegisterErrorKind>>(
+                    "error",
+                    ReRegisterResponse::get_error_for_reflect,
+                    ReRegisterResponse::mut_error_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<ReRegisterResponse>(
                     "ReRegisterResponse",
                     fields,
@@ -1556,6 +2279,7 @@ impl ::protobuf::Clear for ReRegisterResponse {
     fn clear(&mut self) {
         self.clear_succeed();
         self.clear_msg();
+        self.clear_error();
         self.unknown_fields.clear();
     }
 }
@@ -1572,108 +2296,3686 @@ impl ::protobuf::reflect::ProtobufValue for ReRegisterResponse {
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct DeregisterRequest {
+    // message fields
+    pub heartbeat_port: u32,
+    pub service_port: u32,
+    pub service_id: u64,
+    pub meta: ::std::string::String,
+    pub labels: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub ports: ::std::collections::HashMap<::std::string::String, u32>,
+    pub advertised_host: ::std::string::String,
+    pub instance_id: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for DeregisterRequest {}
+
+impl DeregisterRequest {
+    pub fn new() -> DeregisterRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static DeregisterRequest {
+        static mut instance: ::protobuf::lazy::Lazy<DeregisterRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const DeregisterRequest,
+        };
+        unsafe {
+            instance.get(DeregisterRequest::new)
+        }
+    }
+
+    // uint32 heartbeat_port = 1;
+
+    pub fn clear_heartbeat_port(&mut self) {
+        self.heartbeat_port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_heartbeat_port(&mut self, v: u32) {
+        self.heartbeat_port = v;
+    }
+
+    pub fn get_heartbeat_port(&self) -> u32 {
+        self.heartbeat_port
+    }
+
+    fn get_heartbeat_port_for_reflect(&self) -> &u32 {
+        &self.heartbeat_port
+    }
+
+    fn mut_heartbeat_port_for_reflect(&mut self) -> &mut u32 {
+        &mut self.heartbeat_port
+    }
+
+    // uint32 service_port = 2;
+
+    pub fn clear_service_port(&mut self) {
+        self.service_port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service_port(&mut self, v: u32) {
+        self.service_port = v;
+    }
+
+    pub fn get_service_port(&self) -> u32 {
+        self.service_port
+    }
+
+    fn get_service_port_for_reflect(&self) -> &u32 {
+        &self.service_port
+    }
+
+    fn mut_service_port_for_reflect(&mut self) -> &mut u32 {
+        &mut self.service_port
+    }
+
+    // uint64 service_id = 3;
+
+    pub fn clear_service_id(&mut self) {
+        self.service_id = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service_id(&mut self, v: u64) {
+        self.service_id = v;
+    }
+
+    pub fn get_service_id(&self) -> u64 {
+        self.service_id
+    }
+
+    fn get_service_id_for_reflect(&self) -> &u64 {
+        &self.service_id
+    }
+
+    fn mut_service_id_for_reflect(&mut self) -> &mut u64 {
+        &mut self.service_id
+    }
+
+    // string meta = 4;
+
+    pub fn clear_meta(&mut self) {
+        self.meta.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_meta(&mut self, v: ::std::string::String) {
+        self.meta = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_meta(&mut self) -> &mut ::std::string::String {
+        &mut self.meta
+    }
+
+    // Take field
+    pub fn take_meta(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.meta, ::std::string::String::new())
+    }
+
+    pub fn get_meta(&self) -> &str {
+        &self.meta
+    }
+
+    fn get_meta_for_reflect(&self) -> &::std::string::String {
+        &self.meta
+    }
+
+    fn mut_meta_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.meta
+    }
+
+    // repeated .register.DeregisterRequest.LabelsEntry labels = 5;
+
+    pub fn clear_labels(&mut self) {
+        self.labels.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_labels(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.labels = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_labels(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.labels
+    }
+
+    // Take field
+    pub fn take_labels(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.labels, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_labels(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.labels
+    }
+
+    fn get_labels_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.labels
+    }
+
+    fn mut_labels_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.labels
+    }
+
+    // repeated .register.DeregisterRequest.PortsEntry ports = 6;
+
+    pub fn clear_ports(&mut self) {
+        self.ports.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ports(&mut self, v: ::std::collections::HashMap<::std::string::String, u32>) {
+        self.ports = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ports(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u32> {
+        &mut self.ports
+    }
+
+    // Take field
+    pub fn take_ports(&mut self) -> ::std::collections::HashMap<::std::string::String, u32> {
+        ::std::mem::replace(&mut self.ports, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_ports(&self) -> &::std::collections::HashMap<::std::string::String, u32> {
+        &self.ports
+    }
+
+    fn get_ports_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, u32> {
+        &self.ports
+    }
+
+    fn mut_ports_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u32> {
+        &mut self.ports
+    }
+
+    // string advertised_host = 7;
+
+    pub fn clear_advertised_host(&mut self) {
+        self.advertised_host.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_advertised_host(&mut self, v: ::std::string::String) {
+        self.advertised_host = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_advertised_host(&mut self) -> &mut ::std::string::String {
+        &mut self.advertised_host
+    }
+
+    // Take field
+    pub fn take_advertised_host(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.advertised_host, ::std::string::String::new())
+    }
+
+    pub fn get_advertised_host(&self) -> &str {
+        &self.advertised_host
+    }
+
+    fn get_advertised_host_for_reflect(&self) -> &::std::string::String {
+        &self.advertised_host
+    }
+
+    fn mut_advertised_host_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.advertised_host
+    }
+
+    // string instance_id = 8;
+
+    pub fn clear_instance_id(&mut self) {
+        self.instance_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_instance_id(&mut self, v: ::std::string::String) {
+        self.instance_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_instance_id(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
+
+    // Take field
+    pub fn take_instance_id(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.instance_id, ::std::string::String::new())
+    }
+
+    pub fn get_instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    fn get_instance_id_for_reflect(&self) -> &::std::string::String {
+        &self.instance_id
+    }
+
+    fn mut_instance_id_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
+}
+
+impl ::protobuf::Message for DeregisterRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.heartbeat_port = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.service_port = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.service_id = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.meta)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.labels)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(wire_type, is, &mut self.ports)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.advertised_host)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.instance_id)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.heartbeat_port != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.heartbeat_port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.service_port != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.service_port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.service_id != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.service_id, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.meta.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.meta);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(5, &self.labels);
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(6, &self.ports);
+        if !self.advertised_host.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.advertised_host);
+        }
+        if !self.instance_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(8, &self.instance_id);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.heartbeat_port != 0 {
+            os.write_uint32(1, self.heartbeat_port)?;
+        }
+        if self.service_port != 0 {
+            os.write_uint32(2, self.service_port)?;
+        }
+        if self.service_id != 0 {
+            os.write_uint64(3, self.service_id)?;
+        }
+        if !self.meta.is_empty() {
+            os.write_string(4, &self.meta)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(5, &self.labels, os)?;
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(6, &self.ports, os)?;
+        if !self.advertised_host.is_empty() {
+            os.write_string(7, &self.advertised_host)?;
+        }
+        if !self.instance_id.is_empty() {
+            os.write_string(8, &self.instance_id)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for DeregisterRequest {
+    fn new() -> DeregisterRequest {
+        DeregisterRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<DeregisterRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "heartbeat_port",
+                    DeregisterRequest::get_heartbeat_port_for_reflect,
+                    DeregisterRequest::mut_heartbeat_port_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "service_port",
+                    DeregisterRequest::get_service_port_for_reflect,
+                    DeregisterRequest::mut_service_port_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "service_id",
+                    DeregisterRequest::get_service_id_for_reflect,
+                    DeregisterRequest::mut_service_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "meta",
+                    DeregisterRequest::get_meta_for_reflect,
+                    DeregisterRequest::mut_meta_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                    "labels",
+                    DeregisterRequest::get_labels_for_reflect,
+                    DeregisterRequest::mut_labels_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(
+                    "ports",
+                    DeregisterRequest::get_ports_for_reflect,
+                    DeregisterRequest::mut_ports_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "advertised_host",
+                    DeregisterRequest::get_advertised_host_for_reflect,
+                    DeregisterRequest::mut_advertised_host_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "instance_id",
+                    DeregisterRequest::get_instance_id_for_reflect,
+                    DeregisterRequest::mut_instance_id_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<DeregisterRequest>(
+                    "DeregisterRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for DeregisterRequest {
+    fn clear(&mut self) {
+        self.clear_heartbeat_port();
+        self.clear_service_port();
+        self.clear_service_id();
+        self.clear_meta();
+        self.clear_labels();
+        self.clear_ports();
+        self.clear_advertised_host();
+        self.clear_instance_id();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DeregisterRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DeregisterRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct DeregisterResponse {
+    // message fields
+    pub succeed: bool,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for DeregisterResponse {}
+
+impl DeregisterResponse {
+    pub fn new() -> DeregisterResponse {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static DeregisterResponse {
+        static mut instance: ::protobuf::lazy::Lazy<DeregisterResponse> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const DeregisterResponse,
+        };
+        unsafe {
+            instance.get(DeregisterResponse::new)
+        }
+    }
+
+    // bool succeed = 1;
+
+    pub fn clear_succeed(&mut self) {
+        self.succeed = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_succeed(&mut self, v: bool) {
+        self.succeed = v;
+    }
+
+    pub fn get_succeed(&self) -> bool {
+        self.succeed
+    }
+
+    fn get_succeed_for_reflect(&self) -> &bool {
+        &self.succeed
+    }
+
+    fn mut_succeed_for_reflect(&mut self) -> &mut bool {
+        &mut self.succeed
+    }
+}
+
+impl ::protobuf::Message for DeregisterResponse {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.succeed = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.succeed != false {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.succeed != false {
+            os.write_bool(1, self.succeed)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for DeregisterResponse {
+    fn new() -> DeregisterResponse {
+        DeregisterResponse::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<DeregisterResponse>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "succeed",
+                    DeregisterResponse::get_succeed_for_reflect,
+                    DeregisterResponse::mut_succeed_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<DeregisterResponse>(
+                    "DeregisterResponse",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for DeregisterResponse {
+    fn clear(&mut self) {
+        self.clear_succeed();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for DeregisterResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for DeregisterResponse {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ServiceInfo {
+    // message fields
+    pub service_id: u64,
+    pub meta: ::std::string::String,
+    pub host: ::std::string::String,
+    pub service_port: u32,
+    pub heartbeat_port: u32,
+    pub labels: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub ports: ::std::collections::HashMap<::std::string::String, u32>,
+    pub instance_id: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for ServiceInfo {}
+
+impl ServiceInfo {
+    pub fn new() -> ServiceInfo {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static ServiceInfo {
+        static mut instance: ::protobuf::lazy::Lazy<ServiceInfo> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ServiceInfo,
+        };
+        unsafe {
+            instance.get(ServiceInfo::new)
+        }
+    }
+
+    // uint64 service_id = 1;
+
+    pub fn clear_service_id(&mut self) {
+        self.service_id = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service_id(&mut self, v: u64) {
+        self.service_id = v;
+    }
+
+    pub fn get_service_id(&self) -> u64 {
+        self.service_id
+    }
+
+    fn get_service_id_for_reflect(&self) -> &u64 {
+        &self.service_id
+    }
+
+    fn mut_service_id_for_reflect(&mut self) -> &mut u64 {
+        &mut self.service_id
+    }
+
+    // string meta = 2;
+
+    pub fn clear_meta(&mut self) {
+        self.meta.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_meta(&mut self, v: ::std::string::String) {
+        self.meta = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_meta(&mut self) -> &mut ::std::string::String {
+        &mut self.meta
+    }
+
+    // Take field
+    pub fn take_meta(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.meta, ::std::string::String::new())
+    }
+
+    pub fn get_meta(&self) -> &str {
+        &self.meta
+    }
+
+    fn get_meta_for_reflect(&self) -> &::std::string::String {
+        &self.meta
+    }
+
+    fn mut_meta_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.meta
+    }
+
+    // string host = 3;
+
+    pub fn clear_host(&mut self) {
+        self.host.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_host(&mut self, v: ::std::string::String) {
+        self.host = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_host(&mut self) -> &mut ::std::string::String {
+        &mut self.host
+    }
+
+    // Take field
+    pub fn take_host(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.host, ::std::string::String::new())
+    }
+
+    pub fn get_host(&self) -> &str {
+        &self.host
+    }
+
+    fn get_host_for_reflect(&self) -> &::std::string::String {
+        &self.host
+    }
+
+    fn mut_host_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.host
+    }
+
+    // uint32 service_port = 4;
+
+    pub fn clear_service_port(&mut self) {
+        self.service_port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service_port(&mut self, v: u32) {
+        self.service_port = v;
+    }
+
+    pub fn get_service_port(&self) -> u32 {
+        self.service_port
+    }
+
+    fn get_service_port_for_reflect(&self) -> &u32 {
+        &self.service_port
+    }
+
+    fn mut_service_port_for_reflect(&mut self) -> &mut u32 {
+        &mut self.service_port
+    }
+
+    // uint32 heartbeat_port = 5;
+
+    pub fn clear_heartbeat_port(&mut self) {
+        self.heartbeat_port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_heartbeat_port(&mut self, v: u32) {
+        self.heartbeat_port = v;
+    }
+
+    pub fn get_heartbeat_port(&self) -> u32 {
+        self.heartbeat_port
+    }
+
+    fn get_heartbeat_port_for_reflect(&self) -> &u32 {
+        &self.heartbeat_port
+    }
+
+    fn mut_heartbeat_port_for_reflect(&mut self) -> &mut u32 {
+        &mut self.heartbeat_port
+    }
+
+    // repeated .register.ServiceInfo.LabelsEntry labels = 6;
+
+    pub fn clear_labels(&mut self) {
+        self.labels.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_labels(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.labels = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_labels(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.labels
+    }
+
+    // Take field
+    pub fn take_labels(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.labels, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_labels(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.labels
+    }
+
+    fn get_labels_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.labels
+    }
+
+    fn mut_labels_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.labels
+    }
+
+    // repeated .register.ServiceInfo.PortsEntry ports = 7;
+
+    pub fn clear_ports(&mut self) {
+        self.ports.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ports(&mut self, v: ::std::collections::HashMap<::std::string::String, u32>) {
+        self.ports = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ports(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u32> {
+        &mut self.ports
+    }
+
+    // Take field
+    pub fn take_ports(&mut self) -> ::std::collections::HashMap<::std::string::String, u32> {
+        ::std::mem::replace(&mut self.ports, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_ports(&self) -> &::std::collections::HashMap<::std::string::String, u32> {
+        &self.ports
+    }
+
+    fn get_ports_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, u32> {
+        &self.ports
+    }
+
+    fn mut_ports_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u32> {
+        &mut self.ports
+    }
+
+    // string instance_id = 8;
+
+    pub fn clear_instance_id(&mut self) {
+        self.instance_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_instance_id(&mut self, v: ::std::string::String) {
+        self.instance_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_instance_id(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
+
+    // Take field
+    pub fn take_instance_id(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.instance_id, ::std::string::String::new())
+    }
+
+    pub fn get_instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    fn get_instance_id_for_reflect(&self) -> &::std::string::String {
+        &self.instance_id
+    }
+
+    fn mut_instance_id_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
+}
+
+impl ::protobuf::Message for ServiceInfo {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.service_id = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.meta)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.host)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.service_port = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.heartbeat_port = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.labels)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(wire_type, is, &mut self.ports)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.instance_id)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.service_id != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.service_id, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.meta.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.meta);
+        }
+        if !self.host.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.host);
+        }
+        if self.service_port != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.service_port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.heartbeat_port != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.heartbeat_port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(6, &self.labels);
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(7, &self.ports);
+        if !self.instance_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(8, &self.instance_id);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.service_id != 0 {
+            os.write_uint64(1, self.service_id)?;
+        }
+        if !self.meta.is_empty() {
+            os.write_string(2, &self.meta)?;
+        }
+        if !self.host.is_empty() {
+            os.write_string(3, &self.host)?;
+        }
+        if self.service_port != 0 {
+            os.write_uint32(4, self.service_port)?;
+        }
+        if self.heartbeat_port != 0 {
+            os.write_uint32(5, self.heartbeat_port)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(6, &self.labels, os)?;
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(7, &self.ports, os)?;
+        if !self.instance_id.is_empty() {
+            os.write_string(8, &self.instance_id)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for ServiceInfo {
+    fn new() -> ServiceInfo {
+        ServiceInfo::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<ServiceInfo>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "service_id",
+                    ServiceInfo::get_service_id_for_reflect,
+                    ServiceInfo::mut_service_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "meta",
+                    ServiceInfo::get_meta_for_reflect,
+                    ServiceInfo::mut_meta_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "host",
+                    ServiceInfo::get_host_for_reflect,
+                    ServiceInfo::mut_host_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "service_port",
+                    ServiceInfo::get_service_port_for_reflect,
+                    ServiceInfo::mut_service_port_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "heartbeat_port",
+                    ServiceInfo::get_heartbeat_port_for_reflect,
+                    ServiceInfo::mut_heartbeat_port_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                    "labels",
+                    ServiceInfo::get_labels_for_reflect,
+                    ServiceInfo::mut_labels_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(
+                    "ports",
+                    ServiceInfo::get_ports_for_reflect,
+                    ServiceInfo::mut_ports_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "instance_id",
+                    ServiceInfo::get_instance_id_for_reflect,
+                    ServiceInfo::mut_instance_id_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ServiceInfo>(
+                    "ServiceInfo",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for ServiceInfo {
+    fn clear(&mut self) {
+        self.clear_service_id();
+        self.clear_meta();
+        self.clear_host();
+        self.clear_service_port();
+        self.clear_heartbeat_port();
+        self.clear_labels();
+        self.clear_ports();
+        self.clear_instance_id();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ServiceInfo {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ServiceInfo {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct WatchRequest {
+    // message fields
+    pub service_ids: ::std::vec::Vec<u64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for WatchRequest {}
+
+impl WatchRequest {
+    pub fn new() -> WatchRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static WatchRequest {
+        static mut instance: ::protobuf::lazy::Lazy<WatchRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const WatchRequest,
+        };
+        unsafe {
+            instance.get(WatchRequest::new)
+        }
+    }
+
+    // repeated uint64 service_ids = 1;
+
+    pub fn clear_service_ids(&mut self) {
+        self.service_ids.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service_ids(&mut self, v: ::std::vec::Vec<u64>) {
+        self.service_ids = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_service_ids(&mut self) -> &mut ::std::vec::Vec<u64> {
+        &mut self.service_ids
+    }
+
+    // Take field
+    pub fn take_service_ids(&mut self) -> ::std::vec::Vec<u64> {
+        ::std::mem::replace(&mut self.service_ids, ::std::vec::Vec::new())
+    }
+
+    pub fn get_service_ids(&self) -> &[u64] {
+        &self.service_ids
+    }
+
+    fn get_service_ids_for_reflect(&self) -> &::std::vec::Vec<u64> {
+        &self.service_ids
+    }
+
+    fn mut_service_ids_for_reflect(&mut self) -> &mut ::std::vec::Vec<u64> {
+        &mut self.service_ids
+    }
+}
+
+impl ::protobuf::Message for WatchRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_uint64_into(wire_type, is, &mut self.service_ids)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.service_ids.is_empty() {
+            my_size += ::protobuf::rt::vec_packed_varint_size(1, &self.service_ids);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.service_ids.is_empty() {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            // TODO: Data size is computed again, it should be cached
+            os.write_raw_varint32(::protobuf::rt::vec_packed_varint_data_size(&self.service_ids))?;
+            for v in &self.service_ids {
+                os.write_uint64_no_tag(*v)?;
+            };
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for WatchRequest {
+    fn new() -> WatchRequest {
+        WatchRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<WatchRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "service_ids",
+                    WatchRequest::get_service_ids_for_reflect,
+                    WatchRequest::mut_service_ids_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<WatchRequest>(
+                    "WatchRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for WatchRequest {
+    fn clear(&mut self) {
+        self.clear_service_ids();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for WatchRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for WatchRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct WatchEvent {
+    // message fields
+    pub kind: EventKind,
+    pub service: ::protobuf::SingularPtrField<ServiceInfo>,
+    pub health: HealthState,
+    pub old_service: ::protobuf::SingularPtrField<ServiceInfo>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for WatchEvent {}
+
+impl WatchEvent {
+    pub fn new() -> WatchEvent {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static WatchEvent {
+        static mut instance: ::protobuf::lazy::Lazy<WatchEvent> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const WatchEvent,
+        };
+        unsafe {
+            instance.get(WatchEvent::new)
+        }
+    }
+
+    // .register.EventKind kind = 1;
+
+    pub fn clear_kind(&mut self) {
+        self.kind = EventKind::ADDED;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_kind(&mut self, v: EventKind) {
+        self.kind = v;
+    }
+
+    pub fn get_kind(&self) -> EventKind {
+        self.kind
+    }
+
+    fn get_kind_for_reflect(&self) -> &EventKind {
+        &self.kind
+    }
+
+    fn mut_kind_for_reflect(&mut self) -> &mut EventKind {
+        &mut self.kind
+    }
+
+    // .register.ServiceInfo service = 2;
+
+    pub fn clear_service(&mut self) {
+        self.service.clear();
+    }
+
+    pub fn has_service(&self) -> bool {
+        self.service.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service(&mut self, v: ServiceInfo) {
+        self.service = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_service(&mut self) -> &mut ServiceInfo {
+        if self.service.is_none() {
+            self.service.set_default();
+        }
+        self.service.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_service(&mut self) -> ServiceInfo {
+        self.service.take().unwrap_or_else(|| ServiceInfo::new())
+    }
+
+    pub fn get_service(&self) -> &ServiceInfo {
+        self.service.as_ref().unwrap_or_else(|| ServiceInfo::default_instance())
+    }
+
+    fn get_service_for_reflect(&self) -> &::protobuf::SingularPtrField<ServiceInfo> {
+        &self.service
+    }
+
+    fn mut_service_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<ServiceInfo> {
+        &mut self.service
+    }
+
+    // .register.HealthState health = 3;
+
+    pub fn clear_health(&mut self) {
+        self.health = HealthState::HEALTHY;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_health(&mut self, v: HealthState) {
+        self.health = v;
+    }
+
+    pub fn get_health(&self) -> HealthState {
+        self.health
+    }
+
+    fn get_health_for_reflect(&self) -> &HealthState {
+        &self.health
+    }
+
+    fn mut_health_for_reflect(&mut self) -> &mut HealthState {
+        &mut self.health
+    }
+
+    // .register.ServiceInfo old_service = 4;
+
+    pub fn clear_old_service(&mut self) {
+        self.old_service.clear();
+    }
+
+    pub fn has_old_service(&self) -> bool {
+        self.old_service.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_old_service(&mut self, v: ServiceInfo) {
+        self.old_service = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_old_service(&mut self) -> &mut ServiceInfo {
+        if self.old_service.is_none() {
+            self.old_service.set_default();
+        }
+        self.old_service.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_old_service(&mut self) -> ServiceInfo {
+        self.old_service.take().unwrap_or_else(|| ServiceInfo::new())
+    }
+
+    pub fn get_old_service(&self) -> &ServiceInfo {
+        self.old_service.as_ref().unwrap_or_else(|| ServiceInfo::default_instance())
+    }
+
+    fn get_old_service_for_reflect(&self) -> &::protobuf::SingularPtrField<ServiceInfo> {
+        &self.old_service
+    }
+
+    fn mut_old_service_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<ServiceInfo> {
+        &mut self.old_service
+    }
+}
+
+impl ::protobuf::Message for WatchEvent {
+    fn is_initialized(&self) -> bool {
+        for v in &self.service {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.old_service {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_enum()?;
+                    self.kind = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.service)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_enum()?;
+                    self.health = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.old_service)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.kind != EventKind::ADDED {
+            my_size += ::protobuf::rt::enum_size(1, self.kind);
+        }
+        if let Some(ref v) = self.service.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if self.health != HealthState::HEALTHY {
+            my_size += ::protobuf::rt::enum_size(3, self.health);
+        }
+        if let Some(ref v) = self.old_service.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.kind != EventKind::ADDED {
+            os.write_enum(1, self.kind.value())?;
+        }
+        if let Some(ref v) = self.service.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if self.health != HealthState::HEALTHY {
+            os.write_enum(3, self.health.value())?;
+        }
+        if let Some(ref v) = self.old_service.as_ref() {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for WatchEvent {
+    fn new() -> WatchEvent {
+        WatchEvent::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<WatchEvent>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<EventKind>>(
+                    "kind",
+                    WatchEvent::get_kind_for_reflect,
+                    WatchEvent::mut_kind_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<ServiceInfo>>(
+                    "service",
+                    WatchEvent::get_service_for_reflect,
+                    WatchEvent::mut_service_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<HealthState>>(
+                    "health",
+                    WatchEvent::get_health_for_reflect,
+                    WatchEvent::mut_health_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<ServiceInfo>>(
+                    "old_service",
+                    WatchEvent::get_old_service_for_reflect,
+                    WatchEvent::mut_old_service_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<WatchEvent>(
+                    "WatchEvent",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for WatchEvent {
+    fn clear(&mut self) {
+        self.clear_kind();
+        self.clear_service();
+        self.clear_health();
+        self.clear_old_service();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for WatchEvent {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for WatchEvent {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct KeepAliveRequest {
+    // message fields
+    pub service_id: u64,
+    pub instance_id: ::std::string::String,
+    pub advertised_host: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for KeepAliveRequest {}
+
+impl KeepAliveRequest {
+    pub fn new() -> KeepAliveRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static KeepAliveRequest {
+        static mut instance: ::protobuf::lazy::Lazy<KeepAliveRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const KeepAliveRequest,
+        };
+        unsafe {
+            instance.get(KeepAliveRequest::new)
+        }
+    }
+
+    // uint64 service_id = 1;
+
+    pub fn clear_service_id(&mut self) {
+        self.service_id = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service_id(&mut self, v: u64) {
+        self.service_id = v;
+    }
+
+    pub fn get_service_id(&self) -> u64 {
+        self.service_id
+    }
+
+    fn get_service_id_for_reflect(&self) -> &u64 {
+        &self.service_id
+    }
+
+    fn mut_service_id_for_reflect(&mut self) -> &mut u64 {
+        &mut self.service_id
+    }
+
+    // string instance_id = 2;
+
+    pub fn clear_instance_id(&mut self) {
+        self.instance_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_instance_id(&mut self, v: ::std::string::String) {
+        self.instance_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_instance_id(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
+
+    // Take field
+    pub fn take_instance_id(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.instance_id, ::std::string::String::new())
+    }
+
+    pub fn get_instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    fn get_instance_id_for_reflect(&self) -> &::std::string::String {
+        &self.instance_id
+    }
+
+    fn mut_instance_id_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
+
+    // string advertised_host = 3;
+
+    pub fn clear_advertised_host(&mut self) {
+        self.advertised_host.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_advertised_host(&mut self, v: ::std::string::String) {
+        self.advertised_host = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_advertised_host(&mut self) -> &mut ::std::string::String {
+        &mut self.advertised_host
+    }
+
+    // Take field
+    pub fn take_advertised_host(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.advertised_host, ::std::string::String::new())
+    }
+
+    pub fn get_advertised_host(&self) -> &str {
+        &self.advertised_host
+    }
+
+    fn get_advertised_host_for_reflect(&self) -> &::std::string::String {
+        &self.advertised_host
+    }
+
+    fn mut_advertised_host_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.advertised_host
+    }
+}
+
+impl ::protobuf::Message for KeepAliveRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.service_id = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.instance_id)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.advertised_host)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.service_id != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.service_id, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.instance_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.instance_id);
+        }
+        if !self.advertised_host.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.advertised_host);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.service_id != 0 {
+            os.write_uint64(1, self.service_id)?;
+        }
+        if !self.instance_id.is_empty() {
+            os.write_string(2, &self.instance_id)?;
+        }
+        if !self.advertised_host.is_empty() {
+            os.write_string(3, &self.advertised_host)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for KeepAliveRequest {
+    fn new() -> KeepAliveRequest {
+        KeepAliveRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<KeepAliveRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "service_id",
+                    KeepAliveRequest::get_service_id_for_reflect,
+                    KeepAliveRequest::mut_service_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "instance_id",
+                    KeepAliveRequest::get_instance_id_for_reflect,
+                    KeepAliveRequest::mut_instance_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "advertised_host",
+                    KeepAliveRequest::get_advertised_host_for_reflect,
+                    KeepAliveRequest::mut_advertised_host_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<KeepAliveRequest>(
+                    "KeepAliveRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for KeepAliveRequest {
+    fn clear(&mut self) {
+        self.clear_service_id();
+        self.clear_instance_id();
+        self.clear_advertised_host();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for KeepAliveRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for KeepAliveRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct KeepAliveResponse {
+    // message fields
+    pub succeed: bool,
+    pub registry_epoch: u32,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for KeepAliveResponse {}
+
+impl KeepAliveResponse {
+    pub fn new() -> KeepAliveResponse {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static KeepAliveResponse {
+        static mut instance: ::protobuf::lazy::Lazy<KeepAliveResponse> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const KeepAliveResponse,
+        };
+        unsafe {
+            instance.get(KeepAliveResponse::new)
+        }
+    }
+
+    // bool succeed = 1;
+
+    pub fn clear_succeed(&mut self) {
+        self.succeed = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_succeed(&mut self, v: bool) {
+        self.succeed = v;
+    }
+
+    pub fn get_succeed(&self) -> bool {
+        self.succeed
+    }
+
+    fn get_succeed_for_reflect(&self) -> &bool {
+        &self.succeed
+    }
+
+    fn mut_succeed_for_reflect(&mut self) -> &mut bool {
+        &mut self.succeed
+    }
+
+    // uint32 registry_epoch = 2;
+
+    pub fn clear_registry_epoch(&mut self) {
+        self.registry_epoch = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_registry_epoch(&mut self, v: u32) {
+        self.registry_epoch = v;
+    }
+
+    pub fn get_registry_epoch(&self) -> u32 {
+        self.registry_epoch
+    }
+
+    fn get_registry_epoch_for_reflect(&self) -> &u32 {
+        &self.registry_epoch
+    }
+
+    fn mut_registry_epoch_for_reflect(&mut self) -> &mut u32 {
+        &mut self.registry_epoch
+    }
+}
+
+impl ::protobuf::Message for KeepAliveResponse {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.succeed = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.registry_epoch = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.succeed != false {
+            my_size += 2;
+        }
+        if self.registry_epoch != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.registry_epoch, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.succeed != false {
+            os.write_bool(1, self.succeed)?;
+        }
+        if self.registry_epoch != 0 {
+            os.write_uint32(2, self.registry_epoch)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for KeepAliveResponse {
+    fn new() -> KeepAliveResponse {
+        KeepAliveResponse::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<KeepAliveResponse>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "succeed",
+                    KeepAliveResponse::get_succeed_for_reflect,
+                    KeepAliveResponse::mut_succeed_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "registry_epoch",
+                    KeepAliveResponse::get_registry_epoch_for_reflect,
+                    KeepAliveResponse::mut_registry_epoch_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<KeepAliveResponse>(
+                    "KeepAliveResponse",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for KeepAliveResponse {
+    fn clear(&mut self) {
+        self.clear_succeed();
+        self.clear_registry_epoch();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for KeepAliveResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for KeepAliveResponse {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct SessionRequest {
+    // message fields
+    pub service_id: u64,
+    pub instance_id: ::std::string::String,
+    pub meta: ::std::string::String,
+    pub labels: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub service_port: u32,
+    pub ports: ::std::collections::HashMap<::std::string::String, u32>,
+    pub advertised_host: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for SessionRequest {}
+
+impl SessionRequest {
+    pub fn new() -> SessionRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static SessionRequest {
+        static mut instance: ::protobuf::lazy::Lazy<SessionRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const SessionRequest,
+        };
+        unsafe {
+            instance.get(SessionRequest::new)
+        }
+    }
+
+    // uint64 service_id = 1;
+
+    pub fn clear_service_id(&mut self) {
+        self.service_id = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service_id(&mut self, v: u64) {
+        self.service_id = v;
+    }
+
+    pub fn get_service_id(&self) -> u64 {
+        self.service_id
+    }
+
+    fn get_service_id_for_reflect(&self) -> &u64 {
+        &self.service_id
+    }
+
+    fn mut_service_id_for_reflect(&mut self) -> &mut u64 {
+        &mut self.service_id
+    }
+
+    // string instance_id = 2;
+
+    pub fn clear_instance_id(&mut self) {
+        self.instance_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_instance_id(&mut self, v: ::std::string::String) {
+        self.instance_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_instance_id(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
+
+    // Take field
+    pub fn take_instance_id(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.instance_id, ::std::string::String::new())
+    }
+
+    pub fn get_instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    fn get_instance_id_for_reflect(&self) -> &::std::string::String {
+        &self.instance_id
+    }
+
+    fn mut_instance_id_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.instance_id
+    }
+
+    // string meta = 3;
+
+    pub fn clear_meta(&mut self) {
+        self.meta.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_meta(&mut self, v: ::std::string::String) {
+        self.meta = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_meta(&mut self) -> &mut ::std::string::String {
+        &mut self.meta
+    }
+
+    // Take field
+    pub fn take_meta(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.meta, ::std::string::String::new())
+    }
+
+    pub fn get_meta(&self) -> &str {
+        &self.meta
+    }
+
+    fn get_meta_for_reflect(&self) -> &::std::string::String {
+        &self.meta
+    }
+
+    fn mut_meta_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.meta
+    }
+
+    // repeated .register.SessionRequest.LabelsEntry labels = 4;
+
+    pub fn clear_labels(&mut self) {
+        self.labels.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_labels(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.labels = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_labels(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.labels
+    }
+
+    // Take field
+    pub fn take_labels(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.labels, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_labels(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.labels
+    }
+
+    fn get_labels_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.labels
+    }
+
+    fn mut_labels_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.labels
+    }
+
+    // uint32 service_port = 5;
+
+    pub fn clear_service_port(&mut self) {
+        self.service_port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service_port(&mut self, v: u32) {
+        self.service_port = v;
+    }
+
+    pub fn get_service_port(&self) -> u32 {
+        self.service_port
+    }
+
+    fn get_service_port_for_reflect(&self) -> &u32 {
+        &self.service_port
+    }
+
+    fn mut_service_port_for_reflect(&mut self) -> &mut u32 {
+        &mut self.service_port
+    }
+
+    // repeated .register.SessionRequest.PortsEntry ports = 6;
+
+    pub fn clear_ports(&mut self) {
+        self.ports.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ports(&mut self, v: ::std::collections::HashMap<::std::string::String, u32>) {
+        self.ports = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ports(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u32> {
+        &mut self.ports
+    }
+
+    // Take field
+    pub fn take_ports(&mut self) -> ::std::collections::HashMap<::std::string::String, u32> {
+        ::std::mem::replace(&mut self.ports, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_ports(&self) -> &::std::collections::HashMap<::std::string::String, u32> {
+        &self.ports
+    }
+
+    fn get_ports_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, u32> {
+        &self.ports
+    }
+
+    fn mut_ports_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u32> {
+        &mut self.ports
+    }
+
+    // string advertised_host = 7;
+
+    pub fn clear_advertised_host(&mut self) {
+        self.advertised_host.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_advertised_host(&mut self, v: ::std::string::String) {
+        self.advertised_host = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_advertised_host(&mut self) -> &mut ::std::string::String {
+        &mut self.advertised_host
+    }
+
+    // Take field
+    pub fn take_advertised_host(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.advertised_host, ::std::string::String::new())
+    }
+
+    pub fn get_advertised_host(&self) -> &str {
+        &self.advertised_host
+    }
+
+    fn get_advertised_host_for_reflect(&self) -> &::std::string::String {
+        &self.advertised_host
+    }
+
+    fn mut_advertised_host_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.advertised_host
+    }
+}
+
+impl ::protobuf::Message for SessionRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.service_id = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.instance_id)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.meta)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.labels)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.service_port = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(wire_type, is, &mut self.ports)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.advertised_host)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.service_id != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.service_id, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.instance_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.instance_id);
+        }
+        if !self.meta.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.meta);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(4, &self.labels);
+        if self.service_port != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.service_port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(6, &self.ports);
+        if !self.advertised_host.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.advertised_host);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.service_id != 0 {
+            os.write_uint64(1, self.service_id)?;
+        }
+        if !self.instance_id.is_empty() {
+            os.write_string(2, &self.instance_id)?;
+        }
+        if !self.meta.is_empty() {
+            os.write_string(3, &self.meta)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(4, &self.labels, os)?;
+        if self.service_port != 0 {
+            os.write_uint32(5, self.service_port)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(6, &self.ports, os)?;
+        if !self.advertised_host.is_empty() {
+            os.write_string(7, &self.advertised_host)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for SessionRequest {
+    fn new() -> SessionRequest {
+        SessionRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<SessionRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "service_id",
+                    SessionRequest::get_service_id_for_reflect,
+                    SessionRequest::mut_service_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "instance_id",
+                    SessionRequest::get_instance_id_for_reflect,
+                    SessionRequest::mut_instance_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "meta",
+                    SessionRequest::get_meta_for_reflect,
+                    SessionRequest::mut_meta_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                    "labels",
+                    SessionRequest::get_labels_for_reflect,
+                    SessionRequest::mut_labels_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "service_port",
+                    SessionRequest::get_service_port_for_reflect,
+                    SessionRequest::mut_service_port_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint32>(
+                    "ports",
+                    SessionRequest::get_ports_for_reflect,
+                    SessionRequest::mut_ports_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "advertised_host",
+                    SessionRequest::get_advertised_host_for_reflect,
+                    SessionRequest::mut_advertised_host_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<SessionRequest>(
+                    "SessionRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for SessionRequest {
+    fn clear(&mut self) {
+        self.clear_service_id();
+        self.clear_instance_id();
+        self.clear_meta();
+        self.clear_labels();
+        self.clear_service_port();
+        self.clear_ports();
+        self.clear_advertised_host();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for SessionRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SessionRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct SessionResponse {
+    // message fields
+    pub command: SessionCommand,
+    pub registry_epoch: u32,
+    pub lease_ttl_ms: u32,
+    pub error: RegisterErrorKind,
+    pub msg: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for SessionResponse {}
+
+impl SessionResponse {
+    pub fn new() -> SessionResponse {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static SessionResponse {
+        static mut instance: ::protobuf::lazy::Lazy<SessionResponse> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const SessionResponse,
+        };
+        unsafe {
+            instance.get(SessionResponse::new)
+        }
+    }
+
+    // .register.SessionCommand command = 1;
+
+    pub fn clear_command(&mut self) {
+        self.command = SessionCommand::PONG;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_command(&mut self, v: SessionCommand) {
+        self.command = v;
+    }
+
+    pub fn get_command(&self) -> SessionCommand {
+        self.command
+    }
+
+    fn get_command_for_reflect(&self) -> &SessionCommand {
+        &self.command
+    }
+
+    fn mut_command_for_reflect(&mut self) -> &mut SessionCommand {
+        &mut self.command
+    }
+
+    // uint32 registry_epoch = 2;
+
+    pub fn clear_registry_epoch(&mut self) {
+        self.registry_epoch = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_registry_epoch(&mut self, v: u32) {
+        self.registry_epoch = v;
+    }
+
+    pub fn get_registry_epoch(&self) -> u32 {
+        self.registry_epoch
+    }
+
+    fn get_registry_epoch_for_reflect(&self) -> &u32 {
+        &self.registry_epoch
+    }
+
+    fn mut_registry_epoch_for_reflect(&mut self) -> &mut u32 {
+        &mut self.registry_epoch
+    }
+
+    // uint32 lease_ttl_ms = 3;
+
+    pub fn clear_lease_ttl_ms(&mut self) {
+        self.lease_ttl_ms = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_lease_ttl_ms(&mut self, v: u32) {
+        self.lease_ttl_ms = v;
+    }
+
+    pub fn get_lease_ttl_ms(&self) -> u32 {
+        self.lease_ttl_ms
+    }
+
+    fn get_lease_ttl_ms_for_reflect(&self) -> &u32 {
+        &self.lease_ttl_ms
+    }
+
+    fn mut_lease_ttl_ms_for_reflect(&mut self) -> &mut u32 {
+        &mut self.lease_ttl_ms
+    }
+
+    // .register.RegisterErrorKind error = 4;
+
+    pub fn clear_error(&mut self) {
+        self.error = RegisterErrorKind::NONE;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_error(&mut self, v: RegisterErrorKind) {
+        self.error = v;
+    }
+
+    pub fn get_error(&self) -> RegisterErrorKind {
+        self.error
+    }
+
+    fn get_error_for_reflect(&self) -> &RegisterErrorKind {
+        &self.error
+    }
+
+    fn mut_error_for_reflect(&mut self) -> &mut RegisterErrorKind {
+        &mut self.error
+    }
+
+    // string msg = 5;
+
+    pub fn clear_msg(&mut self) {
+        self.msg.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_msg(&mut self, v: ::std::string::String) {
+        self.msg = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_msg(&mut self) -> &mut ::std::string::String {
+        &mut self.msg
+    }
+
+    // Take field
+    pub fn take_msg(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.msg, ::std::string::String::new())
+    }
+
+    pub fn get_msg(&self) -> &str {
+        &self.msg
+    }
+
+    fn get_msg_for_reflect(&self) -> &::std::string::String {
+        &self.msg
+    }
+
+    fn mut_msg_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.msg
+    }
+}
+
+impl ::protobuf::Message for SessionResponse {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_enum()?;
+                    self.command = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.registry_epoch = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.lease_ttl_ms = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_enum()?;
+                    self.error = tmp;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.msg)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.command != SessionCommand::PONG {
+            my_size += ::protobuf::rt::enum_size(1, self.command);
+        }
+        if self.registry_epoch != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.registry_epoch, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.lease_ttl_ms != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.lease_ttl_ms, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.error != RegisterErrorKind::NONE {
+            my_size += ::protobuf::rt::enum_size(4, self.error);
+        }
+        if !self.msg.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.msg);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.command != SessionCommand::PONG {
+            os.write_enum(1, self.command.value())?;
+        }
+        if self.registry_epoch != 0 {
+            os.write_uint32(2, self.registry_epoch)?;
+        }
+        if self.lease_ttl_ms != 0 {
+            os.write_uint32(3, self.lease_ttl_ms)?;
+        }
+        if self.error != RegisterErrorKind::NONE {
+            os.write_enum(4, self.error.value())?;
+        }
+        if !self.msg.is_empty() {
+            os.write_string(5, &self.msg)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for SessionResponse {
+    fn new() -> SessionResponse {
+        SessionResponse::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<SessionResponse>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<SessionCommand>>(
+                    "command",
+                    SessionResponse::get_command_for_reflect,
+                    SessionResponse::mut_command_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "registry_epoch",
+                    SessionResponse::get_registry_epoch_for_reflect,
+                    SessionResponse::mut_registry_epoch_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "lease_ttl_ms",
+                    SessionResponse::get_lease_ttl_ms_for_reflect,
+                    SessionResponse::mut_lease_ttl_ms_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<RegisterErrorKind>>(
+                    "error",
+                    SessionResponse::get_error_for_reflect,
+                    SessionResponse::mut_error_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "msg",
+                    SessionResponse::get_msg_for_reflect,
+                    SessionResponse::mut_msg_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<SessionResponse>(
+                    "SessionResponse",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for SessionResponse {
+    fn clear(&mut self) {
+        self.clear_command();
+        self.clear_registry_epoch();
+        self.clear_lease_ttl_ms();
+        self.clear_error();
+        self.clear_msg();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for SessionResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SessionResponse {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum EventKind {
+    ADDED = 0,
+    DROPPED = 1,
+    HEALTH_CHANGED = 2,
+    UPDATED = 3,
+}
+
+impl ::protobuf::ProtobufEnum for EventKind {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<EventKind> {
+        match value {
+            0 => ::std::option::Option::Some(EventKind::ADDED),
+            1 => ::std::option::Option::Some(EventKind::DROPPED),
+            2 => ::std::option::Option::Some(EventKind::HEALTH_CHANGED),
+            3 => ::std::option::Option::Some(EventKind::UPDATED),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [EventKind] = &[
+            EventKind::ADDED,
+            EventKind::DROPPED,
+            EventKind::HEALTH_CHANGED,
+            EventKind::UPDATED,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static(_: ::std::option::Option<EventKind>) -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("EventKind", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for EventKind {
+}
+
+impl ::std::default::Default for EventKind {
+    fn default() -> Self {
+        EventKind::ADDED
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for EventKind {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum HealthState {
+    HEALTHY = 0,
+    SUSPECT = 1,
+    DOWN = 2,
+}
+
+impl ::protobuf::ProtobufEnum for HealthState {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<HealthState> {
+        match value {
+            0 => ::std::option::Option::Some(HealthState::HEALTHY),
+            1 => ::std::option::Option::Some(HealthState::SUSPECT),
+            2 => ::std::option::Option::Some(HealthState::DOWN),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [HealthState] = &[
+            HealthState::HEALTHY,
+            HealthState::SUSPECT,
+            HealthState::DOWN,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static(_: ::std::option::Option<HealthState>) -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("HealthState", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for HealthState {
+}
+
+impl ::std::default::Default for HealthState {
+    fn default() -> Self {
+        HealthState::HEALTHY
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for HealthState {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum RegisterErrorKind {
+    NONE = 0,
+    CONFLICT = 1,
+    QUOTA_EXCEEDED = 2,
+    UNREACHABLE = 3,
+    UNAVAILABLE = 4,
+}
+
+impl ::protobuf::ProtobufEnum for RegisterErrorKind {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<RegisterErrorKind> {
+        match value {
+            0 => ::std::option::Option::Some(RegisterErrorKind::NONE),
+            1 => ::std::option::Option::Some(RegisterErrorKind::CONFLICT),
+            2 => ::std::option::Option::Some(RegisterErrorKind::QUOTA_EXCEEDED),
+            3 => ::std::option::Option::Some(RegisterErrorKind::UNREACHABLE),
+            4 => ::std::option::Option::Some(RegisterErrorKind::UNAVAILABLE),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [RegisterErrorKind] = &[
+            RegisterErrorKind::NONE,
+            RegisterErrorKind::CONFLICT,
+            RegisterErrorKind::QUOTA_EXCEEDED,
+            RegisterErrorKind::UNREACHABLE,
+            RegisterErrorKind::UNAVAILABLE,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static(_: ::std::option::Option<RegisterErrorKind>) -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("RegisterErrorKind", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for RegisterErrorKind {
+}
+
+impl ::std::default::Default for RegisterErrorKind {
+    fn default() -> Self {
+        RegisterErrorKind::NONE
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RegisterErrorKind {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum SessionCommand {
+    PONG = 0,
+    ACCEPTED = 1,
+    REFUSED = 2,
+    EVICTED = 3,
+}
+
+impl ::protobuf::ProtobufEnum for SessionCommand {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<SessionCommand> {
+        match value {
+            0 => ::std::option::Option::Some(SessionCommand::PONG),
+            1 => ::std::option::Option::Some(SessionCommand::ACCEPTED),
+            2 => ::std::option::Option::Some(SessionCommand::REFUSED),
+            3 => ::std::option::Option::Some(SessionCommand::EVICTED),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [SessionCommand] = &[
+            SessionCommand::PONG,
+            SessionCommand::ACCEPTED,
+            SessionCommand::REFUSED,
+            SessionCommand::EVICTED,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static(_: ::std::option::Option<SessionCommand>) -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("SessionCommand", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for SessionCommand {
+}
+
+impl ::std::default::Default for SessionCommand {
+    fn default() -> Self {
+        SessionCommand::PONG
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SessionCommand {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x1aproto/registry_proto.proto\x12\x08register\"D\n\x0fRegisterRequest\
-    \x12\x1d\n\nservice_id\x18\x01\x20\x01(\x04R\tserviceId\x12\x12\n\x04met\
-    a\x18\x02\x20\x01(\tR\x04meta\"{\n\x10RegisterResponse\x12%\n\x0eheartbe\
-    at_port\x18\x01\x20\x01(\rR\rheartbeatPort\x12!\n\x0cservice_port\x18\
-    \x02\x20\x01(\rR\x0bservicePort\x12\x1d\n\nsession_id\x18\x03\x20\x01(\
-    \x04R\tsessionId\"\x84\x01\n\rStatusRequest\x12+\n\x11heartbeat_succeed\
-    \x18\x01\x20\x01(\x08R\x10heartbeatSucceed\x12'\n\x0fservice_succeed\x18\
-    \x02\x20\x01(\x08R\x0eserviceSucceed\x12\x1d\n\nsession_id\x18\x03\x20\
-    \x01(\x04R\tsessionId\"\x93\x01\n\x0eStatusResponse\x12\x18\n\x07succeed\
-    \x18\x01\x20\x01(\x08R\x07succeed\x12%\n\x0eheartbeat_port\x18\x02\x20\
-    \x01(\rR\rheartbeatPort\x12!\n\x0cservice_port\x18\x03\x20\x01(\rR\x0bse\
-    rvicePort\x12\x1d\n\nsession_id\x18\x04\x20\x01(\x04R\tsessionId\"\x90\
-    \x01\n\x11ReRegisterRequest\x12%\n\x0eheartbeat_port\x18\x01\x20\x01(\rR\
+    \n\x1aproto/registry_proto.proto\x12\x08register\"\xd2\x02\n\x0fRegister\
+    Request\x12\x1d\n\nservice_id\x18\x01\x20\x01(\x04R\tserviceId\x12\x12\n\
+    \x04meta\x18\x02\x20\x01(\tR\x04meta\x12=\n\x06labels\x18\x03\x20\x03(\
+    \x0b2%.register.RegisterRequest.LabelsEntryR\x06labels\x12!\n\x0cservice\
+    _port\x18\x04\x20\x01(\rR\x0bservicePort\x12%\n\x0eheartbeat_port\x18\
+    \x05\x20\x01(\rR\rheartbeatPort\x12'\n\x0fadvertised_host\x18\x06\x20\
+    \x01(\tR\x0eadvertisedHost\x12\x1f\n\x0binstance_id\x18\x07\x20\x01(\tR\
+    \ninstanceId\x1a9\n\x0bLabelsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\
+    \x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\"\xc4\
+    \x01\n\x10RegisterResponse\x12%\n\x0eheartbeat_port\x18\x01\x20\x01(\rR\
     \rheartbeatPort\x12!\n\x0cservice_port\x18\x02\x20\x01(\rR\x0bservicePor\
-    t\x12\x1d\n\nservice_id\x18\x03\x20\x01(\x04R\tserviceId\x12\x12\n\x04me\
-    ta\x18\x04\x20\x01(\tR\x04meta\"@\n\x12ReRegisterResponse\x12\x18\n\x07s\
-    ucceed\x18\x01\x20\x01(\x08R\x07succeed\x12\x10\n\x03msg\x18\x02\x20\x01\
-    (\tR\x03msg2\xdf\x01\n\x08Register\x12C\n\x08Register\x12\x19.register.R\
-    egisterRequest\x1a\x1a.register.RegisterResponse\"\0\x12C\n\x0cReportSta\
-    tus\x12\x17.register.StatusRequest\x1a\x18.register.StatusResponse\"\0\
-    \x12I\n\nReRegister\x12\x1b.register.ReRegisterRequest\x1a\x1c.register.\
-    ReRegisterResponse\"\0J\xd5\x0c\n\x06\x12\x04\0\0,\x01\n\x08\n\x01\x0c\
-    \x12\x03\0\0\x12\n\x08\n\x01\x02\x12\x03\x02\x08\x10\n\n\n\x02\x04\0\x12\
-    \x04\x04\0\x07\x01\n\n\n\x03\x04\0\x01\x12\x03\x04\x08\x17\n\x0b\n\x04\
-    \x04\0\x02\0\x12\x03\x05\x08\x1e\n\r\n\x05\x04\0\x02\0\x04\x12\x04\x05\
-    \x08\x04\x19\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x05\x08\x0e\n\x0c\n\x05\
-    \x04\0\x02\0\x01\x12\x03\x05\x0f\x19\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\
-    \x05\x1c\x1d\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x06\x08\x18\n\r\n\x05\x04\
-    \0\x02\x01\x04\x12\x04\x06\x08\x05\x1e\n\x0c\n\x05\x04\0\x02\x01\x05\x12\
-    \x03\x06\x08\x0e\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x06\x0f\x13\n\x0c\
-    \n\x05\x04\0\x02\x01\x03\x12\x03\x06\x16\x17\n\n\n\x02\x04\x01\x12\x04\t\
-    \0\r\x01\n\n\n\x03\x04\x01\x01\x12\x03\t\x08\x18\n\x0b\n\x04\x04\x01\x02\
-    \0\x12\x03\n\x08\"\n\r\n\x05\x04\x01\x02\0\x04\x12\x04\n\x08\t\x1a\n\x0c\
-    \n\x05\x04\x01\x02\0\x05\x12\x03\n\x08\x0e\n\x0c\n\x05\x04\x01\x02\0\x01\
-    \x12\x03\n\x0f\x1d\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\n\x20!\n\x0b\n\
-    \x04\x04\x01\x02\x01\x12\x03\x0b\x08\x20\n\r\n\x05\x04\x01\x02\x01\x04\
-    \x12\x04\x0b\x08\n\"\n\x0c\n\x05\x04\x01\x02\x01\x05\x12\x03\x0b\x08\x0e\
-    \n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\x0b\x0f\x1b\n\x0c\n\x05\x04\x01\
-    \x02\x01\x03\x12\x03\x0b\x1e\x1f\n\x0b\n\x04\x04\x01\x02\x02\x12\x03\x0c\
-    \x08\x1e\n\r\n\x05\x04\x01\x02\x02\x04\x12\x04\x0c\x08\x0b\x20\n\x0c\n\
-    \x05\x04\x01\x02\x02\x05\x12\x03\x0c\x08\x0e\n\x0c\n\x05\x04\x01\x02\x02\
-    \x01\x12\x03\x0c\x0f\x19\n\x0c\n\x05\x04\x01\x02\x02\x03\x12\x03\x0c\x1c\
-    \x1d\n\n\n\x02\x04\x02\x12\x04\x0f\0\x13\x01\n\n\n\x03\x04\x02\x01\x12\
-    \x03\x0f\x08\x15\n\x0b\n\x04\x04\x02\x02\0\x12\x03\x10\x08#\n\r\n\x05\
-    \x04\x02\x02\0\x04\x12\x04\x10\x08\x0f\x17\n\x0c\n\x05\x04\x02\x02\0\x05\
-    \x12\x03\x10\x08\x0c\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\x10\r\x1e\n\
-    \x0c\n\x05\x04\x02\x02\0\x03\x12\x03\x10!\"\n\x0b\n\x04\x04\x02\x02\x01\
-    \x12\x03\x11\x08!\n\r\n\x05\x04\x02\x02\x01\x04\x12\x04\x11\x08\x10#\n\
-    \x0c\n\x05\x04\x02\x02\x01\x05\x12\x03\x11\x08\x0c\n\x0c\n\x05\x04\x02\
-    \x02\x01\x01\x12\x03\x11\r\x1c\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03\
-    \x11\x1f\x20\n\x0b\n\x04\x04\x02\x02\x02\x12\x03\x12\x08\x1e\n\r\n\x05\
-    \x04\x02\x02\x02\x04\x12\x04\x12\x08\x11!\n\x0c\n\x05\x04\x02\x02\x02\
-    \x05\x12\x03\x12\x08\x0e\n\x0c\n\x05\x04\x02\x02\x02\x01\x12\x03\x12\x0f\
-    \x19\n\x0c\n\x05\x04\x02\x02\x02\x03\x12\x03\x12\x1c\x1d\n\n\n\x02\x04\
-    \x03\x12\x04\x15\0\x1a\x01\n\n\n\x03\x04\x03\x01\x12\x03\x15\x08\x16\n\
-    \x0b\n\x04\x04\x03\x02\0\x12\x03\x16\x08\x19\n\r\n\x05\x04\x03\x02\0\x04\
-    \x12\x04\x16\x08\x15\x18\n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03\x16\x08\
-    \x0c\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03\x16\r\x14\n\x0c\n\x05\x04\x03\
-    \x02\0\x03\x12\x03\x16\x17\x18\n\x0b\n\x04\x04\x03\x02\x01\x12\x03\x17\
-    \x08\"\n\r\n\x05\x04\x03\x02\x01\x04\x12\x04\x17\x08\x16\x19\n\x0c\n\x05\
-    \x04\x03\x02\x01\x05\x12\x03\x17\x08\x0e\n\x0c\n\x05\x04\x03\x02\x01\x01\
-    \x12\x03\x17\x0f\x1d\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\x03\x17\x20!\n\
-    \x0b\n\x04\x04\x03\x02\x02\x12\x03\x18\x08\x20\n\r\n\x05\x04\x03\x02\x02\
-    \x04\x12\x04\x18\x08\x17\"\n\x0c\n\x05\x04\x03\x02\x02\x05\x12\x03\x18\
-    \x08\x0e\n\x0c\n\x05\x04\x03\x02\x02\x01\x12\x03\x18\x0f\x1b\n\x0c\n\x05\
-    \x04\x03\x02\x02\x03\x12\x03\x18\x1e\x1f\n\x0b\n\x04\x04\x03\x02\x03\x12\
-    \x03\x19\x08\x1e\n\r\n\x05\x04\x03\x02\x03\x04\x12\x04\x19\x08\x18\x20\n\
-    \x0c\n\x05\x04\x03\x02\x03\x05\x12\x03\x19\x08\x0e\n\x0c\n\x05\x04\x03\
-    \x02\x03\x01\x12\x03\x19\x0f\x19\n\x0c\n\x05\x04\x03\x02\x03\x03\x12\x03\
-    \x19\x1c\x1d\n\n\n\x02\x04\x04\x12\x04\x1c\0!\x01\n\n\n\x03\x04\x04\x01\
-    \x12\x03\x1c\x08\x19\n\x0b\n\x04\x04\x04\x02\0\x12\x03\x1d\x08\"\n\r\n\
-    \x05\x04\x04\x02\0\x04\x12\x04\x1d\x08\x1c\x1b\n\x0c\n\x05\x04\x04\x02\0\
-    \x05\x12\x03\x1d\x08\x0e\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03\x1d\x0f\
-    \x1d\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x03\x1d\x20!\n\x0b\n\x04\x04\x04\
-    \x02\x01\x12\x03\x1e\x08\x20\n\r\n\x05\x04\x04\x02\x01\x04\x12\x04\x1e\
-    \x08\x1d\"\n\x0c\n\x05\x04\x04\x02\x01\x05\x12\x03\x1e\x08\x0e\n\x0c\n\
-    \x05\x04\x04\x02\x01\x01\x12\x03\x1e\x0f\x1b\n\x0c\n\x05\x04\x04\x02\x01\
-    \x03\x12\x03\x1e\x1e\x1f\n\x0b\n\x04\x04\x04\x02\x02\x12\x03\x1f\x08\x1e\
-    \n\r\n\x05\x04\x04\x02\x02\x04\x12\x04\x1f\x08\x1e\x20\n\x0c\n\x05\x04\
-    \x04\x02\x02\x05\x12\x03\x1f\x08\x0e\n\x0c\n\x05\x04\x04\x02\x02\x01\x12\
-    \x03\x1f\x0f\x19\n\x0c\n\x05\x04\x04\x02\x02\x03\x12\x03\x1f\x1c\x1d\n\
-    \x0b\n\x04\x04\x04\x02\x03\x12\x03\x20\x08\x18\n\r\n\x05\x04\x04\x02\x03\
-    \x04\x12\x04\x20\x08\x1f\x1e\n\x0c\n\x05\x04\x04\x02\x03\x05\x12\x03\x20\
-    \x08\x0e\n\x0c\n\x05\x04\x04\x02\x03\x01\x12\x03\x20\x0f\x13\n\x0c\n\x05\
-    \x04\x04\x02\x03\x03\x12\x03\x20\x16\x17\n\n\n\x02\x04\x05\x12\x04#\0&\
-    \x01\n\n\n\x03\x04\x05\x01\x12\x03#\x08\x1a\n\x0b\n\x04\x04\x05\x02\0\
-    \x12\x03$\x08\x19\n\r\n\x05\x04\x05\x02\0\x04\x12\x04$\x08#\x1c\n\x0c\n\
-    \x05\x04\x05\x02\0\x05\x12\x03$\x08\x0c\n\x0c\n\x05\x04\x05\x02\0\x01\
-    \x12\x03$\r\x14\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x03$\x17\x18\n\x0b\n\
-    \x04\x04\x05\x02\x01\x12\x03%\x08\x17\n\r\n\x05\x04\x05\x02\x01\x04\x12\
-    \x04%\x08$\x19\n\x0c\n\x05\x04\x05\x02\x01\x05\x12\x03%\x08\x0e\n\x0c\n\
-    \x05\x04\x05\x02\x01\x01\x12\x03%\x0f\x12\n\x0c\n\x05\x04\x05\x02\x01\
-    \x03\x12\x03%\x15\x16\n\n\n\x02\x06\0\x12\x04(\0,\x01\n\n\n\x03\x06\0\
-    \x01\x12\x03(\x08\x10\n\x0b\n\x04\x06\0\x02\0\x12\x03)\x08C\n\x0c\n\x05\
-    \x06\0\x02\0\x01\x12\x03)\x0c\x14\n\x0c\n\x05\x06\0\x02\0\x02\x12\x03)\
-    \x15$\n\x0c\n\x05\x06\0\x02\0\x03\x12\x03)/?\n\x0b\n\x04\x06\0\x02\x01\
-    \x12\x03*\x08C\n\x0c\n\x05\x06\0\x02\x01\x01\x12\x03*\x0c\x18\n\x0c\n\
-    \x05\x06\0\x02\x01\x02\x12\x03*\x19&\n\x0c\n\x05\x06\0\x02\x01\x03\x12\
-    \x03*1?\n\x0b\n\x04\x06\0\x02\x02\x12\x03+\x08I\n\x0c\n\x05\x06\0\x02\
-    \x02\x01\x12\x03+\x0c\x16\n\x0c\n\x05\x06\0\x02\x02\x02\x12\x03+\x17(\n\
-    \x0c\n\x05\x06\0\x02\x02\x03\x12\x03+3Eb\x06proto3\
+    t\x12\x1d\n\nsession_id\x18\x03\x20\x01(\x04R\tsessionId\x12%\n\x0eregis\
+    try_epoch\x18\x04\x20\x01(\rR\rregistryEpoch\x12\x20\n\x0clease_ttl_ms\
+    \x18\x05\x20\x01(\rR\nleaseTtlMs\"\xc2\x02\n\rStatusRequest\x12+\n\x11he\
+    artbeat_succeed\x18\x01\x20\x01(\x08R\x10heartbeatSucceed\x12'\n\x0fserv\
+    ice_succeed\x18\x02\x20\x01(\x08R\x0eserviceSucceed\x12\x1d\n\nsession_i\
+    d\x18\x03\x20\x01(\x04R\tsessionId\x12!\n\x0cservice_port\x18\x04\x20\
+    \x01(\rR\x0bservicePort\x12%\n\x0eheartbeat_port\x18\x05\x20\x01(\rR\rhe\
+    artbeatPort\x128\n\x05ports\x18\x06\x20\x03(\x0b2\".register.StatusReque\
+    st.PortsEntryR\x05ports\x1a8\n\nPortsEntry\x12\x10\n\x03key\x18\x01\x20\
+    \x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\rR\x05value:\x028\
+    \x01\"\xb8\x01\n\x0eStatusResponse\x12\x18\n\x07succeed\x18\x01\x20\x01(\
+    \x08R\x07succeed\x12%\n\x0eheartbeat_port\x18\x02\x20\x01(\rR\rheartbeat\
+    Port\x12!\n\x0cservice_port\x18\x03\x20\x01(\rR\x0bservicePort\x12\x1d\n\
+    \nsession_id\x18\x04\x20\x01(\x04R\tsessionId\x12#\n\rstale_session\x18\
+    \x05\x20\x01(\x08R\x0cstaleSession\"\xce\x03\n\x11ReRegisterRequest\x12%\
+    \n\x0eheartbeat_port\x18\x01\x20\x01(\rR\rheartbeatPort\x12!\n\x0cservic\
+    e_port\x18\x02\x20\x01(\rR\x0bservicePort\x12\x1d\n\nservice_id\x18\x03\
+    \x20\x01(\x04R\tserviceId\x12\x12\n\x04meta\x18\x04\x20\x01(\tR\x04meta\
+    \x12?\n\x06labels\x18\x05\x20\x03(\x0b2'.register.ReRegisterRequest.Labe\
+    lsEntryR\x06labels\x12<\n\x05ports\x18\x06\x20\x03(\x0b2&.register.ReReg\
+    isterRequest.PortsEntryR\x05ports\x12'\n\x0fadvertised_host\x18\x07\x20\
+    \x01(\tR\x0eadvertisedHost\x12\x1f\n\x0binstance_id\x18\x08\x20\x01(\tR\
+    \ninstanceId\x1a9\n\x0bLabelsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\
+    \x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\x1a8\n\
+    \nPortsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05va\
+    lue\x18\x02\x20\x01(\rR\x05value:\x028\x01\"s\n\x12ReRegisterResponse\
+    \x12\x18\n\x07succeed\x18\x01\x20\x01(\x08R\x07succeed\x12\x10\n\x03msg\
+    \x18\x02\x20\x01(\tR\x03msg\x121\n\x05error\x18\x03\x20\x01(\x0e2\x1b.re\
+    gister.RegisterErrorKindR\x05error\"\xce\x03\n\x11DeregisterRequest\x12%\
+    \n\x0eheartbeat_port\x18\x01\x20\x01(\rR\rheartbeatPort\x12!\n\x0cservic\
+    e_port\x18\x02\x20\x01(\rR\x0bservicePort\x12\x1d\n\nservice_id\x18\x03\
+    \x20\x01(\x04R\tserviceId\x12\x12\n\x04meta\x18\x04\x20\x01(\tR\x04meta\
+    \x12?\n\x06labels\x18\x05\x20\x03(\x0b2'.register.DeregisterRequest.Labe\
+    lsEntryR\x06labels\x12<\n\x05ports\x18\x06\x20\x03(\x0b2&.register.Dereg\
+    isterRequest.PortsEntryR\x05ports\x12'\n\x0fadvertised_host\x18\x07\x20\
+    \x01(\tR\x0eadvertisedHost\x12\x1f\n\x0binstance_id\x18\x08\x20\x01(\tR\
+    \ninstanceId\x1a9\n\x0bLabelsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\
+    \x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\x1a8\n\
+    \nPortsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05va\
+    lue\x18\x02\x20\x01(\rR\x05value:\x028\x01\".\n\x12DeregisterResponse\
+    \x12\x18\n\x07succeed\x18\x01\x20\x01(\x08R\x07succeed\"\xa7\x03\n\x0bSe\
+    rviceInfo\x12\x1d\n\nservice_id\x18\x01\x20\x01(\x04R\tserviceId\x12\x12\
+    \n\x04meta\x18\x02\x20\x01(\tR\x04meta\x12\x12\n\x04host\x18\x03\x20\x01\
+    (\tR\x04host\x12!\n\x0cservice_port\x18\x04\x20\x01(\rR\x0bservicePort\
+    \x12%\n\x0eheartbeat_port\x18\x05\x20\x01(\rR\rheartbeatPort\x129\n\x06l\
+    abels\x18\x06\x20\x03(\x0b2!.register.ServiceInfo.LabelsEntryR\x06labels\
+    \x126\n\x05ports\x18\x07\x20\x03(\x0b2\x20.register.ServiceInfo.PortsEnt\
+    ryR\x05ports\x12\x1f\n\x0binstance_id\x18\x08\x20\x01(\tR\ninstanceId\
+    \x1a9\n\x0bLabelsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\
+    \x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\x1a8\n\nPortsEntr\
+    y\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\
+    \x20\x01(\rR\x05value:\x028\x01\"/\n\x0cWatchRequest\x12\x1f\n\x0bservic\
+    e_ids\x18\x01\x20\x03(\x04R\nserviceIds\"\xcd\x01\n\nWatchEvent\x12'\n\
+    \x04kind\x18\x01\x20\x01(\x0e2\x13.register.EventKindR\x04kind\x12/\n\
+    \x07service\x18\x02\x20\x01(\x0b2\x15.register.ServiceInfoR\x07service\
+    \x12-\n\x06health\x18\x03\x20\x01(\x0e2\x15.register.HealthStateR\x06hea\
+    lth\x126\n\x0bold_service\x18\x04\x20\x01(\x0b2\x15.register.ServiceInfo\
+    R\noldService\"{\n\x10KeepAliveRequest\x12\x1d\n\nservice_id\x18\x01\x20\
+    \x01(\x04R\tserviceId\x12\x1f\n\x0binstance_id\x18\x02\x20\x01(\tR\ninst\
+    anceId\x12'\n\x0fadvertised_host\x18\x03\x20\x01(\tR\x0eadvertisedHost\"\
+    T\n\x11KeepAliveResponse\x12\x18\n\x07succeed\x18\x01\x20\x01(\x08R\x07s\
+    ucceed\x12%\n\x0eregistry_epoch\x18\x02\x20\x01(\rR\rregistryEpoch\"\x9e\
+    \x03\n\x0eSessionRequest\x12\x1d\n\nservice_id\x18\x01\x20\x01(\x04R\tse\
+    rviceId\x12\x1f\n\x0binstance_id\x18\x02\x20\x01(\tR\ninstanceId\x12\x12\
+    \n\x04meta\x18\x03\x20\x01(\tR\x04meta\x12<\n\x06labels\x18\x04\x20\x03(\
+    \x0b2$.register.SessionRequest.LabelsEntryR\x06labels\x12!\n\x0cservice_\
+    port\x18\x05\x20\x01(\rR\x0bservicePort\x129\n\x05ports\x18\x06\x20\x03(\
+    \x0b2#.register.SessionRequest.PortsEntryR\x05ports\x12'\n\x0fadvertised\
+    _host\x18\x07\x20\x01(\tR\x0eadvertisedHost\x1a9\n\x0bLabelsEntry\x12\
+    \x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\
+    \x01(\tR\x05value:\x028\x01\x1a8\n\nPortsEntry\x12\x10\n\x03key\x18\x01\
+    \x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\rR\x05value:\x02\
+    8\x01\"\xd3\x01\n\x0fSessionResponse\x122\n\x07command\x18\x01\x20\x01(\
+    \x0e2\x18.register.SessionCommandR\x07command\x12%\n\x0eregistry_epoch\
+    \x18\x02\x20\x01(\rR\rregistryEpoch\x12\x20\n\x0clease_ttl_ms\x18\x03\
+    \x20\x01(\rR\nleaseTtlMs\x121\n\x05error\x18\x04\x20\x01(\x0e2\x1b.regis\
+    ter.RegisterErrorKindR\x05error\x12\x10\n\x03msg\x18\x05\x20\x01(\tR\x03\
+    msg*D\n\tEventKind\x12\t\n\x05ADDED\x10\0\x12\x0b\n\x07DROPPED\x10\x01\
+    \x12\x12\n\x0eHEALTH_CHANGED\x10\x02\x12\x0b\n\x07UPDATED\x10\x03*1\n\
+    \x0bHealthState\x12\x0b\n\x07HEALTHY\x10\0\x12\x0b\n\x07SUSPECT\x10\x01\
+    \x12\x08\n\x04DOWN\x10\x02*a\n\x11RegisterErrorKind\x12\x08\n\x04NONE\
+    \x10\0\x12\x0c\n\x08CONFLICT\x10\x01\x12\x12\n\x0eQUOTA_EXCEEDED\x10\x02\
+    \x12\x0f\n\x0bUNREACHABLE\x10\x03\x12\x0f\n\x0bUNAVAILABLE\x10\x04*B\n\
+    \x0eSessionCommand\x12\x08\n\x04PONG\x10\0\x12\x0c\n\x08ACCEPTED\x10\x01\
+    \x12\x0b\n\x07REFUSED\x10\x02\x12\x0b\n\x07EVICTED\x10\x032\xf7\x03\n\
+    \x08Register\x12C\n\x08Register\x12\x19.register.RegisterRequest\x1a\x1a\
+    .register.RegisterResponse\"\0\x12C\n\x0cReportStatus\x12\x17.register.S\
+    tatusRequest\x1a\x18.register.StatusResponse\"\0\x12I\n\nReRegister\x12\
+    \x1b.register.ReRegisterRequest\x1a\x1c.register.ReRegisterResponse\"\0\
+    \x129\n\x05Watch\x12\x16.register.WatchRequest\x1a\x14.register.WatchEve\
+    nt\"\00\x01\x12I\n\nDeregister\x12\x1b.register.DeregisterRequest\x1a\
+    \x1c.register.DeregisterResponse\"\0\x12F\n\tKeepAlive\x12\x1a.register.\
+    KeepAliveRequest\x1a\x1b.register.KeepAliveResponse\"\0\x12H\n\x0bOpenSe\
+    ssion\x12\x18.register.SessionRequest\x1a\x19.register.SessionResponse\"\
+    \0(\x010\x01J\xa8C\n\x07\x12\x05\0\0\xba\x01\x01\n\x08\n\x01\x0c\x12\x03\
+    \0\0\x12\n\x08\n\x01\x02\x12\x03\x02\x08\x10\n\xdc\x02\n\x02\x04\0\x12\
+    \x04\t\0\x11\x01\x1a\xcf\x02\x20`service_port`\x20and\x20`heartbeat_port\
+    `\x20are\x20the\x20ports\x20the\x20container\x20would\x20like\n\x20to\
+    \x20bind,\x200\x20if\x20it\x20does\x20not\x20care.\x20the\x20registry\
+    \x20may\x20ignore\x20them.\n\x20`advertised_host`\x20is\x20the\x20ip\x20\
+    other\x20services\x20reach\x20the\x20container\x20on,\x20the\x20peer\n\
+    \x20address\x20is\x20used\x20if\x20it\x20is\x20empty.\x20`instance_id`\
+    \x20is\x20chosen\x20by\x20the\x20container\x20and\n\x20stays\x20the\x20s\
+    ame\x20across\x20re-registrations.\n\n\n\n\x03\x04\0\x01\x12\x03\t\x08\
+    \x17\n\x0b\n\x04\x04\0\x02\0\x12\x03\n\x08\x1e\n\r\n\x05\x04\0\x02\0\x04\
+    \x12\x04\n\x08\t\x19\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\n\x08\x0e\n\x0c\
+    \n\x05\x04\0\x02\0\x01\x12\x03\n\x0f\x19\n\x0c\n\x05\x04\0\x02\0\x03\x12\
+    \x03\n\x1c\x1d\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x0b\x08\x18\n\r\n\x05\
+    \x04\0\x02\x01\x04\x12\x04\x0b\x08\n\x1e\n\x0c\n\x05\x04\0\x02\x01\x05\
+    \x12\x03\x0b\x08\x0e\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x0b\x0f\x13\n\
+    \x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x0b\x16\x17\n\x0b\n\x04\x04\0\x02\
+    \x02\x12\x03\x0c\x08'\n\r\n\x05\x04\0\x02\x02\x04\x12\x04\x0c\x08\x0b\
+    \x18\n\x0c\n\x05\x04\0\x02\x02\x06\x12\x03\x0c\x08\x1b\n\x0c\n\x05\x04\0\
+    \x02\x02\x01\x12\x03\x0c\x1c\"\n\x0c\n\x05\x04\0\x02\x02\x03\x12\x03\x0c\
+    %&\n\x0b\n\x04\x04\0\x02\x03\x12\x03\r\x08\x20\n\r\n\x05\x04\0\x02\x03\
+    \x04\x12\x04\r\x08\x0c'\n\x0c\n\x05\x04\0\x02\x03\x05\x12\x03\r\x08\x0e\
+    \n\x0c\n\x05\x04\0\x02\x03\x01\x12\x03\r\x0f\x1b\n\x0c\n\x05\x04\0\x02\
+    \x03\x03\x12\x03\r\x1e\x1f\n\x0b\n\x04\x04\0\x02\x04\x12\x03\x0e\x08\"\n\
+    \r\n\x05\x04\0\x02\x04\x04\x12\x04\x0e\x08\r\x20\n\x0c\n\x05\x04\0\x02\
+    \x04\x05\x12\x03\x0e\x08\x0e\n\x0c\n\x05\x04\0\x02\x04\x01\x12\x03\x0e\
+    \x0f\x1d\n\x0c\n\x05\x04\0\x02\x04\x03\x12\x03\x0e\x20!\n\x0b\n\x04\x04\
+    \0\x02\x05\x12\x03\x0f\x08#\n\r\n\x05\x04\0\x02\x05\x04\x12\x04\x0f\x08\
+    \x0e\"\n\x0c\n\x05\x04\0\x02\x05\x05\x12\x03\x0f\x08\x0e\n\x0c\n\x05\x04\
+    \0\x02\x05\x01\x12\x03\x0f\x0f\x1e\n\x0c\n\x05\x04\0\x02\x05\x03\x12\x03\
+    \x0f!\"\n\x0b\n\x04\x04\0\x02\x06\x12\x03\x10\x08\x1f\n\r\n\x05\x04\0\
+    \x02\x06\x04\x12\x04\x10\x08\x0f#\n\x0c\n\x05\x04\0\x02\x06\x05\x12\x03\
+    \x10\x08\x0e\n\x0c\n\x05\x04\0\x02\x06\x01\x12\x03\x10\x0f\x1a\n\x0c\n\
+    \x05\x04\0\x02\x06\x03\x12\x03\x10\x1d\x1e\n\xf9\x01\n\x02\x04\x01\x12\
+    \x04\x17\0\x1d\x01\x1a\xec\x01\x20`registry_epoch`\x20changes\x20every\
+    \x20time\x20the\x20registry\x20restarts,\x20it\x20is\x20also\x20sent\n\
+    \x20with\x20every\x20heartbeat\x20request.\x20`lease_ttl_ms`\x20is\x20se\
+    t\x20by\x20a\x20registry\x20in\x20push\n\x20mode,\x20the\x20container\
+    \x20must\x20call\x20KeepAlive\x20within\x20it\x20instead\x20of\x20answer\
+    ing\n\x20heartbeats.\n\n\n\n\x03\x04\x01\x01\x12\x03\x17\x08\x18\n\x0b\n\
+    \x04\x04\x01\x02\0\x12\x03\x18\x08\"\n\r\n\x05\x04\x01\x02\0\x04\x12\x04\
+    \x18\x08\x17\x1a\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\x18\x08\x0e\n\x0c\
+    \n\x05\x04\x01\x02\0\x01\x12\x03\x18\x0f\x1d\n\x0c\n\x05\x04\x01\x02\0\
+    \x03\x12\x03\x18\x20!\n\x0b\n\x04\x04\x01\x02\x01\x12\x03\x19\x08\x20\n\
+    \r\n\x05\x04\x01\x02\x01\x04\x12\x04\x19\x08\x18\"\n\x0c\n\x05\x04\x01\
+    \x02\x01\x05\x12\x03\x19\x08\x0e\n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\
+    \x19\x0f\x1b\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\x19\x1e\x1f\n\x0b\n\
+    \x04\x04\x01\x02\x02\x12\x03\x1a\x08\x1e\n\r\n\x05\x04\x01\x02\x02\x04\
+    \x12\x04\x1a\x08\x19\x20\n\x0c\n\x05\x04\x01\x02\x02\x05\x12\x03\x1a\x08\
+    \x0e\n\x0c\n\x05\x04\x01\x02\x02\x01\x12\x03\x1a\x0f\x19\n\x0c\n\x05\x04\
+    \x01\x02\x02\x03\x12\x03\x1a\x1c\x1d\n\x0b\n\x04\x04\x01\x02\x03\x12\x03\
+    \x1b\x08\"\n\r\n\x05\x04\x01\x02\x03\x04\x12\x04\x1b\x08\x1a\x1e\n\x0c\n\
+    \x05\x04\x01\x02\x03\x05\x12\x03\x1b\x08\x0e\n\x0c\n\x05\x04\x01\x02\x03\
+    \x01\x12\x03\x1b\x0f\x1d\n\x0c\n\x05\x04\x01\x02\x03\x03\x12\x03\x1b\x20\
+    !\n\x0b\n\x04\x04\x01\x02\x04\x12\x03\x1c\x08\x20\n\r\n\x05\x04\x01\x02\
+    \x04\x04\x12\x04\x1c\x08\x1b\"\n\x0c\n\x05\x04\x01\x02\x04\x05\x12\x03\
+    \x1c\x08\x0e\n\x0c\n\x05\x04\x01\x02\x04\x01\x12\x03\x1c\x0f\x1b\n\x0c\n\
+    \x05\x04\x01\x02\x04\x03\x12\x03\x1c\x1e\x1f\n\xed\x01\n\x02\x04\x02\x12\
+    \x04\"\0)\x01\x1a\xe0\x01\x20`service_port`\x20and\x20`heartbeat_port`\
+    \x20are\x20the\x20ports\x20the\x20container\x20bound,\x20they\n\x20are\
+    \x20required\x20if\x20the\x20registry\x20proposed\x20port\x200.\x20`port\
+    s`\x20are\x20the\x20named\x20ports\n\x20served\x20besides\x20the\x20serv\
+    ice\x20port,\x20they\x20are\x20only\x20read\x20once\x20both\x20succeed.\
+    \n\n\n\n\x03\x04\x02\x01\x12\x03\"\x08\x15\n\x0b\n\x04\x04\x02\x02\0\x12\
+    \x03#\x08#\n\r\n\x05\x04\x02\x02\0\x04\x12\x04#\x08\"\x17\n\x0c\n\x05\
+    \x04\x02\x02\0\x05\x12\x03#\x08\x0c\n\x0c\n\x05\x04\x02\x02\0\x01\x12\
+    \x03#\r\x1e\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03#!\"\n\x0b\n\x04\x04\
+    \x02\x02\x01\x12\x03$\x08!\n\r\n\x05\x04\x02\x02\x01\x04\x12\x04$\x08##\
+    \n\x0c\n\x05\x04\x02\x02\x01\x05\x12\x03$\x08\x0c\n\x0c\n\x05\x04\x02\
+    \x02\x01\x01\x12\x03$\r\x1c\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03$\x1f\
+    \x20\n\x0b\n\x04\x04\x02\x02\x02\x12\x03%\x08\x1e\n\r\n\x05\x04\x02\x02\
+    \x02\x04\x12\x04%\x08$!\n\x0c\n\x05\x04\x02\x02\x02\x05\x12\x03%\x08\x0e\
+    \n\x0c\n\x05\x04\x02\x02\x02\x01\x12\x03%\x0f\x19\n\x0c\n\x05\x04\x02\
+    \x02\x02\x03\x12\x03%\x1c\x1d\n\x0b\n\x04\x04\x02\x02\x03\x12\x03&\x08\
+    \x20\n\r\n\x05\x04\x02\x02\x03\x04\x12\x04&\x08%\x1e\n\x0c\n\x05\x04\x02\
+    \x02\x03\x05\x12\x03&\x08\x0e\n\x0c\n\x05\x04\x02\x02\x03\x01\x12\x03&\
+    \x0f\x1b\n\x0c\n\x05\x04\x02\x02\x03\x03\x12\x03&\x1e\x1f\n\x0b\n\x04\
+    \x04\x02\x02\x04\x12\x03'\x08\"\n\r\n\x05\x04\x02\x02\x04\x04\x12\x04'\
+    \x08&\x20\n\x0c\n\x05\x04\x02\x02\x04\x05\x12\x03'\x08\x0e\n\x0c\n\x05\
+    \x04\x02\x02\x04\x01\x12\x03'\x0f\x1d\n\x0c\n\x05\x04\x02\x02\x04\x03\
+    \x12\x03'\x20!\n\x0b\n\x04\x04\x02\x02\x05\x12\x03(\x08&\n\r\n\x05\x04\
+    \x02\x02\x05\x04\x12\x04(\x08'\"\n\x0c\n\x05\x04\x02\x02\x05\x06\x12\x03\
+    (\x08\x1b\n\x0c\n\x05\x04\x02\x02\x05\x01\x12\x03(\x1c!\n\x0c\n\x05\x04\
+    \x02\x02\x05\x03\x12\x03($%\n\x86\x01\n\x02\x04\x03\x12\x04-\03\x01\x1az\
+    \x20`stale_session`\x20is\x20set\x20when\x20the\x20session\x20was\x20cre\
+    ated\x20by\x20a\x20previous\n\x20incarnation\x20of\x20the\x20registry,\
+    \x20`succeed`\x20is\x20false\x20then.\n\n\n\n\x03\x04\x03\x01\x12\x03-\
+    \x08\x16\n\x0b\n\x04\x04\x03\x02\0\x12\x03.\x08\x19\n\r\n\x05\x04\x03\
+    \x02\0\x04\x12\x04.\x08-\x18\n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03.\x08\
+    \x0c\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03.\r\x14\n\x0c\n\x05\x04\x03\
+    \x02\0\x03\x12\x03.\x17\x18\n\x0b\n\x04\x04\x03\x02\x01\x12\x03/\x08\"\n\
+    \r\n\x05\x04\x03\x02\x01\x04\x12\x04/\x08.\x19\n\x0c\n\x05\x04\x03\x02\
+    \x01\x05\x12\x03/\x08\x0e\n\x0c\n\x05\x04\x03\x02\x01\x01\x12\x03/\x0f\
+    \x1d\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\x03/\x20!\n\x0b\n\x04\x04\x03\
+    \x02\x02\x12\x030\x08\x20\n\r\n\x05\x04\x03\x02\x02\x04\x12\x040\x08/\"\
+    \n\x0c\n\x05\x04\x03\x02\x02\x05\x12\x030\x08\x0e\n\x0c\n\x05\x04\x03\
+    \x02\x02\x01\x12\x030\x0f\x1b\n\x0c\n\x05\x04\x03\x02\x02\x03\x12\x030\
+    \x1e\x1f\n\x0b\n\x04\x04\x03\x02\x03\x12\x031\x08\x1e\n\r\n\x05\x04\x03\
+    \x02\x03\x04\x12\x041\x080\x20\n\x0c\n\x05\x04\x03\x02\x03\x05\x12\x031\
+    \x08\x0e\n\x0c\n\x05\x04\x03\x02\x03\x01\x12\x031\x0f\x19\n\x0c\n\x05\
+    \x04\x03\x02\x03\x03\x12\x031\x1c\x1d\n\x0b\n\x04\x04\x03\x02\x04\x12\
+    \x032\x08\x1f\n\r\n\x05\x04\x03\x02\x04\x04\x12\x042\x081\x1e\n\x0c\n\
+    \x05\x04\x03\x02\x04\x05\x12\x032\x08\x0c\n\x0c\n\x05\x04\x03\x02\x04\
+    \x01\x12\x032\r\x1a\n\x0c\n\x05\x04\x03\x02\x04\x03\x12\x032\x1d\x1e\n\n\
+    \n\x02\x04\x04\x12\x045\0>\x01\n\n\n\x03\x04\x04\x01\x12\x035\x08\x19\n\
+    \x0b\n\x04\x04\x04\x02\0\x12\x036\x08\"\n\r\n\x05\x04\x04\x02\0\x04\x12\
+    \x046\x085\x1b\n\x0c\n\x05\x04\x04\x02\0\x05\x12\x036\x08\x0e\n\x0c\n\
+    \x05\x04\x04\x02\0\x01\x12\x036\x0f\x1d\n\x0c\n\x05\x04\x04\x02\0\x03\
+    \x12\x036\x20!\n\x0b\n\x04\x04\x04\x02\x01\x12\x037\x08\x20\n\r\n\x05\
+    \x04\x04\x02\x01\x04\x12\x047\x086\"\n\x0c\n\x05\x04\x04\x02\x01\x05\x12\
+    \x037\x08\x0e\n\x0c\n\x05\x04\x04\x02\x01\x01\x12\x037\x0f\x1b\n\x0c\n\
+    \x05\x04\x04\x02\x01\x03\x12\x037\x1e\x1f\n\x0b\n\x04\x04\x04\x02\x02\
+    \x12\x038\x08\x1e\n\r\n\x05\x04\x04\x02\x02\x04\x12\x048\x087\x20\n\x0c\
+    \n\x05\x04\x04\x02\x02\x05\x12\x038\x08\x0e\n\x0c\n\x05\x04\x04\x02\x02\
+    \x01\x12\x038\x0f\x19\n\x0c\n\x05\x04\x04\x02\x02\x03\x12\x038\x1c\x1d\n\
+    \x0b\n\x04\x04\x04\x02\x03\x12\x039\x08\x18\n\r\n\x05\x04\x04\x02\x03\
+    \x04\x12\x049\x088\x1e\n\x0c\n\x05\x04\x04\x02\x03\x05\x12\x039\x08\x0e\
+    \n\x0c\n\x05\x04\x04\x02\x03\x01\x12\x039\x0f\x13\n\x0c\n\x05\x04\x04\
+    \x02\x03\x03\x12\x039\x16\x17\n\x0b\n\x04\x04\x04\x02\x04\x12\x03:\x08'\
+    \n\r\n\x05\x04\x04\x02\x04\x04\x12\x04:\x089\x18\n\x0c\n\x05\x04\x04\x02\
+    \x04\x06\x12\x03:\x08\x1b\n\x0c\n\x05\x04\x04\x02\x04\x01\x12\x03:\x1c\"\
+    \n\x0c\n\x05\x04\x04\x02\x04\x03\x12\x03:%&\n\x0b\n\x04\x04\x04\x02\x05\
+    \x12\x03;\x08&\n\r\n\x05\x04\x04\x02\x05\x04\x12\x04;\x08:'\n\x0c\n\x05\
+    \x04\x04\x02\x05\x06\x12\x03;\x08\x1b\n\x0c\n\x05\x04\x04\x02\x05\x01\
+    \x12\x03;\x1c!\n\x0c\n\x05\x04\x04\x02\x05\x03\x12\x03;$%\n\x0b\n\x04\
+    \x04\x04\x02\x06\x12\x03<\x08#\n\r\n\x05\x04\x04\x02\x06\x04\x12\x04<\
+    \x08;&\n\x0c\n\x05\x04\x04\x02\x06\x05\x12\x03<\x08\x0e\n\x0c\n\x05\x04\
+    \x04\x02\x06\x01\x12\x03<\x0f\x1e\n\x0c\n\x05\x04\x04\x02\x06\x03\x12\
+    \x03<!\"\n\x0b\n\x04\x04\x04\x02\x07\x12\x03=\x08\x1f\n\r\n\x05\x04\x04\
+    \x02\x07\x04\x12\x04=\x08<#\n\x0c\n\x05\x04\x04\x02\x07\x05\x12\x03=\x08\
+    \x0e\n\x0c\n\x05\x04\x04\x02\x07\x01\x12\x03=\x0f\x1a\n\x0c\n\x05\x04\
+    \x04\x02\x07\x03\x12\x03=\x1d\x1e\nq\n\x02\x04\x05\x12\x04B\0F\x01\x1ae\
+    \x20`error`\x20tells\x20why\x20the\x20registry\x20refused\x20the\x20serv\
+    ice\x20when\x20`succeed`\x20is\x20false,\n\x20`msg`\x20has\x20the\x20det\
+    ails.\n\n\n\n\x03\x04\x05\x01\x12\x03B\x08\x1a\n\x0b\n\x04\x04\x05\x02\0\
+    \x12\x03C\x08\x19\n\r\n\x05\x04\x05\x02\0\x04\x12\x04C\x08B\x1c\n\x0c\n\
+    \x05\x04\x05\x02\0\x05\x12\x03C\x08\x0c\n\x0c\n\x05\x04\x05\x02\0\x01\
+    \x12\x03C\r\x14\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x03C\x17\x18\n\x0b\n\
+    \x04\x04\x05\x02\x01\x12\x03D\x08\x17\n\r\n\x05\x04\x05\x02\x01\x04\x12\
+    \x04D\x08C\x19\n\x0c\n\x05\x04\x05\x02\x01\x05\x12\x03D\x08\x0e\n\x0c\n\
+    \x05\x04\x05\x02\x01\x01\x12\x03D\x0f\x12\n\x0c\n\x05\x04\x05\x02\x01\
+    \x03\x12\x03D\x15\x16\n\x0b\n\x04\x04\x05\x02\x02\x12\x03E\x08$\n\r\n\
+    \x05\x04\x05\x02\x02\x04\x12\x04E\x08D\x17\n\x0c\n\x05\x04\x05\x02\x02\
+    \x06\x12\x03E\x08\x19\n\x0c\n\x05\x04\x05\x02\x02\x01\x12\x03E\x1a\x1f\n\
+    \x0c\n\x05\x04\x05\x02\x02\x03\x12\x03E\"#\n\n\n\x02\x04\x06\x12\x04H\0Q\
+    \x01\n\n\n\x03\x04\x06\x01\x12\x03H\x08\x19\n\x0b\n\x04\x04\x06\x02\0\
+    \x12\x03I\x08\"\n\r\n\x05\x04\x06\x02\0\x04\x12\x04I\x08H\x1b\n\x0c\n\
+    \x05\x04\x06\x02\0\x05\x12\x03I\x08\x0e\n\x0c\n\x05\x04\x06\x02\0\x01\
+    \x12\x03I\x0f\x1d\n\x0c\n\x05\x04\x06\x02\0\x03\x12\x03I\x20!\n\x0b\n\
+    \x04\x04\x06\x02\x01\x12\x03J\x08\x20\n\r\n\x05\x04\x06\x02\x01\x04\x12\
+    \x04J\x08I\"\n\x0c\n\x05\x04\x06\x02\x01\x05\x12\x03J\x08\x0e\n\x0c\n\
+    \x05\x04\x06\x02\x01\x01\x12\x03J\x0f\x1b\n\x0c\n\x05\x04\x06\x02\x01\
+    \x03\x12\x03J\x1e\x1f\n\x0b\n\x04\x04\x06\x02\x02\x12\x03K\x08\x1e\n\r\n\
+    \x05\x04\x06\x02\x02\x04\x12\x04K\x08J\x20\n\x0c\n\x05\x04\x06\x02\x02\
+    \x05\x12\x03K\x08\x0e\n\x0c\n\x05\x04\x06\x02\x02\x01\x12\x03K\x0f\x19\n\
+    \x0c\n\x05\x04\x06\x02\x02\x03\x12\x03K\x1c\x1d\n\x0b\n\x04\x04\x06\x02\
+    \x03\x12\x03L\x08\x18\n\r\n\x05\x04\x06\x02\x03\x04\x12\x04L\x08K\x1e\n\
+    \x0c\n\x05\x04\x06\x02\x03\x05\x12\x03L\x08\x0e\n\x0c\n\x05\x04\x06\x02\
+    \x03\x01\x12\x03L\x0f\x13\n\x0c\n\x05\x04\x06\x02\x03\x03\x12\x03L\x16\
+    \x17\n\x0b\n\x04\x04\x06\x02\x04\x12\x03M\x08'\n\r\n\x05\x04\x06\x02\x04\
+    \x04\x12\x04M\x08L\x18\n\x0c\n\x05\x04\x06\x02\x04\x06\x12\x03M\x08\x1b\
+    \n\x0c\n\x05\x04\x06\x02\x04\x01\x12\x03M\x1c\"\n\x0c\n\x05\x04\x06\x02\
+    \x04\x03\x12\x03M%&\n\x0b\n\x04\x04\x06\x02\x05\x12\x03N\x08&\n\r\n\x05\
+    \x04\x06\x02\x05\x04\x12\x04N\x08M'\n\x0c\n\x05\x04\x06\x02\x05\x06\x12\
+    \x03N\x08\x1b\n\x0c\n\x05\x04\x06\x02\x05\x01\x12\x03N\x1c!\n\x0c\n\x05\
+    \x04\x06\x02\x05\x03\x12\x03N$%\n\x0b\n\x04\x04\x06\x02\x06\x12\x03O\x08\
+    #\n\r\n\x05\x04\x06\x02\x06\x04\x12\x04O\x08N&\n\x0c\n\x05\x04\x06\x02\
+    \x06\x05\x12\x03O\x08\x0e\n\x0c\n\x05\x04\x06\x02\x06\x01\x12\x03O\x0f\
+    \x1e\n\x0c\n\x05\x04\x06\x02\x06\x03\x12\x03O!\"\n\x0b\n\x04\x04\x06\x02\
+    \x07\x12\x03P\x08\x1f\n\r\n\x05\x04\x06\x02\x07\x04\x12\x04P\x08O#\n\x0c\
+    \n\x05\x04\x06\x02\x07\x05\x12\x03P\x08\x0e\n\x0c\n\x05\x04\x06\x02\x07\
+    \x01\x12\x03P\x0f\x1a\n\x0c\n\x05\x04\x06\x02\x07\x03\x12\x03P\x1d\x1e\n\
+    \n\n\x02\x04\x07\x12\x04S\0U\x01\n\n\n\x03\x04\x07\x01\x12\x03S\x08\x1a\
+    \n\x0b\n\x04\x04\x07\x02\0\x12\x03T\x08\x19\n\r\n\x05\x04\x07\x02\0\x04\
+    \x12\x04T\x08S\x1c\n\x0c\n\x05\x04\x07\x02\0\x05\x12\x03T\x08\x0c\n\x0c\
+    \n\x05\x04\x07\x02\0\x01\x12\x03T\r\x14\n\x0c\n\x05\x04\x07\x02\0\x03\
+    \x12\x03T\x17\x18\n\n\n\x02\x04\x08\x12\x04W\0`\x01\n\n\n\x03\x04\x08\
+    \x01\x12\x03W\x08\x13\n\x0b\n\x04\x04\x08\x02\0\x12\x03X\x08\x1e\n\r\n\
+    \x05\x04\x08\x02\0\x04\x12\x04X\x08W\x15\n\x0c\n\x05\x04\x08\x02\0\x05\
+    \x12\x03X\x08\x0e\n\x0c\n\x05\x04\x08\x02\0\x01\x12\x03X\x0f\x19\n\x0c\n\
+    \x05\x04\x08\x02\0\x03\x12\x03X\x1c\x1d\n\x0b\n\x04\x04\x08\x02\x01\x12\
+    \x03Y\x08\x18\n\r\n\x05\x04\x08\x02\x01\x04\x12\x04Y\x08X\x1e\n\x0c\n\
+    \x05\x04\x08\x02\x01\x05\x12\x03Y\x08\x0e\n\x0c\n\x05\x04\x08\x02\x01\
+    \x01\x12\x03Y\x0f\x13\n\x0c\n\x05\x04\x08\x02\x01\x03\x12\x03Y\x16\x17\n\
+    \x0b\n\x04\x04\x08\x02\x02\x12\x03Z\x08\x18\n\r\n\x05\x04\x08\x02\x02\
+    \x04\x12\x04Z\x08Y\x18\n\x0c\n\x05\x04\x08\x02\x02\x05\x12\x03Z\x08\x0e\
+    \n\x0c\n\x05\x04\x08\x02\x02\x01\x12\x03Z\x0f\x13\n\x0c\n\x05\x04\x08\
+    \x02\x02\x03\x12\x03Z\x16\x17\n\x0b\n\x04\x04\x08\x02\x03\x12\x03[\x08\
+    \x20\n\r\n\x05\x04\x08\x02\x03\x04\x12\x04[\x08Z\x18\n\x0c\n\x05\x04\x08\
+    \x02\x03\x05\x12\x03[\x08\x0e\n\x0c\n\x05\x04\x08\x02\x03\x01\x12\x03[\
+    \x0f\x1b\n\x0c\n\x05\x04\x08\x02\x03\x03\x12\x03[\x1e\x1f\n\x0b\n\x04\
+    \x04\x08\x02\x04\x12\x03\\\x08\"\n\r\n\x05\x04\x08\x02\x04\x04\x12\x04\\\
+    \x08[\x20\n\x0c\n\x05\x04\x08\x02\x04\x05\x12\x03\\\x08\x0e\n\x0c\n\x05\
+    \x04\x08\x02\x04\x01\x12\x03\\\x0f\x1d\n\x0c\n\x05\x04\x08\x02\x04\x03\
+    \x12\x03\\\x20!\n\x0b\n\x04\x04\x08\x02\x05\x12\x03]\x08'\n\r\n\x05\x04\
+    \x08\x02\x05\x04\x12\x04]\x08\\\"\n\x0c\n\x05\x04\x08\x02\x05\x06\x12\
+    \x03]\x08\x1b\n\x0c\n\x05\x04\x08\x02\x05\x01\x12\x03]\x1c\"\n\x0c\n\x05\
+    \x04\x08\x02\x05\x03\x12\x03]%&\n\x0b\n\x04\x04\x08\x02\x06\x12\x03^\x08\
+    &\n\r\n\x05\x04\x08\x02\x06\x04\x12\x04^\x08]'\n\x0c\n\x05\x04\x08\x02\
+    \x06\x06\x12\x03^\x08\x1b\n\x0c\n\x05\x04\x08\x02\x06\x01\x12\x03^\x1c!\
+    \n\x0c\n\x05\x04\x08\x02\x06\x03\x12\x03^$%\n\x0b\n\x04\x04\x08\x02\x07\
+    \x12\x03_\x08\x1f\n\r\n\x05\x04\x08\x02\x07\x04\x12\x04_\x08^&\n\x0c\n\
+    \x05\x04\x08\x02\x07\x05\x12\x03_\x08\x0e\n\x0c\n\x05\x04\x08\x02\x07\
+    \x01\x12\x03_\x0f\x1a\n\x0c\n\x05\x04\x08\x02\x07\x03\x12\x03_\x1d\x1e\n\
+    ;\n\x02\x04\t\x12\x04c\0e\x01\x1a/\x20watch\x20all\x20services\x20if\x20\
+    `service_ids`\x20is\x20empty.\n\n\n\n\x03\x04\t\x01\x12\x03c\x08\x14\n\
+    \x0b\n\x04\x04\t\x02\0\x12\x03d\x08(\n\x0c\n\x05\x04\t\x02\0\x04\x12\x03\
+    d\x08\x10\n\x0c\n\x05\x04\t\x02\0\x05\x12\x03d\x11\x17\n\x0c\n\x05\x04\t\
+    \x02\0\x01\x12\x03d\x18#\n\x0c\n\x05\x04\t\x02\0\x03\x12\x03d&'\n\n\n\
+    \x02\x05\0\x12\x04g\0l\x01\n\n\n\x03\x05\0\x01\x12\x03g\x05\x0e\n\x0b\n\
+    \x04\x05\0\x02\0\x12\x03h\x08\x12\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03h\
+    \x08\r\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03h\x10\x11\n\x0b\n\x04\x05\0\
+    \x02\x01\x12\x03i\x08\x14\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03i\x08\x0f\
+    \n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03i\x12\x13\n\x0b\n\x04\x05\0\x02\
+    \x02\x12\x03j\x08\x1b\n\x0c\n\x05\x05\0\x02\x02\x01\x12\x03j\x08\x16\n\
+    \x0c\n\x05\x05\0\x02\x02\x02\x12\x03j\x19\x1a\n\x0b\n\x04\x05\0\x02\x03\
+    \x12\x03k\x08\x14\n\x0c\n\x05\x05\0\x02\x03\x01\x12\x03k\x08\x0f\n\x0c\n\
+    \x05\x05\0\x02\x03\x02\x12\x03k\x12\x13\n\n\n\x02\x05\x01\x12\x04n\0r\
+    \x01\n\n\n\x03\x05\x01\x01\x12\x03n\x05\x10\n\x0b\n\x04\x05\x01\x02\0\
+    \x12\x03o\x08\x14\n\x0c\n\x05\x05\x01\x02\0\x01\x12\x03o\x08\x0f\n\x0c\n\
+    \x05\x05\x01\x02\0\x02\x12\x03o\x12\x13\n\x0b\n\x04\x05\x01\x02\x01\x12\
+    \x03p\x08\x14\n\x0c\n\x05\x05\x01\x02\x01\x01\x12\x03p\x08\x0f\n\x0c\n\
+    \x05\x05\x01\x02\x01\x02\x12\x03p\x12\x13\n\x0b\n\x04\x05\x01\x02\x02\
+    \x12\x03q\x08\x11\n\x0c\n\x05\x05\x01\x02\x02\x01\x12\x03q\x08\x0c\n\x0c\
+    \n\x05\x05\x01\x02\x02\x02\x12\x03q\x0f\x10\n\n\n\x02\x05\x02\x12\x04t\0\
+    z\x01\n\n\n\x03\x05\x02\x01\x12\x03t\x05\x16\n\x0b\n\x04\x05\x02\x02\0\
+    \x12\x03u\x08\x11\n\x0c\n\x05\x05\x02\x02\0\x01\x12\x03u\x08\x0c\n\x0c\n\
+    \x05\x05\x02\x02\0\x02\x12\x03u\x0f\x10\n\x0b\n\x04\x05\x02\x02\x01\x12\
+    \x03v\x08\x15\n\x0c\n\x05\x05\x02\x02\x01\x01\x12\x03v\x08\x10\n\x0c\n\
+    \x05\x05\x02\x02\x01\x02\x12\x03v\x13\x14\n\x0b\n\x04\x05\x02\x02\x02\
+    \x12\x03w\x08\x1b\n\x0c\n\x05\x05\x02\x02\x02\x01\x12\x03w\x08\x16\n\x0c\
+    \n\x05\x05\x02\x02\x02\x02\x12\x03w\x19\x1a\n\x0b\n\x04\x05\x02\x02\x03\
+    \x12\x03x\x08\x18\n\x0c\n\x05\x05\x02\x02\x03\x01\x12\x03x\x08\x13\n\x0c\
+    \n\x05\x05\x02\x02\x03\x02\x12\x03x\x16\x17\n\x0b\n\x04\x05\x02\x02\x04\
+    \x12\x03y\x08\x18\n\x0c\n\x05\x05\x02\x02\x04\x01\x12\x03y\x08\x13\n\x0c\
+    \n\x05\x05\x02\x02\x04\x02\x12\x03y\x16\x17\nI\n\x02\x04\n\x12\x05}\0\
+    \x83\x01\x01\x1a<\x20`health`\x20is\x20only\x20meaningful\x20when\x20`ki\
+    nd`\x20is\x20HEALTH_CHANGED.\n\n\n\n\x03\x04\n\x01\x12\x03}\x08\x12\n\
+    \x0b\n\x04\x04\n\x02\0\x12\x03~\x08\x1b\n\r\n\x05\x04\n\x02\0\x04\x12\
+    \x04~\x08}\x14\n\x0c\n\x05\x04\n\x02\0\x06\x12\x03~\x08\x11\n\x0c\n\x05\
+    \x04\n\x02\0\x01\x12\x03~\x12\x16\n\x0c\n\x05\x04\n\x02\0\x03\x12\x03~\
+    \x19\x1a\n\x0b\n\x04\x04\n\x02\x01\x12\x03\x7f\x08\x20\n\r\n\x05\x04\n\
+    \x02\x01\x04\x12\x04\x7f\x08~\x1b\n\x0c\n\x05\x04\n\x02\x01\x06\x12\x03\
+    \x7f\x08\x13\n\x0c\n\x05\x04\n\x02\x01\x01\x12\x03\x7f\x14\x1b\n\x0c\n\
+    \x05\x04\n\x02\x01\x03\x12\x03\x7f\x1e\x1f\n\x0c\n\x04\x04\n\x02\x02\x12\
+    \x04\x80\x01\x08\x1f\n\x0e\n\x05\x04\n\x02\x02\x04\x12\x05\x80\x01\x08\
+    \x7f\x20\n\r\n\x05\x04\n\x02\x02\x06\x12\x04\x80\x01\x08\x13\n\r\n\x05\
+    \x04\n\x02\x02\x01\x12\x04\x80\x01\x14\x1a\n\r\n\x05\x04\n\x02\x02\x03\
+    \x12\x04\x80\x01\x1d\x1e\nF\n\x04\x04\n\x02\x03\x12\x04\x82\x01\x08$\x1a\
+    8\x20the\x20service\x20before\x20the\x20update,\x20only\x20set\x20for\
+    \x20`UPDATED`.\n\n\x0f\n\x05\x04\n\x02\x03\x04\x12\x06\x82\x01\x08\x80\
+    \x01\x1f\n\r\n\x05\x04\n\x02\x03\x06\x12\x04\x82\x01\x08\x13\n\r\n\x05\
+    \x04\n\x02\x03\x01\x12\x04\x82\x01\x14\x1f\n\r\n\x05\x04\n\x02\x03\x03\
+    \x12\x04\x82\x01\"#\n{\n\x02\x04\x0b\x12\x06\x87\x01\0\x8b\x01\x01\x1am\
+    \x20only\x20the\x20host\x20of\x20the\x20service\x20may\x20renew\x20its\
+    \x20lease,\x20`advertised_host`\x20is\x20the\n\x20one\x20it\x20registere\
+    d\x20with,\x20if\x20any.\n\n\x0b\n\x03\x04\x0b\x01\x12\x04\x87\x01\x08\
+    \x18\n\x0c\n\x04\x04\x0b\x02\0\x12\x04\x88\x01\x08\x1e\n\x0f\n\x05\x04\
+    \x0b\x02\0\x04\x12\x06\x88\x01\x08\x87\x01\x1a\n\r\n\x05\x04\x0b\x02\0\
+    \x05\x12\x04\x88\x01\x08\x0e\n\r\n\x05\x04\x0b\x02\0\x01\x12\x04\x88\x01\
+    \x0f\x19\n\r\n\x05\x04\x0b\x02\0\x03\x12\x04\x88\x01\x1c\x1d\n\x0c\n\x04\
+    \x04\x0b\x02\x01\x12\x04\x89\x01\x08\x1f\n\x0f\n\x05\x04\x0b\x02\x01\x04\
+    \x12\x06\x89\x01\x08\x88\x01\x1e\n\r\n\x05\x04\x0b\x02\x01\x05\x12\x04\
+    \x89\x01\x08\x0e\n\r\n\x05\x04\x0b\x02\x01\x01\x12\x04\x89\x01\x0f\x1a\n\
+    \r\n\x05\x04\x0b\x02\x01\x03\x12\x04\x89\x01\x1d\x1e\n\x0c\n\x04\x04\x0b\
+    \x02\x02\x12\x04\x8a\x01\x08#\n\x0f\n\x05\x04\x0b\x02\x02\x04\x12\x06\
+    \x8a\x01\x08\x89\x01\x1f\n\r\n\x05\x04\x0b\x02\x02\x05\x12\x04\x8a\x01\
+    \x08\x0e\n\r\n\x05\x04\x0b\x02\x02\x01\x12\x04\x8a\x01\x0f\x1e\n\r\n\x05\
+    \x04\x0b\x02\x02\x03\x12\x04\x8a\x01!\"\n\xa3\x01\n\x02\x04\x0c\x12\x06\
+    \x8f\x01\0\x92\x01\x01\x1a\x94\x01\x20`succeed`\x20is\x20false\x20if\x20\
+    the\x20registry\x20does\x20not\x20know\x20the\x20service,\x20e.g.\x20its\
+    \x20lease\n\x20expired\x20or\x20the\x20registry\x20restarted,\x20the\x20\
+    container\x20should\x20re-register.\n\n\x0b\n\x03\x04\x0c\x01\x12\x04\
+    \x8f\x01\x08\x19\n\x0c\n\x04\x04\x0c\x02\0\x12\x04\x90\x01\x08\x19\n\x0f\
+    \n\x05\x04\x0c\x02\0\x04\x12\x06\x90\x01\x08\x8f\x01\x1b\n\r\n\x05\x04\
+    \x0c\x02\0\x05\x12\x04\x90\x01\x08\x0c\n\r\n\x05\x04\x0c\x02\0\x01\x12\
+    \x04\x90\x01\r\x14\n\r\n\x05\x04\x0c\x02\0\x03\x12\x04\x90\x01\x17\x18\n\
+    \x0c\n\x04\x04\x0c\x02\x01\x12\x04\x91\x01\x08\"\n\x0f\n\x05\x04\x0c\x02\
+    \x01\x04\x12\x06\x91\x01\x08\x90\x01\x19\n\r\n\x05\x04\x0c\x02\x01\x05\
+    \x12\x04\x91\x01\x08\x0e\n\r\n\x05\x04\x0c\x02\x01\x01\x12\x04\x91\x01\
+    \x0f\x1d\n\r\n\x05\x04\x0c\x02\x01\x03\x12\x04\x91\x01\x20!\n\x7f\n\x02\
+    \x04\r\x12\x06\x96\x01\0\x9e\x01\x01\x1aq\x20the\x20first\x20message\x20\
+    of\x20a\x20session\x20stream\x20describes\x20the\x20service,\x20the\x20f\
+    ollowing\n\x20ones\x20are\x20pings\x20which\x20keep\x20it\x20alive.\n\n\
+    \x0b\n\x03\x04\r\x01\x12\x04\x96\x01\x08\x16\n\x0c\n\x04\x04\r\x02\0\x12\
+    \x04\x97\x01\x08\x1e\n\x0f\n\x05\x04\r\x02\0\x04\x12\x06\x97\x01\x08\x96\
+    \x01\x18\n\r\n\x05\x04\r\x02\0\x05\x12\x04\x97\x01\x08\x0e\n\r\n\x05\x04\
+    \r\x02\0\x01\x12\x04\x97\x01\x0f\x19\n\r\n\x05\x04\r\x02\0\x03\x12\x04\
+    \x97\x01\x1c\x1d\n\x0c\n\x04\x04\r\x02\x01\x12\x04\x98\x01\x08\x1f\n\x0f\
+    \n\x05\x04\r\x02\x01\x04\x12\x06\x98\x01\x08\x97\x01\x1e\n\r\n\x05\x04\r\
+    \x02\x01\x05\x12\x04\x98\x01\x08\x0e\n\r\n\x05\x04\r\x02\x01\x01\x12\x04\
+    \x98\x01\x0f\x1a\n\r\n\x05\x04\r\x02\x01\x03\x12\x04\x98\x01\x1d\x1e\n\
+    \x0c\n\x04\x04\r\x02\x02\x12\x04\x99\x01\x08\x18\n\x0f\n\x05\x04\r\x02\
+    \x02\x04\x12\x06\x99\x01\x08\x98\x01\x1f\n\r\n\x05\x04\r\x02\x02\x05\x12\
+    \x04\x99\x01\x08\x0e\n\r\n\x05\x04\r\x02\x02\x01\x12\x04\x99\x01\x0f\x13\
+    \n\r\n\x05\x04\r\x02\x02\x03\x12\x04\x99\x01\x16\x17\n\x0c\n\x04\x04\r\
+    \x02\x03\x12\x04\x9a\x01\x08'\n\x0f\n\x05\x04\r\x02\x03\x04\x12\x06\x9a\
+    \x01\x08\x99\x01\x18\n\r\n\x05\x04\r\x02\x03\x06\x12\x04\x9a\x01\x08\x1b\
+    \n\r\n\x05\x04\r\x02\x03\x01\x12\x04\x9a\x01\x1c\"\n\r\n\x05\x04\r\x02\
+    \x03\x03\x12\x04\x9a\x01%&\n\x0c\n\x04\x04\r\x02\x04\x12\x04\x9b\x01\x08\
+    \x20\n\x0f\n\x05\x04\r\x02\x04\x04\x12\x06\x9b\x01\x08\x9a\x01'\n\r\n\
+    \x05\x04\r\x02\x04\x05\x12\x04\x9b\x01\x08\x0e\n\r\n\x05\x04\r\x02\x04\
+    \x01\x12\x04\x9b\x01\x0f\x1b\n\r\n\x05\x04\r\x02\x04\x03\x12\x04\x9b\x01\
+    \x1e\x1f\n\x0c\n\x04\x04\r\x02\x05\x12\x04\x9c\x01\x08&\n\x0f\n\x05\x04\
+    \r\x02\x05\x04\x12\x06\x9c\x01\x08\x9b\x01\x20\n\r\n\x05\x04\r\x02\x05\
+    \x06\x12\x04\x9c\x01\x08\x1b\n\r\n\x05\x04\r\x02\x05\x01\x12\x04\x9c\x01\
+    \x1c!\n\r\n\x05\x04\r\x02\x05\x03\x12\x04\x9c\x01$%\n\x0c\n\x04\x04\r\
+    \x02\x06\x12\x04\x9d\x01\x08#\n\x0f\n\x05\x04\r\x02\x06\x04\x12\x06\x9d\
+    \x01\x08\x9c\x01&\n\r\n\x05\x04\r\x02\x06\x05\x12\x04\x9d\x01\x08\x0e\n\
+    \r\n\x05\x04\r\x02\x06\x01\x12\x04\x9d\x01\x0f\x1e\n\r\n\x05\x04\r\x02\
+    \x06\x03\x12\x04\x9d\x01!\"\n\x0c\n\x02\x05\x03\x12\x06\xa0\x01\0\xa7\
+    \x01\x01\n\x0b\n\x03\x05\x03\x01\x12\x04\xa0\x01\x05\x13\n\x1f\n\x04\x05\
+    \x03\x02\0\x12\x04\xa2\x01\x08\x11\x1a\x11\x20answers\x20a\x20ping.\n\n\
+    \r\n\x05\x05\x03\x02\0\x01\x12\x04\xa2\x01\x08\x0c\n\r\n\x05\x05\x03\x02\
+    \0\x02\x12\x04\xa2\x01\x0f\x10\n\x0c\n\x04\x05\x03\x02\x01\x12\x04\xa3\
+    \x01\x08\x15\n\r\n\x05\x05\x03\x02\x01\x01\x12\x04\xa3\x01\x08\x10\n\r\n\
+    \x05\x05\x03\x02\x01\x02\x12\x04\xa3\x01\x13\x14\n\x0c\n\x04\x05\x03\x02\
+    \x02\x12\x04\xa4\x01\x08\x14\n\r\n\x05\x05\x03\x02\x02\x01\x12\x04\xa4\
+    \x01\x08\x0f\n\r\n\x05\x05\x03\x02\x02\x02\x12\x04\xa4\x01\x12\x13\nL\n\
+    \x04\x05\x03\x02\x03\x12\x04\xa6\x01\x08\x14\x1a>\x20the\x20registry\x20\
+    dropped\x20the\x20service,\x20the\x20stream\x20is\x20closed\x20next.\n\n\
+    \r\n\x05\x05\x03\x02\x03\x01\x12\x04\xa6\x01\x08\x0f\n\r\n\x05\x05\x03\
+    \x02\x03\x02\x12\x04\xa6\x01\x12\x13\nW\n\x02\x04\x0e\x12\x06\xaa\x01\0\
+    \xb0\x01\x01\x1aI\x20`lease_ttl_ms`\x20is\x20only\x20set\x20for\x20ACCEP\
+    TED,\x20`error`\x20and\x20`msg`\x20for\x20REFUSED.\n\n\x0b\n\x03\x04\x0e\
+    \x01\x12\x04\xaa\x01\x08\x17\n\x0c\n\x04\x04\x0e\x02\0\x12\x04\xab\x01\
+    \x08#\n\x0f\n\x05\x04\x0e\x02\0\x04\x12\x06\xab\x01\x08\xaa\x01\x19\n\r\
+    \n\x05\x04\x0e\x02\0\x06\x12\x04\xab\x01\x08\x16\n\r\n\x05\x04\x0e\x02\0\
+    \x01\x12\x04\xab\x01\x17\x1e\n\r\n\x05\x04\x0e\x02\0\x03\x12\x04\xab\x01\
+    !\"\n\x0c\n\x04\x04\x0e\x02\x01\x12\x04\xac\x01\x08\"\n\x0f\n\x05\x04\
+    \x0e\x02\x01\x04\x12\x06\xac\x01\x08\xab\x01#\n\r\n\x05\x04\x0e\x02\x01\
+    \x05\x12\x04\xac\x01\x08\x0e\n\r\n\x05\x04\x0e\x02\x01\x01\x12\x04\xac\
+    \x01\x0f\x1d\n\r\n\x05\x04\x0e\x02\x01\x03\x12\x04\xac\x01\x20!\n\x0c\n\
+    \x04\x04\x0e\x02\x02\x12\x04\xad\x01\x08\x20\n\x0f\n\x05\x04\x0e\x02\x02\
+    \x04\x12\x06\xad\x01\x08\xac\x01\"\n\r\n\x05\x04\x0e\x02\x02\x05\x12\x04\
+    \xad\x01\x08\x0e\n\r\n\x05\x04\x0e\x02\x02\x01\x12\x04\xad\x01\x0f\x1b\n\
+    \r\n\x05\x04\x0e\x02\x02\x03\x12\x04\xad\x01\x1e\x1f\n\x0c\n\x04\x04\x0e\
+    \x02\x03\x12\x04\xae\x01\x08$\n\x0f\n\x05\x04\x0e\x02\x03\x04\x12\x06\
+    \xae\x01\x08\xad\x01\x20\n\r\n\x05\x04\x0e\x02\x03\x06\x12\x04\xae\x01\
+    \x08\x19\n\r\n\x05\x04\x0e\x02\x03\x01\x12\x04\xae\x01\x1a\x1f\n\r\n\x05\
+    \x04\x0e\x02\x03\x03\x12\x04\xae\x01\"#\n\x0c\n\x04\x04\x0e\x02\x04\x12\
+    \x04\xaf\x01\x08\x17\n\x0f\n\x05\x04\x0e\x02\x04\x04\x12\x06\xaf\x01\x08\
+    \xae\x01$\n\r\n\x05\x04\x0e\x02\x04\x05\x12\x04\xaf\x01\x08\x0e\n\r\n\
+    \x05\x04\x0e\x02\x04\x01\x12\x04\xaf\x01\x0f\x12\n\r\n\x05\x04\x0e\x02\
+    \x04\x03\x12\x04\xaf\x01\x15\x16\n\x0c\n\x02\x06\0\x12\x06\xb2\x01\0\xba\
+    \x01\x01\n\x0b\n\x03\x06\0\x01\x12\x04\xb2\x01\x08\x10\n\x0c\n\x04\x06\0\
+    \x02\0\x12\x04\xb3\x01\x08C\n\r\n\x05\x06\0\x02\0\x01\x12\x04\xb3\x01\
+    \x0c\x14\n\r\n\x05\x06\0\x02\0\x02\x12\x04\xb3\x01\x15$\n\r\n\x05\x06\0\
+    \x02\0\x03\x12\x04\xb3\x01/?\n\x0c\n\x04\x06\0\x02\x01\x12\x04\xb4\x01\
+    \x08C\n\r\n\x05\x06\0\x02\x01\x01\x12\x04\xb4\x01\x0c\x18\n\r\n\x05\x06\
+    \0\x02\x01\x02\x12\x04\xb4\x01\x19&\n\r\n\x05\x06\0\x02\x01\x03\x12\x04\
+    \xb4\x011?\n\x0c\n\x04\x06\0\x02\x02\x12\x04\xb5\x01\x08I\n\r\n\x05\x06\
+    \0\x02\x02\x01\x12\x04\xb5\x01\x0c\x16\n\r\n\x05\x06\0\x02\x02\x02\x12\
+    \x04\xb5\x01\x17(\n\r\n\x05\x06\0\x02\x02\x03\x12\x04\xb5\x013E\n\x0c\n\
+    \x04\x06\0\x02\x03\x12\x04\xb6\x01\x08>\n\r\n\x05\x06\0\x02\x03\x01\x12\
+    \x04\xb6\x01\x0c\x11\n\r\n\x05\x06\0\x02\x03\x02\x12\x04\xb6\x01\x12\x1e\
+    \n\r\n\x05\x06\0\x02\x03\x06\x12\x04\xb6\x01)/\n\r\n\x05\x06\0\x02\x03\
+    \x03\x12\x04\xb6\x010:\n\x0c\n\x04\x06\0\x02\x04\x12\x04\xb7\x01\x08I\n\
+    \r\n\x05\x06\0\x02\x04\x01\x12\x04\xb7\x01\x0c\x16\n\r\n\x05\x06\0\x02\
+    \x04\x02\x12\x04\xb7\x01\x17(\n\r\n\x05\x06\0\x02\x04\x03\x12\x04\xb7\
+    \x013E\n\x0c\n\x04\x06\0\x02\x05\x12\x04\xb8\x01\x08F\n\r\n\x05\x06\0\
+    \x02\x05\x01\x12\x04\xb8\x01\x0c\x15\n\r\n\x05\x06\0\x02\x05\x02\x12\x04\
+    \xb8\x01\x16&\n\r\n\x05\x06\0\x02\x05\x03\x12\x04\xb8\x011B\n\x0c\n\x04\
+    \x06\0\x02\x06\x12\x04\xb9\x01\x08R\n\r\n\x05\x06\0\x02\x06\x01\x12\x04\
+    \xb9\x01\x0c\x17\n\r\n\x05\x06\0\x02\x06\x05\x12\x04\xb9\x01\x18\x1e\n\r\
+    \n\x05\x06\0\x02\x06\x02\x12\x04\xb9\x01\x1f-\n\r\n\x05\x06\0\x02\x06\
+    \x06\x12\x04\xb9\x018>\n\r\n\x05\x06\0\x02\x06\x03\x12\x04\xb9\x01?Nb\
+    \x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
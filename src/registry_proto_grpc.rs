@@ -39,6 +39,34 @@ const METHOD_REGISTER_RE_REGISTER: ::grpcio::Method<super::registry_proto::ReReg
     resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
 };
 
+const METHOD_REGISTER_WATCH: ::grpcio::Method<super::registry_proto::WatchRequest, super::registry_proto::WatchEvent> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::ServerStreaming,
+    name: "/register.Register/Watch",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_REGISTER_DEREGISTER: ::grpcio::Method<super::registry_proto::DeregisterRequest, super::registry_proto::DeregisterResponse> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/register.Register/Deregister",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_REGISTER_KEEP_ALIVE: ::grpcio::Method<super::registry_proto::KeepAliveRequest, super::registry_proto::KeepAliveResponse> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/register.Register/KeepAlive",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_REGISTER_OPEN_SESSION: ::grpcio::Method<super::registry_proto::SessionRequest, super::registry_proto::SessionResponse> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Duplex,
+    name: "/register.Register/OpenSession",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
 pub struct RegisterClient {
     client: ::grpcio::Client,
 }
@@ -97,6 +125,54 @@ impl RegisterClient {
     pub fn re_register_async(&self, req: &super::registry_proto::ReRegisterRequest) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::registry_proto::ReRegisterResponse>> {
         self.re_register_async_opt(req, ::grpcio::CallOption::default())
     }
+
+    pub fn watch_opt(&self, req: &super::registry_proto::WatchRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientSStreamReceiver<super::registry_proto::WatchEvent>> {
+        self.client.server_streaming(&METHOD_REGISTER_WATCH, req, opt)
+    }
+
+    pub fn watch(&self, req: &super::registry_proto::WatchRequest) -> ::grpcio::Result<::grpcio::ClientSStreamReceiver<super::registry_proto::WatchEvent>> {
+        self.watch_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn deregister_opt(&self, req: &super::registry_proto::DeregisterRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::registry_proto::DeregisterResponse> {
+        self.client.unary_call(&METHOD_REGISTER_DEREGISTER, req, opt)
+    }
+
+    pub fn deregister(&self, req: &super::registry_proto::DeregisterRequest) -> ::grpcio::Result<super::registry_proto::DeregisterResponse> {
+        self.deregister_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn deregister_async_opt(&self, req: &super::registry_proto::DeregisterRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::registry_proto::DeregisterResponse>> {
+        self.client.unary_call_async(&METHOD_REGISTER_DEREGISTER, req, opt)
+    }
+
+    pub fn deregister_async(&self, req: &super::registry_proto::DeregisterRequest) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::registry_proto::DeregisterResponse>> {
+        self.deregister_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn keep_alive_opt(&self, req: &super::registry_proto::KeepAliveRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::registry_proto::KeepAliveResponse> {
+        self.client.unary_call(&METHOD_REGISTER_KEEP_ALIVE, req, opt)
+    }
+
+    pub fn keep_alive(&self, req: &super::registry_proto::KeepAliveRequest) -> ::grpcio::Result<super::registry_proto::KeepAliveResponse> {
+        self.keep_alive_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn keep_alive_async_opt(&self, req: &super::registry_proto::KeepAliveRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::registry_proto::KeepAliveResponse>> {
+        self.client.unary_call_async(&METHOD_REGISTER_KEEP_ALIVE, req, opt)
+    }
+
+    pub fn keep_alive_async(&self, req: &super::registry_proto::KeepAliveRequest) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::registry_proto::KeepAliveResponse>> {
+        self.keep_alive_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn open_session_opt(&self, opt: ::grpcio::CallOption) -> ::grpcio::Result<(::grpcio::ClientDuplexSender<super::registry_proto::SessionRequest>, ::grpcio::ClientDuplexReceiver<super::registry_proto::SessionResponse>)> {
+        self.client.duplex_streaming(&METHOD_REGISTER_OPEN_SESSION, opt)
+    }
+
+    pub fn open_session(&self) -> ::grpcio::Result<(::grpcio::ClientDuplexSender<super::registry_proto::SessionRequest>, ::grpcio::ClientDuplexReceiver<super::registry_proto::SessionResponse>)> {
+        self.open_session_opt(::grpcio::CallOption::default())
+    }
     pub fn spawn<F>(&self, f: F) where F: ::futures::Future<Item = (), Error = ()> + Send + 'static {
         self.client.spawn(f)
     }
@@ -106,6 +182,10 @@ pub trait Register {
     fn register(&self, ctx: ::grpcio::RpcContext, req: super::registry_proto::RegisterRequest, sink: ::grpcio::UnarySink<super::registry_proto::RegisterResponse>);
     fn report_status(&self, ctx: ::grpcio::RpcContext, req: super::registry_proto::StatusRequest, sink: ::grpcio::UnarySink<super::registry_proto::StatusResponse>);
     fn re_register(&self, ctx: ::grpcio::RpcContext, req: super::registry_proto::ReRegisterRequest, sink: ::grpcio::UnarySink<super::registry_proto::ReRegisterResponse>);
+    fn watch(&self, ctx: ::grpcio::RpcContext, req: super::registry_proto::WatchRequest, sink: ::grpcio::ServerStreamingSink<super::registry_proto::WatchEvent>);
+    fn deregister(&self, ctx: ::grpcio::RpcContext, req: super::registry_proto::DeregisterRequest, sink: ::grpcio::UnarySink<super::registry_proto::DeregisterResponse>);
+    fn keep_alive(&self, ctx: ::grpcio::RpcContext, req: super::registry_proto::KeepAliveRequest, sink: ::grpcio::UnarySink<super::registry_proto::KeepAliveResponse>);
+    fn open_session(&self, ctx: ::grpcio::RpcContext, stream: ::grpcio::RequestStream<super::registry_proto::SessionRequest>, sink: ::grpcio::DuplexSink<super::registry_proto::SessionResponse>);
 }
 
 pub fn create_register<S: Register + Send + Clone + 'static>(s: S) -> ::grpcio::Service {
@@ -122,5 +202,21 @@ pub fn create_register<S: Register + Send + Clone + 'static>(s: S) -> ::grpcio::
     builder = builder.add_unary_handler(&METHOD_REGISTER_RE_REGISTER, move |ctx, req, resp| {
         instance.re_register(ctx, req, resp)
     });
+    let instance = s.clone();
+    builder = builder.add_server_streaming_handler(&METHOD_REGISTER_WATCH, move |ctx, req, resp| {
+        instance.watch(ctx, req, resp)
+    });
+    let instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_REGISTER_DEREGISTER, move |ctx, req, resp| {
+        instance.deregister(ctx, req, resp)
+    });
+    let instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_REGISTER_KEEP_ALIVE, move |ctx, req, resp| {
+        instance.keep_alive(ctx, req, resp)
+    });
+    let instance = s.clone();
+    builder = builder.add_duplex_streaming_handler(&METHOD_REGISTER_OPEN_SESSION, move |ctx, req, resp| {
+        instance.open_session(ctx, req, resp)
+    });
     builder.build()
 }
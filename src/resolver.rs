@@ -0,0 +1,568 @@
+use std::thread::{self, JoinHandle};
+use std::net::{SocketAddr, IpAddr};
+use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, BTreeMap};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::cmp;
+
+use futures::{Future, Stream};
+use futures::future::{self, Either, Loop};
+use futures::sync::oneshot::{self, Sender};
+use grpcio::{ChannelBuilder, ClientSStreamReceiver, EnvBuilder, Error as GrpcError};
+use rand::{self, Rng};
+use tokio_core::reactor::{Core, Timeout};
+
+use super::registry_proto_grpc::*;
+use super::registry_proto::*;
+use super::{Service, ServiceId, ServiceEvent, Health};
+use tls::ClientTls;
+
+// virtual nodes of every instance on the consistent hash ring.
+const VIRTUAL_NODES: usize = 64;
+// delay before subscribing again to a remote registry, doubled after every
+// subscription that receives nothing.
+const MIN_RESUBSCRIBE_MS: u64 = 100;
+const MAX_RESUBSCRIBE_MS: u64 = 10_000;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    RoundRobin,
+    Random,
+    LeastRecentlyUsed,
+    // instances are picked by the key passed to `Resolver::pick_by_key`,
+    // `Resolver::pick` falls back to round robin.
+    ConsistentHash,
+}
+
+#[derive(Debug)]
+struct Instance {
+    service: Service,
+    health: Health,
+    last_used: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct Group {
+    instances: Vec<Instance>,
+    next: usize,
+    // hash of a virtual node -> the instance, instances may share a service addr
+    // when only their heartbeat ports differ, so the whole service is hashed.
+    ring: BTreeMap<u64, Service>,
+}
+
+impl Group {
+    fn position(&self, service: &Service) -> Option<usize> {
+        self.instances.iter().position(|i| i.service == *service)
+    }
+
+    fn rebuild_ring(&mut self) {
+        self.ring.clear();
+        for instance in &self.instances {
+            for n in 0..VIRTUAL_NODES {
+                self.ring
+                    .insert(hash(&(&instance.service, n)), instance.service.clone());
+            }
+        }
+    }
+
+    // suspect instances are only picked if there is no healthy one.
+    fn candidates(&self) -> Vec<usize> {
+        let healthy = self.instances
+            .iter()
+            .enumerate()
+            .filter(|&(_, i)| i.health == Health::Healthy)
+            .map(|(n, _)| n)
+            .collect::<Vec<_>>();
+        if !healthy.is_empty() {
+            return healthy;
+        }
+        self.instances
+            .iter()
+            .enumerate()
+            .filter(|&(_, i)| i.health != Health::Down)
+            .map(|(n, _)| n)
+            .collect()
+    }
+
+    fn pick(&mut self, strategy: Strategy, key: Option<u64>) -> Option<Service> {
+        let candidates = self.candidates();
+        if candidates.is_empty() {
+            return None;
+        }
+        let n = match (strategy, key) {
+            (Strategy::Random, _) => candidates[rand::thread_rng().gen_range(0, candidates.len())],
+            (Strategy::LeastRecentlyUsed, _) => {
+                *candidates
+                    .iter()
+                    .min_by_key(|&&n| self.instances[n].last_used)
+                    .unwrap()
+            }
+            (Strategy::ConsistentHash, Some(key)) => self.pick_on_ring(key, &candidates),
+            _ => {
+                self.next = self.next.wrapping_add(1);
+                candidates[self.next % candidates.len()]
+            }
+        };
+        let instance = &mut self.instances[n];
+        instance.last_used = Some(Instant::now());
+        Some(instance.service.clone())
+    }
+
+    // walk the ring clockwise from `key` to the first virtual node of a candidate.
+    fn pick_on_ring(&self, key: u64, candidates: &[usize]) -> usize {
+        let services = candidates
+            .iter()
+            .map(|&n| (&self.instances[n].service, n))
+            .collect::<HashMap<_, _>>();
+        self.ring
+            .range(key..)
+            .chain(self.ring.iter())
+            .filter_map(|(_, service)| services.get(service))
+            .next()
+            .cloned()
+            .unwrap_or(candidates[0])
+    }
+}
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+// a live view of the services in a registry, fed either by the registry
+// callbacks (`add`, `remove`, `set_health`, `update`), by `apply` with the events of
+// `Registry::watch`, or by `watch_remote`.
+#[derive(Clone)]
+pub struct Resolver {
+    strategy: Strategy,
+    groups: Arc<Mutex<HashMap<ServiceId, Group>>>,
+}
+
+impl Resolver {
+    pub fn new(strategy: Strategy) -> Self {
+        Resolver {
+            strategy: strategy,
+            groups: Default::default(),
+        }
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    pub fn add(&self, service: Service) {
+        let mut groups = self.groups.lock().unwrap();
+        let group = groups.entry(service.sid).or_insert_with(Group::default);
+        if group.position(&service).is_some() {
+            return;
+        }
+        group.instances.push(Instance {
+            service: service,
+            health: Health::Healthy,
+            last_used: None,
+        });
+        group.rebuild_ring();
+    }
+
+    pub fn remove(&self, service: &Service) {
+        let mut groups = self.groups.lock().unwrap();
+        let empty = match groups.get_mut(&service.sid) {
+            Some(group) => {
+                if let Some(n) = group.position(service) {
+                    group.instances.remove(n);
+                    group.rebuild_ring();
+                }
+                group.instances.is_empty()
+            }
+            None => false,
+        };
+        if empty {
+            groups.remove(&service.sid);
+        }
+    }
+
+    pub fn set_health(&self, service: &Service, health: Health) {
+        if health == Health::Down {
+            return self.remove(service);
+        }
+        let mut groups = self.groups.lock().unwrap();
+        if let Some(group) = groups.get_mut(&service.sid) {
+            if let Some(n) = group.position(service) {
+                group.instances[n].health = health;
+            }
+        }
+    }
+
+    // an instance came back with other addresses or ports, it keeps its health.
+    pub fn update(&self, old: &Service, service: Service) {
+        {
+            let mut groups = self.groups.lock().unwrap();
+            if let Some(group) = groups.get_mut(&old.sid) {
+                if let Some(n) = group.position(old) {
+                    group.instances[n].service = service;
+                    group.rebuild_ring();
+                    return;
+                }
+            }
+        }
+        self.add(service)
+    }
+
+    // forget the services in `ids`, or all services if it is empty.
+    pub fn clear(&self, ids: &[ServiceId]) {
+        let mut groups = self.groups.lock().unwrap();
+        if ids.is_empty() {
+            groups.clear();
+        }
+        for id in ids {
+            groups.remove(id);
+        }
+    }
+
+    pub fn apply(&self, event: ServiceEvent) {
+        match event {
+            ServiceEvent::Added(s) => self.add(s),
+            ServiceEvent::Dropped(s) => self.remove(&s),
+            ServiceEvent::HealthChanged(s, health) => self.set_health(&s, health),
+            ServiceEvent::Updated(old, s) => self.update(&old, s),
+        }
+    }
+
+    pub fn services(&self, sid: ServiceId) -> Vec<Service> {
+        let groups = self.groups.lock().unwrap();
+        groups
+            .get(&sid)
+            .map(|group| {
+                group.instances.iter().map(|i| i.service.clone()).collect()
+            })
+            .unwrap_or_else(Vec::new)
+    }
+
+    pub fn pick(&self, sid: ServiceId) -> Option<Service> {
+        let mut groups = self.groups.lock().unwrap();
+        groups
+            .get_mut(&sid)
+            .and_then(|group| group.pick(self.strategy, None))
+    }
+
+    // the same key is mapped to the same instance as long as the instance is
+    // alive, only meaningful with `Strategy::ConsistentHash`.
+    pub fn pick_by_key<K: Hash>(&self, sid: ServiceId, key: &K) -> Option<Service> {
+        let mut groups = self.groups.lock().unwrap();
+        groups
+            .get_mut(&sid)
+            .and_then(|group| group.pick(self.strategy, Some(hash(key))))
+    }
+
+    // follow the `Watch` stream of a remote registry, only services in `ids`
+    // are watched unless it is empty. when the stream ends, e.g. the registry
+    // restarted, the watched services are forgotten and the resolver subscribes
+    // again with backoff, the view is rebuilt from the snapshot sent then.
+    pub fn watch_remote(
+        &self,
+        registry_addr: SocketAddr,
+        ids: Vec<ServiceId>,
+    ) -> Result<RemoteWatch, GrpcError> {
+        self.start_remote_watch(registry_addr, ids, None)
+    }
+
+    pub fn watch_remote_tls(
+        &self,
+        registry_addr: SocketAddr,
+        ids: Vec<ServiceId>,
+        tls: &ClientTls,
+    ) -> Result<RemoteWatch, GrpcError> {
+        self.start_remote_watch(registry_addr, ids, Some(tls))
+    }
+
+    fn start_remote_watch(
+        &self,
+        registry_addr: SocketAddr,
+        ids: Vec<ServiceId>,
+        tls: Option<&ClientTls>,
+    ) -> Result<RemoteWatch, GrpcError> {
+        let env = Arc::new(EnvBuilder::new().build());
+        let addr = format!("{}", registry_addr);
+        let ch = match tls {
+            Some(tls) => ChannelBuilder::new(env).secure_connect(&addr, tls.credentials()),
+            None => ChannelBuilder::new(env).connect(&addr),
+        };
+        let client = RegisterClient::new(ch);
+        let mut req = WatchRequest::new();
+        req.set_service_ids(ids.iter().map(|id| id.0).collect());
+        let events = client.watch(&req)?;
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let resolver = self.clone();
+        let thread_handle = thread::Builder::new()
+            .name("resolver_remote_watch".to_string())
+            .spawn(move || {
+                let mut core = Core::new().unwrap();
+                let handle = core.handle();
+                let min = Duration::from_millis(MIN_RESUBSCRIBE_MS);
+                // the client is owned by the loop, it lives as long as the streams.
+                let watching = future::loop_fn((min, Some(events)), move |(backoff, events)| {
+                    let events = match events {
+                        Some(events) => Ok(events),
+                        None => client.watch(&req),
+                    };
+                    let received = Rc::new(Cell::new(false));
+                    let (resolver, ids, handle) = (resolver.clone(), ids.clone(), handle.clone());
+                    follow(events, resolver.clone(), received.clone()).then(move |_| {
+                        // the registry may have dropped services meanwhile.
+                        resolver.clear(&ids);
+                        let delay = if received.get() { min } else { backoff };
+                        let max = Duration::from_millis(MAX_RESUBSCRIBE_MS);
+                        let next = cmp::min(delay * 2, max);
+                        Timeout::new(delay, &handle)
+                            .unwrap()
+                            .then(move |_| Ok(Loop::Continue((next, None))))
+                    })
+                });
+                let watching = watching.map(|()| ()).map_err(|()| ());
+                let _ = core.run(shutdown_rx.map_err(|_| ()).select(watching));
+            })
+            .unwrap();
+
+        Ok(RemoteWatch {
+            shutdown_sender: Some(shutdown_tx),
+            thread_handle: Some(thread_handle),
+        })
+    }
+}
+
+// stops following the remote registry when dropped.
+pub struct RemoteWatch {
+    shutdown_sender: Option<Sender<()>>,
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for RemoteWatch {
+    fn drop(&mut self) {
+        // the thread may have exited because the stream was closed.
+        let _ = self.shutdown_sender.take().unwrap().send(());
+        self.thread_handle.take().unwrap().join().unwrap();
+    }
+}
+
+// apply the events of one subscription, `received` is set once any arrives.
+fn follow(
+    events: Result<ClientSStreamReceiver<WatchEvent>, GrpcError>,
+    resolver: Resolver,
+    received: Rc<Cell<bool>>,
+) -> impl Future<Item = (), Error = ()> {
+    let events = match events {
+        Ok(events) => events,
+        Err(e) => {
+            warn!("subscribe to remote registry failed: {:?}", e);
+            return Either::A(future::err(()));
+        }
+    };
+    let f = events
+        .for_each(move |e| {
+            received.set(true);
+            match event_from_watch_event(e) {
+                Some(event) => resolver.apply(event),
+                None => warn!("receive a malformed watch event"),
+            }
+            Ok(())
+        })
+        .map_err(|e| warn!("remote watch stream closed: {:?}", e));
+    Either::B(f)
+}
+
+fn service_from_info(info: &ServiceInfo) -> Option<Service> {
+    let host = match info.get_host().parse::<IpAddr>() {
+        Ok(host) => host,
+        Err(_) => return None,
+    };
+    if info.service_port > u32::from(u16::max_value()) ||
+        info.heartbeat_port > u32::from(u16::max_value()) ||
+        info.get_ports().values().any(|p| *p > u32::from(u16::max_value()))
+    {
+        return None;
+    }
+    Some(Service {
+        sid: info.service_id.into(),
+        instance_id: info.get_instance_id().to_string().into(),
+        meta: info.get_meta().to_string(),
+        labels: info.get_labels()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+        host: host,
+        service_port: info.service_port as u16,
+        heartbeat_port: info.heartbeat_port as u16,
+        ports: info.get_ports()
+            .iter()
+            .map(|(k, v)| (k.clone(), *v as u16))
+            .collect(),
+    })
+}
+
+fn event_from_watch_event(e: WatchEvent) -> Option<ServiceEvent> {
+    let service = match service_from_info(e.get_service()) {
+        Some(service) => service,
+        None => return None,
+    };
+    let event = match e.kind {
+        EventKind::ADDED => ServiceEvent::Added(service),
+        EventKind::DROPPED => ServiceEvent::Dropped(service),
+        EventKind::HEALTH_CHANGED => {
+            let health = match e.health {
+                HealthState::HEALTHY => Health::Healthy,
+                HealthState::SUSPECT => Health::Suspect,
+                HealthState::DOWN => Health::Down,
+            };
+            ServiceEvent::HealthChanged(service, health)
+        }
+        EventKind::UPDATED => match service_from_info(e.get_old_service()) {
+            Some(old) => ServiceEvent::Updated(old, service),
+            None => return None,
+        },
+    };
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::collections::HashSet;
+
+    use super::{Resolver, Strategy, event_from_watch_event};
+    use super::super::{Service, ServiceId, ServiceEvent, Health};
+
+    fn service(sid: u64, port: u16) -> Service {
+        Service {
+            sid: ServiceId(sid),
+            instance_id: Default::default(),
+            meta: String::new(),
+            labels: Default::default(),
+            host: "127.0.0.1".parse::<IpAddr>().unwrap(),
+            service_port: port,
+            heartbeat_port: port + 5_000,
+            ports: Default::default(),
+        }
+    }
+
+    fn resolver(strategy: Strategy) -> Resolver {
+        let resolver = Resolver::new(strategy);
+        for port in 20_000..20_003 {
+            resolver.add(service(1, port));
+        }
+        resolver.add(service(2, 20_003));
+        resolver
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let resolver = resolver(Strategy::RoundRobin);
+        let picked = (0..6)
+            .map(|_| resolver.pick(ServiceId(1)).unwrap().service_port)
+            .collect::<Vec<_>>();
+        assert_eq!(&picked[..3], &picked[3..]);
+        assert_eq!(picked[..3].iter().collect::<HashSet<_>>().len(), 3);
+        assert_eq!(resolver.pick(ServiceId(2)), Some(service(2, 20_003)));
+        assert_eq!(resolver.pick(ServiceId(3)), None);
+    }
+
+    #[test]
+    fn test_random() {
+        let resolver = resolver(Strategy::Random);
+        for _ in 0..10 {
+            assert_eq!(resolver.pick(ServiceId(1)).unwrap().service_id(), ServiceId(1));
+        }
+    }
+
+    #[test]
+    fn test_least_recently_used() {
+        let resolver = resolver(Strategy::LeastRecentlyUsed);
+        let picked = (0..3)
+            .map(|_| resolver.pick(ServiceId(1)).unwrap())
+            .collect::<HashSet<_>>();
+        assert_eq!(picked.len(), 3);
+
+        // a new instance has never been used.
+        resolver.add(service(1, 20_010));
+        assert_eq!(resolver.pick(ServiceId(1)), Some(service(1, 20_010)));
+    }
+
+    #[test]
+    fn test_consistent_hash() {
+        let resolver = resolver(Strategy::ConsistentHash);
+        let s = resolver.pick_by_key(ServiceId(1), &"user:1").unwrap();
+        for _ in 0..5 {
+            assert_eq!(resolver.pick_by_key(ServiceId(1), &"user:1"), Some(s.clone()));
+        }
+
+        // keys of other instances stay where they were.
+        let keys = (0..50).map(|n| format!("user:{}", n)).collect::<Vec<_>>();
+        let before = keys.iter()
+            .map(|k| resolver.pick_by_key(ServiceId(1), k).unwrap())
+            .collect::<Vec<_>>();
+        resolver.remove(&s);
+        for (k, b) in keys.iter().zip(before) {
+            let after = resolver.pick_by_key(ServiceId(1), k).unwrap();
+            assert_ne!(after, s);
+            if b != s {
+                assert_eq!(after, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_health() {
+        let resolver = resolver(Strategy::RoundRobin);
+        resolver.set_health(&service(1, 20_000), Health::Suspect);
+        resolver.set_health(&service(1, 20_001), Health::Suspect);
+        for _ in 0..4 {
+            assert_eq!(resolver.pick(ServiceId(1)), Some(service(1, 20_002)));
+        }
+
+        resolver.apply(ServiceEvent::Dropped(service(1, 20_002)));
+        let picked = (0..4)
+            .map(|_| resolver.pick(ServiceId(1)).unwrap())
+            .collect::<HashSet<_>>();
+        assert_eq!(picked.len(), 2);
+
+        resolver.apply(ServiceEvent::HealthChanged(service(1, 20_000), Health::Down));
+        resolver.apply(ServiceEvent::HealthChanged(service(1, 20_001), Health::Healthy));
+        assert_eq!(resolver.services(ServiceId(1)), vec![service(1, 20_001)]);
+    }
+
+    #[test]
+    fn test_update() {
+        let resolver = resolver(Strategy::RoundRobin);
+        resolver.set_health(&service(1, 20_000), Health::Suspect);
+        let event = ServiceEvent::Updated(service(1, 20_000), service(1, 20_010));
+        resolver.apply(event);
+        let services = resolver.services(ServiceId(1));
+        assert_eq!(services.len(), 3);
+        assert!(services.contains(&service(1, 20_010)));
+        assert!(!services.contains(&service(1, 20_000)));
+        // it is still suspect.
+        for _ in 0..4 {
+            assert!(resolver.pick(ServiceId(1)) != Some(service(1, 20_010)));
+        }
+
+        // an unknown instance is added.
+        resolver.update(&service(3, 20_000), service(3, 20_001));
+        assert_eq!(resolver.services(ServiceId(3)), vec![service(3, 20_001)]);
+    }
+
+    #[test]
+    fn test_watch_event_conversion() {
+        let s = service(1, 20_000);
+        let event = ServiceEvent::HealthChanged(s.clone(), Health::Suspect);
+        assert_eq!(event_from_watch_event(event.clone().into()), Some(event));
+        let event = ServiceEvent::Updated(s.clone(), service(1, 20_001));
+        assert_eq!(event_from_watch_event(event.clone().into()), Some(event));
+        let event = ServiceEvent::Added(s);
+        assert_eq!(event_from_watch_event(event.clone().into()), Some(event));
+    }
+}
@@ -1,28 +1,39 @@
-use std::io;
-use std::net::IpAddr;
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
-use std::sync::{Arc, Mutex};
-
-use grpcio::{Environment, ServerBuilder, RpcContext, UnarySink, Error as GrpcError,
-             Server as GrpcServer};
+use std::cmp;
+use std::str;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::ops::Range;
+use std::collections::{HashMap, HashSet, BTreeMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::{self, Rng};
+use grpcio::{Environment, ServerBuilder, RpcContext, UnarySink, ServerStreamingSink, WriteFlags,
+             RpcStatus, RpcStatusCode, Error as GrpcError, Server as GrpcServer, RequestStream,
+             DuplexSink};
 use super::registry_proto_grpc::*;
 use super::registry_proto::*;
-use futures::Future;
-
-use super::{ServiceId, Service};
+use futures::{Future, Stream, Sink};
+use futures::future::Either;
+use futures::sync::mpsc::{self as future_mpsc, UnboundedSender};
+use futures::sync::oneshot;
 
-fn fresh_session_id() -> usize {
-    static NEXT_ID: AtomicUsize = ATOMIC_USIZE_INIT;
-    NEXT_ID.fetch_add(1, Ordering::SeqCst)
-}
+use super::{ServiceId, InstanceId, Service, ServiceEvent, Health};
+use tls::ServerTls;
 
+// the high 32 bits are the epoch of the registry which created the session,
+// so a session id is never reused by a restarted registry.
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Debug)]
 struct SessionId(u64);
 
-impl From<usize> for SessionId {
-    fn from(u: usize) -> Self {
-        SessionId(u as u64)
+impl SessionId {
+    fn new(epoch: u32, seq: u32) -> Self {
+        SessionId(u64::from(epoch) << 32 | u64::from(seq))
+    }
+
+    fn epoch(&self) -> u32 {
+        (self.0 >> 32) as u32
     }
 }
 
@@ -36,128 +47,870 @@ impl From<u64> for SessionId {
 struct Session {
     session_id: SessionId,
     service_id: ServiceId,
+    instance_id: InstanceId,
     meta: String,
+    labels: BTreeMap<String, String>,
     host: IpAddr,
     service_port: u16,
     heartbeat_port: u16,
+    // named ports reported while the container is still stepping its own ports.
+    ports: BTreeMap<String, u16>,
+    // last time the container talked about this session.
+    touched: Instant,
 }
 
 impl Session {
-    const DEFAULT_SERVICE_PORT: u16 = 20_000;
-    const DEFAULT_HEARTBEAT_PORT: u16 = 25_000;
-
-    fn new<T: Into<IpAddr>>(service_id: ServiceId, meta: String, host: T) -> Self {
+    fn new<T: Into<IpAddr>>(
+        session_id: SessionId,
+        service_id: ServiceId,
+        meta: String,
+        labels: BTreeMap<String, String>,
+        host: T,
+        (service_port, heartbeat_port): (u16, u16),
+    ) -> Self {
         Session {
-            session_id: fresh_session_id().into(),
+            session_id: session_id,
             service_id: service_id,
+            instance_id: InstanceId::default(),
             meta: meta,
+            labels: labels,
             host: host.into(),
-            service_port: Self::DEFAULT_SERVICE_PORT,
-            heartbeat_port: Self::DEFAULT_HEARTBEAT_PORT,
+            service_port: service_port,
+            heartbeat_port: heartbeat_port,
+            ports: BTreeMap::new(),
+            touched: Instant::now(),
+        }
+    }
+
+    fn touch(&mut self) {
+        self.touched = Instant::now();
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        self.touched.elapsed() >= ttl
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PortKind {
+    Service,
+    Heartbeat,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AllocError {
+    // every port of the range is taken on the host.
+    RangeExhausted(PortKind),
+    // the port proposed by the container is taken on the host.
+    PortInUse(PortKind, u16),
+}
+
+impl AllocError {
+    pub fn is_range_exhausted(&self) -> bool {
+        match *self {
+            AllocError::RangeExhausted(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_port_in_use(&self) -> bool {
+        match *self {
+            AllocError::PortInUse(..) => true,
+            _ => false,
         }
     }
+}
+
+// decide the ports proposed to a container, `used` holds the ports taken on
+// the container's host by the registered services and the pending sessions.
+// port 0 asks the container to bind any port and report the one it got.
+pub trait PortAllocator: Send + Sync {
+    // `proposed` is the port asked for by the container, 0 if it does not care.
+    fn allocate(&self, kind: PortKind, proposed: u16, used: &HashSet<u16>)
+        -> Result<u16, AllocError>;
+
+    // the container failed to bind `failed`, which is in `used`.
+    fn next(&self, kind: PortKind, failed: u16, used: &HashSet<u16>) -> Result<u16, AllocError>;
+}
+
+impl<A: PortAllocator + ?Sized> PortAllocator for Arc<A> {
+    fn allocate(&self, kind: PortKind, proposed: u16, used: &HashSet<u16>)
+        -> Result<u16, AllocError> {
+        (**self).allocate(kind, proposed, used)
+    }
+
+    fn next(&self, kind: PortKind, failed: u16, used: &HashSet<u16>) -> Result<u16, AllocError> {
+        (**self).next(kind, failed, used)
+    }
+}
+
+// the first free port of the range, moving forward on failures.
+#[derive(Clone, Debug)]
+pub struct Sequential {
+    service_ports: Range<u16>,
+    heartbeat_ports: Range<u16>,
+}
 
-    fn step_service_port(&mut self) {
-        self.service_port += 1;
+impl Sequential {
+    pub const DEFAULT_SERVICE_PORTS: Range<u16> = 20_000..25_000;
+    pub const DEFAULT_HEARTBEAT_PORTS: Range<u16> = 25_000..30_000;
+
+    pub fn new(service_ports: Range<u16>, heartbeat_ports: Range<u16>) -> Self {
+        Sequential {
+            service_ports: service_ports,
+            heartbeat_ports: heartbeat_ports,
+        }
+    }
+
+    fn range(&self, kind: PortKind) -> &Range<u16> {
+        match kind {
+            PortKind::Service => &self.service_ports,
+            PortKind::Heartbeat => &self.heartbeat_ports,
+        }
     }
 
-    fn step_heartbeat_port(&mut self) {
-        self.heartbeat_port += 1;
+    // the first port in the range from `from` which is not used.
+    fn free_port(&self, kind: PortKind, from: u16, used: &HashSet<u16>) -> Result<u16, AllocError> {
+        let range = self.range(kind);
+        (cmp::max(from, range.start)..range.end)
+            .find(|p| !used.contains(p))
+            .ok_or(AllocError::RangeExhausted(kind))
+    }
+}
+
+impl Default for Sequential {
+    fn default() -> Self {
+        Sequential::new(Self::DEFAULT_SERVICE_PORTS, Self::DEFAULT_HEARTBEAT_PORTS)
+    }
+}
+
+impl PortAllocator for Sequential {
+    fn allocate(&self, kind: PortKind, _: u16, used: &HashSet<u16>) -> Result<u16, AllocError> {
+        self.free_port(kind, 0, used)
     }
 
-    fn step_both(&mut self) {
-        self.step_service_port();
-        self.step_heartbeat_port();
+    fn next(&self, kind: PortKind, failed: u16, used: &HashSet<u16>) -> Result<u16, AllocError> {
+        self.free_port(kind, failed.saturating_add(1), used)
     }
 }
 
-type Sessions = Arc<Mutex<HashMap<SessionId, Session>>>;
+// any free port of the range, containers started together on a host are less
+// likely to race for the same port.
+#[derive(Clone, Debug, Default)]
+pub struct Random {
+    ranges: Sequential,
+}
+
+impl Random {
+    pub fn new(service_ports: Range<u16>, heartbeat_ports: Range<u16>) -> Self {
+        Random { ranges: Sequential::new(service_ports, heartbeat_ports) }
+    }
+}
+
+impl PortAllocator for Random {
+    fn allocate(&self, kind: PortKind, _: u16, used: &HashSet<u16>) -> Result<u16, AllocError> {
+        let free = self.ranges
+            .range(kind)
+            .clone()
+            .filter(|p| !used.contains(p))
+            .collect::<Vec<_>>();
+        rand::thread_rng()
+            .choose(&free)
+            .cloned()
+            .ok_or(AllocError::RangeExhausted(kind))
+    }
+
+    fn next(&self, kind: PortKind, _: u16, used: &HashSet<u16>) -> Result<u16, AllocError> {
+        self.allocate(kind, 0, used)
+    }
+}
+
+// the port proposed by the container if it is free on the host, `fallback`
+// decides when the container proposes nothing or fails to bind its port.
+#[derive(Clone, Debug, Default)]
+pub struct Proposed<A> {
+    fallback: A,
+}
+
+impl<A: PortAllocator> Proposed<A> {
+    pub fn new(fallback: A) -> Self {
+        Proposed { fallback: fallback }
+    }
+}
+
+impl<A: PortAllocator> PortAllocator for Proposed<A> {
+    fn allocate(
+        &self,
+        kind: PortKind,
+        proposed: u16,
+        used: &HashSet<u16>,
+    ) -> Result<u16, AllocError> {
+        if proposed == 0 {
+            self.fallback.allocate(kind, proposed, used)
+        } else if used.contains(&proposed) {
+            Err(AllocError::PortInUse(kind, proposed))
+        } else {
+            Ok(proposed)
+        }
+    }
+
+    fn next(&self, kind: PortKind, failed: u16, used: &HashSet<u16>) -> Result<u16, AllocError> {
+        self.fallback.next(kind, failed, used)
+    }
+}
+
+// leave the choice to the os, containers bind port 0 and report the ports
+// they got in `StatusRequest`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ephemeral;
+
+impl PortAllocator for Ephemeral {
+    fn allocate(&self, _: PortKind, _: u16, _: &HashSet<u16>) -> Result<u16, AllocError> {
+        Ok(0)
+    }
+
+    fn next(&self, _: PortKind, _: u16, _: &HashSet<u16>) -> Result<u16, AllocError> {
+        Ok(0)
+    }
+}
+
+type UsedPorts = Arc<Fn(IpAddr) -> HashSet<u16> + Send + Sync + 'static>;
+type Admission = Arc<Fn(&Service) -> Result<(), RegisterError> + Send + Sync + 'static>;
+type Renew = Arc<Fn(ServiceId, &InstanceId, IpAddr) -> bool + Send + Sync + 'static>;
+type Streams = Arc<Fn(StreamEvent) + Send + Sync + 'static>;
+
+// answer a re-registration once the registry decided, dropping it answers
+// `RegisterError::Unavailable`.
+pub type Responder = oneshot::Sender<Result<(), RegisterError>>;
+
+// why the registry refused a service, the container is told.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegisterError {
+    // the service id is taken by another instance, see `registry::ConflictPolicy`,
+    // or a named port is taken by another service on the host.
+    Conflict(String),
+    // the service id has too many instances.
+    QuotaExceeded(String),
+    // the registry can not connect to the heartbeat port.
+    Unreachable(String),
+    // the registry is shutting down.
+    Unavailable,
+}
+
+impl RegisterError {
+    pub fn is_conflict(&self) -> bool {
+        match *self {
+            RegisterError::Conflict(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_quota_exceeded(&self) -> bool {
+        match *self {
+            RegisterError::QuotaExceeded(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_unreachable(&self) -> bool {
+        match *self {
+            RegisterError::Unreachable(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_unavailable(&self) -> bool {
+        match *self {
+            RegisterError::Unavailable => true,
+            _ => false,
+        }
+    }
+
+    // the error sent to the container, see `from_kind`.
+    fn into_kind(self) -> (RegisterErrorKind, String) {
+        match self {
+            RegisterError::Conflict(msg) => (RegisterErrorKind::CONFLICT, msg),
+            RegisterError::QuotaExceeded(msg) => (RegisterErrorKind::QUOTA_EXCEEDED, msg),
+            RegisterError::Unreachable(msg) => (RegisterErrorKind::UNREACHABLE, msg),
+            RegisterError::Unavailable => {
+                (RegisterErrorKind::UNAVAILABLE, "registry unavailable".to_string())
+            }
+        }
+    }
+
+    // the error a container was sent, an unknown kind means the registry is unavailable.
+    pub fn from_kind(kind: RegisterErrorKind, msg: String) -> Self {
+        match kind {
+            RegisterErrorKind::CONFLICT => RegisterError::Conflict(msg),
+            RegisterErrorKind::QUOTA_EXCEEDED => RegisterError::QuotaExceeded(msg),
+            RegisterErrorKind::UNREACHABLE => RegisterError::Unreachable(msg),
+            RegisterErrorKind::NONE | RegisterErrorKind::UNAVAILABLE => RegisterError::Unavailable,
+        }
+    }
+
+    fn status_code(&self) -> RpcStatusCode {
+        match *self {
+            RegisterError::Conflict(_) => RpcStatusCode::AlreadyExists,
+            RegisterError::QuotaExceeded(_) => RpcStatusCode::ResourceExhausted,
+            RegisterError::Unreachable(_) => RpcStatusCode::FailedPrecondition,
+            RegisterError::Unavailable => RpcStatusCode::Unavailable,
+        }
+    }
+}
+
+// pushed by the registry down a session stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    // the service was added, the container pings within the ttl.
+    Accepted(Duration),
+    Refused(RegisterError),
+    // the registry dropped the service, e.g. its lease expired.
+    Evicted,
+}
+
+// what happens on the session streams, see `Sessions::streams`. the id of a
+// stream is unique within an incarnation of the registry.
+pub enum StreamEvent {
+    // the first message of a stream, the registry answers through the sender.
+    // commands stop once it is dropped, the stream lasts until the container
+    // closes it.
+    Opened(u64, Service, UnboundedSender<Command>),
+    Pinged(u64),
+    // true if the container closed the stream, false if it broke.
+    Closed(u64, bool),
+}
+
+type SessionMap = HashMap<SessionId, Session>;
+
+// sessions of the containers which are still negotiating ports. a container
+// crashed in the middle of the negotiation never finishes its session, so a
+// session not touched for `ttl` is reaped by a background thread, which exits
+// once all the clones are dropped.
+#[derive(Clone)]
+pub struct Sessions {
+    map: Arc<Mutex<SessionMap>>,
+    ttl: Duration,
+    epoch: u32,
+    next_seq: Arc<AtomicUsize>,
+    allocator: Arc<PortAllocator>,
+    used_ports: UsedPorts,
+    admission: Admission,
+    advertised_hosts: Option<Arc<HashSet<IpAddr>>>,
+    lease_ttl: Option<Duration>,
+    renew: Renew,
+    streams: Option<Streams>,
+}
+
+impl Sessions {
+    pub const DEFAULT_TTL_SECS: u64 = 60;
+
+    pub fn new(ttl: Duration) -> Self {
+        let map = Arc::new(Mutex::new(HashMap::new()));
+        let weak = Arc::downgrade(&map);
+        thread::Builder::new()
+            .name("session_reaper".to_string())
+            .spawn(move || reap_loop(weak, ttl))
+            .unwrap();
+        Sessions {
+            map: map,
+            ttl: ttl,
+            epoch: rand::random(),
+            next_seq: Default::default(),
+            allocator: Arc::new(Sequential::default()),
+            used_ports: Arc::new(|_| HashSet::new()),
+            admission: Arc::new(|_| Ok(())),
+            advertised_hosts: None,
+            lease_ttl: None,
+            renew: Arc::new(|_, _, _| false),
+            streams: None,
+        }
+    }
+
+    // `Sequential` is used by default.
+    pub fn allocator<A: PortAllocator + 'static>(mut self, allocator: A) -> Self {
+        self.allocator = Arc::new(allocator);
+        self
+    }
+
+    // ports taken on a host by the services which finished the negotiation,
+    // they are never proposed to a container on the same host.
+    pub fn used_ports<F>(mut self, f: F) -> Self
+    where
+        F: Fn(IpAddr) -> HashSet<u16> + Send + Sync + 'static,
+    {
+        self.used_ports = Arc::new(f);
+        self
+    }
+
+    // decide whether a service which finished the negotiation is accepted, the
+    // error is sent back to the container. all are accepted by default.
+    pub fn admission<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Service) -> Result<(), RegisterError> + Send + Sync + 'static,
+    {
+        self.admission = Arc::new(f);
+        self
+    }
+
+    // push mode, the containers renew the leases of their services with `KeepAlive`
+    // within `ttl` instead of answering heartbeats. `renew` tells whether the
+    // service is still known on the host.
+    pub fn leases<F>(mut self, ttl: Duration, renew: F) -> Self
+    where
+        F: Fn(ServiceId, &InstanceId, IpAddr) -> bool + Send + Sync + 'static,
+    {
+        self.lease_ttl = Some(ttl);
+        self.renew = Arc::new(renew);
+        self
+    }
+
+    // serve session streams, a container keeps one open instead of negotiating
+    // ports and answering heartbeats. they are refused if it is not set.
+    pub fn streams<F>(mut self, f: F) -> Self
+    where
+        F: Fn(StreamEvent) + Send + Sync + 'static,
+    {
+        self.streams = Some(Arc::new(f));
+        self
+    }
+
+    // the hosts a container may advertise instead of its peer address, only the
+    // peer address is accepted if it is not set.
+    pub fn advertised_hosts<I: IntoIterator<Item = IpAddr>>(mut self, hosts: I) -> Self {
+        let hosts = hosts.into_iter().map(canonical_host).collect();
+        self.advertised_hosts = Some(Arc::new(hosts));
+        self
+    }
+
+    // identify this incarnation of the registry, it is chosen randomly.
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    fn fresh_id(&self) -> SessionId {
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        SessionId::new(self.epoch, seq as u32)
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    // number of sessions which have not finished the port negotiation yet.
+    pub fn pending(&self) -> usize {
+        self.map.lock().unwrap().len()
+    }
+
+    // a lease is renewed by the instance id, a service without one would expire
+    // after a single ttl.
+    fn check_instance_id(&self, sid: u64, id: &str) -> Result<(), (RpcStatusCode, String)> {
+        if self.lease_ttl.is_none() || !id.is_empty() {
+            return Ok(());
+        }
+        let msg = format!("service:{:?} did not report its instance id", sid);
+        Err((RpcStatusCode::InvalidArgument, msg))
+    }
+
+    // the host a service is reached on, the peer address unless the container
+    // advertised another one out of the allowlist. deregister and keep alive go
+    // through here as well, so a peer may only speak for its own host by default.
+    fn host_of(&self, peer: &[u8], advertised: &str) -> Result<IpAddr, (RpcStatusCode, String)> {
+        let peer = match extract_host_from_grpc_bytes(peer) {
+            Ok(peer) => peer,
+            Err(e) => return Err((RpcStatusCode::InvalidArgument, e)),
+        };
+        if advertised.is_empty() {
+            return Ok(peer);
+        }
+        let host = match advertised.parse::<IpAddr>() {
+            Ok(host) => canonical_host(host),
+            Err(_) => {
+                let msg = format!("invalid advertised host: {:?}", advertised);
+                return Err((RpcStatusCode::InvalidArgument, msg));
+            }
+        };
+        if host == peer {
+            return Ok(host);
+        }
+        match self.advertised_hosts {
+            Some(ref allowed) if allowed.contains(&host) => Ok(host),
+            _ => {
+                let msg = format!("{} is not allowed to advertise {}", peer, host);
+                Err((RpcStatusCode::PermissionDenied, msg))
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<SessionMap> {
+        self.map.lock().unwrap()
+    }
+
+    // a service which just finished the negotiation may not be in the used ports
+    // yet, the container will fail to bind and step over it.
+    fn ports_on(&self, map: &SessionMap, host: IpAddr) -> HashSet<u16> {
+        let mut used = (self.used_ports)(host);
+        used.extend(pending_ports(map, host));
+        used
+    }
+
+    // a named port reported by `session` which another pending session or the
+    // session itself holds, the registered services are left to the admission.
+    // `session` must not be in `map`.
+    fn taken_named_port(
+        &self,
+        map: &SessionMap,
+        session: &Session,
+        ports: &BTreeMap<String, u16>,
+    ) -> Option<u16> {
+        let mut used = pending_ports(map, session.host);
+        used.insert(session.service_port);
+        used.insert(session.heartbeat_port);
+        ports.values().cloned().find(|port| !used.insert(*port))
+    }
+
+    fn allocate(
+        &self,
+        map: &SessionMap,
+        host: IpAddr,
+        (service_port, heartbeat_port): (u16, u16),
+    ) -> Result<(u16, u16), AllocError> {
+        let mut used = self.ports_on(map, host);
+        let service_port = self.allocator
+            .allocate(PortKind::Service, service_port, &used)?;
+        used.insert(service_port);
+        let heartbeat_port = self.allocator
+            .allocate(PortKind::Heartbeat, heartbeat_port, &used)?;
+        Ok((service_port, heartbeat_port))
+    }
+
+    // move the ports the container failed to bind forward, `session` must not
+    // be in `map`.
+    fn step(
+        &self,
+        map: &SessionMap,
+        session: &mut Session,
+        service_failed: bool,
+        heartbeat_failed: bool,
+    ) -> Result<(), AllocError> {
+        let mut used = self.ports_on(map, session.host);
+        used.insert(session.service_port);
+        used.insert(session.heartbeat_port);
+        used.extend(session.ports.values().cloned());
+        if service_failed {
+            session.service_port = self.allocator
+                .next(PortKind::Service, session.service_port, &used)?;
+            used.insert(session.service_port);
+        }
+        if heartbeat_failed {
+            session.heartbeat_port = self.allocator
+                .next(PortKind::Heartbeat, session.heartbeat_port, &used)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Sessions {
+    fn default() -> Self {
+        Sessions::new(Duration::from_secs(Self::DEFAULT_TTL_SECS))
+    }
+}
+
+fn pending_ports(map: &SessionMap, host: IpAddr) -> HashSet<u16> {
+    let mut used = HashSet::new();
+    for session in map.values().filter(|s| s.host == host) {
+        used.insert(session.service_port);
+        used.insert(session.heartbeat_port);
+        used.extend(session.ports.values().cloned());
+    }
+    used
+}
+
+fn reap(map: &mut SessionMap, ttl: Duration) -> usize {
+    let expired = map.values()
+        .filter(|s| s.is_expired(ttl))
+        .map(|s| s.session_id)
+        .collect::<Vec<_>>();
+    for id in &expired {
+        let session = map.remove(id).unwrap();
+        info!("session:{:?} expired, service_id:{:?}", id, session.service_id);
+    }
+    expired.len()
+}
+
+fn reap_loop(map: Weak<Mutex<SessionMap>>, ttl: Duration) {
+    let interval = cmp::max(ttl / 2, Duration::from_millis(10));
+    loop {
+        thread::sleep(interval);
+        match map.upgrade() {
+            Some(map) => {
+                reap(&mut map.lock().unwrap(), ttl);
+            }
+            None => return,
+        }
+    }
+}
 
 #[derive(Clone)]
-pub struct RegisterService<F1, F2> {
+pub struct RegisterService<F1, F2, F3, F4> {
     sessions: Sessions,
     register_handle: F1,
     re_register_handle: F2,
+    watch_handle: F3,
+    deregister_handle: F4,
 }
 
-pub fn create_grpc_server<F1, F2>(
+pub fn create_grpc_server<F1, F2, F3, F4>(
     port: u16,
     register_handle: F1,
     re_register_handle: F2,
+    watch_handle: F3,
+    deregister_handle: F4,
 ) -> Result<GrpcServer, GrpcError>
 where
     F1: Fn(Service) + Send + Clone + 'static,
-    F2: Fn(Service) + Send + Clone + 'static,
+    F2: Fn(Service, Responder) + Send + Clone + 'static,
+    F3: Fn(UnboundedSender<ServiceEvent>) + Send + Clone + 'static,
+    F4: Fn(Service) + Send + Clone + 'static,
+{
+    create_grpc_server_on(
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), port),
+        None,
+        Sessions::default(),
+        register_handle,
+        re_register_handle,
+        watch_handle,
+        deregister_handle,
+    )
+}
+
+pub fn create_tls_grpc_server<F1, F2, F3, F4>(
+    port: u16,
+    tls: &ServerTls,
+    register_handle: F1,
+    re_register_handle: F2,
+    watch_handle: F3,
+    deregister_handle: F4,
+) -> Result<GrpcServer, GrpcError>
+where
+    F1: Fn(Service) + Send + Clone + 'static,
+    F2: Fn(Service, Responder) + Send + Clone + 'static,
+    F3: Fn(UnboundedSender<ServiceEvent>) + Send + Clone + 'static,
+    F4: Fn(Service) + Send + Clone + 'static,
+{
+    create_grpc_server_on(
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), port),
+        Some(tls),
+        Sessions::default(),
+        register_handle,
+        re_register_handle,
+        watch_handle,
+        deregister_handle,
+    )
+}
+
+// bind to any address, e.g. `[::]:port` or loopback only, the server is plain
+// text if `tls` is `None`. `sessions` can be kept to watch the pending sessions.
+pub fn create_grpc_server_on<F1, F2, F3, F4>(
+    addr: SocketAddr,
+    tls: Option<&ServerTls>,
+    sessions: Sessions,
+    register_handle: F1,
+    re_register_handle: F2,
+    watch_handle: F3,
+    deregister_handle: F4,
+) -> Result<GrpcServer, GrpcError>
+where
+    F1: Fn(Service) + Send + Clone + 'static,
+    F2: Fn(Service, Responder) + Send + Clone + 'static,
+    F3: Fn(UnboundedSender<ServiceEvent>) + Send + Clone + 'static,
+    F4: Fn(Service) + Send + Clone + 'static,
 {
     let env = Arc::new(Environment::new(1));
-    let register_service = RegisterService::new(register_handle, re_register_handle);
+    let register_service = RegisterService::with_sessions(
+        sessions,
+        register_handle,
+        re_register_handle,
+        watch_handle,
+        deregister_handle,
+    );
     let service = create_register(register_service);
-    ServerBuilder::new(env)
-        .register_service(service)
-        .bind("0.0.0.0", port)
-        .build()
+    let host = match addr.ip() {
+        IpAddr::V4(ip) => format!("{}", ip),
+        IpAddr::V6(ip) => format!("[{}]", ip),
+    };
+    let port = addr.port();
+    let builder = ServerBuilder::new(env).register_service(service);
+    let builder = match tls {
+        Some(tls) => builder.bind_secure(host, port, tls.credentials()),
+        None => builder.bind(host, port),
+    };
+    builder.build()
 }
 
-impl<F1, F2> RegisterService<F1, F2>
+impl<F1, F2, F3, F4> RegisterService<F1, F2, F3, F4>
 where
     F1: Fn(Service) + Send + Clone + 'static,
-    F2: Fn(Service) + Send + Clone + 'static,
+    F2: Fn(Service, Responder) + Send + Clone + 'static,
+    F3: Fn(UnboundedSender<ServiceEvent>) + Send + Clone + 'static,
+    F4: Fn(Service) + Send + Clone + 'static,
 {
-    pub fn new(register_handle: F1, re_register_handle: F2) -> Self {
+    pub fn new(
+        register_handle: F1,
+        re_register_handle: F2,
+        watch_handle: F3,
+        deregister_handle: F4,
+    ) -> Self {
+        RegisterService::with_sessions(
+            Sessions::default(),
+            register_handle,
+            re_register_handle,
+            watch_handle,
+            deregister_handle,
+        )
+    }
+
+    pub fn with_sessions(
+        sessions: Sessions,
+        register_handle: F1,
+        re_register_handle: F2,
+        watch_handle: F3,
+        deregister_handle: F4,
+    ) -> Self {
         RegisterService {
-            sessions: Default::default(),
+            sessions: sessions,
             register_handle: register_handle,
             re_register_handle: re_register_handle,
+            watch_handle: watch_handle,
+            deregister_handle: deregister_handle,
         }
     }
 }
 
-impl<F1, F2> Register for RegisterService<F1, F2>
+impl<F1, F2, F3, F4> Register for RegisterService<F1, F2, F3, F4>
 where
     F1: Fn(Service) + Send + Clone + 'static,
-    F2: Fn(Service) + Send + Clone + 'static,
+    F2: Fn(Service, Responder) + Send + Clone + 'static,
+    F3: Fn(UnboundedSender<ServiceEvent>) + Send + Clone + 'static,
+    F4: Fn(Service) + Send + Clone + 'static,
 {
-    fn register(&self, ctx: RpcContext, req: RegisterRequest, sink: UnarySink<RegisterResponse>) {
-        let host = extract_host_from_grpc_bytes(ctx.host());
-        let session = Session::new(req.service_id.into(), req.meta, host);
-        let mut lock = self.sessions.lock().unwrap();
+    fn register(
+        &self,
+        ctx: RpcContext,
+        mut req: RegisterRequest,
+        sink: UnarySink<RegisterResponse>,
+    ) {
+        let host = match self.sessions.host_of(ctx.host(), &req.advertised_host) {
+            Ok(host) => host,
+            Err((code, msg)) => return reject(&ctx, sink, code, msg),
+        };
+        let checked = self.sessions.check_instance_id(req.service_id, &req.instance_id);
+        if let Err((code, msg)) = checked {
+            return reject(&ctx, sink, code, msg);
+        }
+        let labels = req.take_labels().into_iter().collect();
+        let instance_id = req.take_instance_id().into();
+        let mut lock = self.sessions.lock();
+        let proposed = (req.service_port as u16, req.heartbeat_port as u16);
+        let ports = match self.sessions.allocate(&lock, host, proposed) {
+            Ok(ports) => ports,
+            Err(e) => {
+                let msg = format!("allocate ports on {} failed: {:?}", host, e);
+                return reject(&ctx, sink, RpcStatusCode::ResourceExhausted, msg);
+            }
+        };
+        let session_id = self.sessions.fresh_id();
+        let sid = req.service_id.into();
+        let mut session = Session::new(session_id, sid, req.meta, labels, host, ports);
+        session.instance_id = instance_id;
         debug_assert!(!lock.contains_key(&session.session_id));
         lock.insert(session.session_id, session.clone());
-        let rsp = session.into();
+        let mut rsp = RegisterResponse::from(session);
+        if let Some(ttl) = self.sessions.lease_ttl {
+            rsp.set_lease_ttl_ms(as_millis(ttl));
+        }
         let f = sink.success(rsp).map_err(|e| warn!("{:?}", e));
         ctx.spawn(f);
     }
 
-    fn report_status(&self, ctx: RpcContext, req: StatusRequest, sink: UnarySink<StatusResponse>) {
+    fn report_status(
+        &self,
+        ctx: RpcContext,
+        mut req: StatusRequest,
+        sink: UnarySink<StatusResponse>,
+    ) {
+        // check the peer before touching the session, so a malformed request
+        // does not consume it.
+        if let Err(e) = extract_host_from_grpc_bytes(ctx.host()) {
+            return reject(&ctx, sink, RpcStatusCode::InvalidArgument, e);
+        }
         let mut rsp = StatusResponse::new();
-        let mut lock = self.sessions.lock().unwrap();
-        if let Some(mut session) = lock.remove(&req.session_id.into()) {
+        let mut lock = self.sessions.lock();
+        let ttl = self.sessions.ttl();
+        let session_id = SessionId::from(req.session_id);
+        // the reaper may not have run yet.
+        let session = lock.remove(&session_id)
+            .and_then(|s| if s.is_expired(ttl) { None } else { Some(s) });
+        let ports = req.take_ports()
+            .into_iter()
+            .map(|(k, v)| (k, v as u16))
+            .collect::<BTreeMap<_, _>>();
+        if let Some(mut session) = session {
             rsp.succeed = true;
+            // the ports really bound, which differ from the proposed ones if the
+            // container was asked to bind port 0.
+            if req.service_succeed && req.service_port != 0 {
+                session.service_port = req.service_port as u16;
+            }
+            if req.heartbeat_succeed && req.heartbeat_port != 0 {
+                session.heartbeat_port = req.heartbeat_port as u16;
+            }
             if req.heartbeat_succeed && req.service_succeed {
-                let host = extract_host_from_grpc_bytes(ctx.host());
+                if session.service_port == 0 || session.heartbeat_port == 0 {
+                    let msg = format!("session:{:?} did not report its ports", session_id);
+                    return reject(&ctx, sink, RpcStatusCode::InvalidArgument, msg);
+                }
+                if let Some(port) = self.sessions.taken_named_port(&lock, &session, &ports) {
+                    let msg = format!("named port {} of session:{:?} is taken", port, session_id);
+                    return reject(&ctx, sink, RpcStatusCode::AlreadyExists, msg);
+                }
                 let service = Service {
                     sid: session.service_id,
+                    instance_id: session.instance_id,
                     meta: session.meta,
-                    host: host,
+                    labels: session.labels,
+                    host: session.host,
                     service_port: session.service_port,
                     heartbeat_port: session.heartbeat_port,
+                    ports: ports,
                 };
+                // the session is dropped, registering again will not help.
+                if let Err(e) = (self.sessions.admission)(&service) {
+                    let msg = format!("service:{:?} refused: {:?}", service, e);
+                    return reject(&ctx, sink, e.status_code(), msg);
+                }
                 (self.register_handle)(service);
             } else {
-                if req.heartbeat_succeed && !req.service_succeed {
-                    session.step_service_port();
-                } else if !req.heartbeat_succeed && req.service_succeed {
-                    session.step_heartbeat_port();
-                } else {
-                    session.step_both();
+                session.ports = ports;
+                let stepped = self.sessions.step(
+                    &lock,
+                    &mut session,
+                    !req.service_succeed,
+                    !req.heartbeat_succeed,
+                );
+                if let Err(e) = stepped {
+                    // the session is dropped, the container has to register again.
+                    let msg = format!("allocate ports on {} failed: {:?}", session.host, e);
+                    return reject(&ctx, sink, RpcStatusCode::ResourceExhausted, msg);
                 }
                 rsp.service_port = u32::from(session.service_port);
                 rsp.heartbeat_port = u32::from(session.heartbeat_port);
                 rsp.session_id = session.session_id.0;
+                session.touch();
                 lock.insert(session.session_id, session);
             }
         } else {
             rsp.succeed = false;
+            rsp.stale_session = session_id.epoch() != self.sessions.epoch();
         }
         let f = sink.success(rsp).map_err(|e| warn!("{:?}", e));
         ctx.spawn(f);
@@ -166,25 +919,327 @@ where
     fn re_register(
         &self,
         ctx: RpcContext,
-        req: ReRegisterRequest,
+        mut req: ReRegisterRequest,
         sink: UnarySink<ReRegisterResponse>,
     ) {
-        let host = extract_host_from_grpc_bytes(ctx.host());
+        let host = match self.sessions.host_of(ctx.host(), &req.advertised_host) {
+            Ok(host) => host,
+            Err((code, msg)) => return reject(&ctx, sink, code, msg),
+        };
+        let checked = self.sessions.check_instance_id(req.service_id, &req.instance_id);
+        if let Err((code, msg)) = checked {
+            return reject(&ctx, sink, code, msg);
+        }
         let service = Service {
             sid: req.service_id.into(),
+            instance_id: req.take_instance_id().into(),
+            labels: req.take_labels().into_iter().collect(),
             meta: req.meta,
             host: host,
             service_port: req.service_port as u16,
             heartbeat_port: req.heartbeat_port as u16,
+            ports: req.take_ports()
+                .into_iter()
+                .map(|(k, v)| (k, v as u16))
+                .collect(),
         };
-        (self.re_register_handle)(service);
+        let (tx, rx) = oneshot::channel();
+        (self.re_register_handle)(service, tx);
+        // the registry decides, answer once it did.
+        let f = rx.then(|res| {
+            let res = res.unwrap_or(Err(RegisterError::Unavailable));
+            sink.success(res.into())
+        }).map_err(|e| warn!("{:?}", e));
+        ctx.spawn(f);
+    }
 
-        let mut rsp = ReRegisterResponse::new();
+    fn watch(&self, ctx: RpcContext, req: WatchRequest, sink: ServerStreamingSink<WatchEvent>) {
+        let (tx, rx) = future_mpsc::unbounded();
+        // registry will send a snapshot of the service table first, then changes.
+        (self.watch_handle)(tx);
+        let ids = req.service_ids;
+        let events = rx.filter(move |e| ids.is_empty() || ids.contains(&e.service().sid.0))
+            .map(|e| (e.into(), WriteFlags::default()))
+            // receiver of an unbounded channel never return Err.
+            .map_err(|_| -> GrpcError { unreachable!() });
+        // when the watcher disconnects, `rx` is dropped and the registry will forget `tx`.
+        let f = sink.send_all(events)
+            .map(|_| ())
+            .map_err(|e| warn!("watch stream closed: {:?}", e));
+        ctx.spawn(f);
+    }
+
+    fn deregister(
+        &self,
+        ctx: RpcContext,
+        mut req: DeregisterRequest,
+        sink: UnarySink<DeregisterResponse>,
+    ) {
+        let host = match self.sessions.host_of(ctx.host(), &req.advertised_host) {
+            Ok(host) => host,
+            Err((code, msg)) => return reject(&ctx, sink, code, msg),
+        };
+        let service = Service {
+            sid: req.service_id.into(),
+            instance_id: req.take_instance_id().into(),
+            labels: req.take_labels().into_iter().collect(),
+            meta: req.meta,
+            host: host,
+            service_port: req.service_port as u16,
+            heartbeat_port: req.heartbeat_port as u16,
+            ports: req.take_ports()
+                .into_iter()
+                .map(|(k, v)| (k, v as u16))
+                .collect(),
+        };
+        (self.deregister_handle)(service);
+
+        let mut rsp = DeregisterResponse::new();
         rsp.set_succeed(true);
-        rsp.set_msg("succeed".to_string());
         let f = sink.success(rsp).map_err(|e| warn!("{:?}", e));
         ctx.spawn(f);
     }
+
+    fn keep_alive(
+        &self,
+        ctx: RpcContext,
+        mut req: KeepAliveRequest,
+        sink: UnarySink<KeepAliveResponse>,
+    ) {
+        let host = match self.sessions.host_of(ctx.host(), &req.advertised_host) {
+            Ok(host) => host,
+            Err((code, msg)) => return reject(&ctx, sink, code, msg),
+        };
+        let instance_id = req.take_instance_id().into();
+        let mut rsp = KeepAliveResponse::new();
+        rsp.set_succeed((self.sessions.renew)(req.service_id.into(), &instance_id, host));
+        rsp.set_registry_epoch(self.sessions.epoch());
+        let f = sink.success(rsp).map_err(|e| warn!("{:?}", e));
+        ctx.spawn(f);
+    }
+
+    fn open_session(
+        &self,
+        ctx: RpcContext,
+        stream: RequestStream<SessionRequest>,
+        sink: DuplexSink<SessionResponse>,
+    ) {
+        let streams = match self.sessions.streams {
+            Some(ref streams) => Arc::clone(streams),
+            None => {
+                let msg = "session streams are not served".to_string();
+                return ctx.spawn(fail_session(sink, RpcStatusCode::Unimplemented, msg));
+            }
+        };
+        let sessions = self.sessions.clone();
+        let peer = ctx.host().to_vec();
+        let f = stream
+            .into_future()
+            .map_err(|(e, _)| warn!("session stream broke before it opened: {:?}", e))
+            .and_then(move |(first, pings)| {
+                let service = match first {
+                    Some(req) => {
+                        let host = sessions.host_of(&peer, &req.advertised_host);
+                        host.and_then(|host| session_service(req, host))
+                    }
+                    None => {
+                        let msg = "session stream closed before it opened".to_string();
+                        Err((RpcStatusCode::InvalidArgument, msg))
+                    }
+                };
+                match service {
+                    Ok(service) => Either::A(run_session(&sessions, streams, service, pings, sink)),
+                    Err((code, msg)) => Either::B(fail_session(sink, code, msg)),
+                }
+            });
+        ctx.spawn(f);
+    }
+}
+
+// the service described by the first message of a session stream, which has
+// no heartbeat port.
+fn session_service(
+    mut req: SessionRequest,
+    host: IpAddr,
+) -> Result<Service, (RpcStatusCode, String)> {
+    if req.service_port == 0 {
+        let msg = format!("service:{:?} did not report its port", req.service_id);
+        return Err((RpcStatusCode::InvalidArgument, msg));
+    }
+    // the stream services on a host share no heartbeat port, only the instance id
+    // tells them apart.
+    if req.instance_id.is_empty() {
+        let msg = format!("service:{:?} did not report its instance id", req.service_id);
+        return Err((RpcStatusCode::InvalidArgument, msg));
+    }
+    Ok(Service {
+        sid: req.service_id.into(),
+        instance_id: req.take_instance_id().into(),
+        labels: req.take_labels().into_iter().collect(),
+        meta: req.take_meta(),
+        host: host,
+        service_port: req.service_port as u16,
+        heartbeat_port: 0,
+        ports: req.take_ports()
+            .into_iter()
+            .map(|(k, v)| (k, v as u16))
+            .collect(),
+    })
+}
+
+// every ping is answered, so the container can tell the registry is alive. the
+// responses end once the container closed the stream and the registry dropped
+// the sender of its commands.
+fn run_session(
+    sessions: &Sessions,
+    streams: Streams,
+    service: Service,
+    pings: RequestStream<SessionRequest>,
+    sink: DuplexSink<SessionResponse>,
+) -> impl Future<Item = (), Error = ()> {
+    let id = sessions.fresh_id().0;
+    let epoch = sessions.epoch();
+    let (commands, command_rx) = future_mpsc::unbounded();
+    let (pongs, pong_rx) = future_mpsc::unbounded();
+    streams(StreamEvent::Opened(id, service, commands));
+    let ping = Arc::clone(&streams);
+    let read = pings
+        .for_each(move |_| {
+            ping(StreamEvent::Pinged(id));
+            // `PONG` is the default command, the writing half is gone if the stream broke.
+            let _ = pongs.unbounded_send(SessionResponse::new());
+            Ok(())
+        })
+        .then(move |res| -> Result<(), ()> {
+            if let Err(ref e) = res {
+                warn!("session stream {} broke: {:?}", id, e);
+            }
+            streams(StreamEvent::Closed(id, res.is_ok()));
+            Ok(())
+        });
+    let responses = command_rx
+        .map(SessionResponse::from)
+        .select(pong_rx)
+        .map(move |mut rsp| {
+            rsp.set_registry_epoch(epoch);
+            (rsp, WriteFlags::default())
+        })
+        // receiver of an unbounded channel never return Err.
+        .map_err(|_| -> GrpcError { unreachable!() });
+    // keep reading until the stream ends even if writing failed, so the registry
+    // is always told.
+    let write = sink.send_all(responses).then(|res| -> Result<(), ()> {
+        if let Err(e) = res {
+            warn!("session stream closed: {:?}", e);
+        }
+        Ok(())
+    });
+    read.join(write).map(|_| ())
+}
+
+fn fail_session(
+    sink: DuplexSink<SessionResponse>,
+    code: RpcStatusCode,
+    msg: String,
+) -> impl Future<Item = (), Error = ()> {
+    warn!("reject session stream, reason: {}", msg);
+    let status = RpcStatus::new(code, Some(msg));
+    sink.fail(status).map_err(|e| warn!("{:?}", e))
+}
+
+impl From<Service> for ServiceInfo {
+    fn from(s: Service) -> Self {
+        let mut info = ServiceInfo::new();
+        info.set_service_id(s.sid.0);
+        info.set_instance_id(s.instance_id.0);
+        info.set_meta(s.meta);
+        info.set_labels(s.labels.into_iter().collect());
+        info.set_ports(
+            s.ports
+                .into_iter()
+                .map(|(k, v)| (k, u32::from(v)))
+                .collect(),
+        );
+        info.set_host(format!("{}", s.host));
+        info.set_service_port(u32::from(s.service_port));
+        info.set_heartbeat_port(u32::from(s.heartbeat_port));
+        info
+    }
+}
+
+impl From<ServiceEvent> for WatchEvent {
+    fn from(e: ServiceEvent) -> Self {
+        let mut event = WatchEvent::new();
+        match e {
+            ServiceEvent::Added(s) => {
+                event.set_kind(EventKind::ADDED);
+                event.set_service(s.into());
+            }
+            ServiceEvent::Dropped(s) => {
+                event.set_kind(EventKind::DROPPED);
+                event.set_service(s.into());
+            }
+            ServiceEvent::HealthChanged(s, health) => {
+                event.set_kind(EventKind::HEALTH_CHANGED);
+                event.set_service(s.into());
+                event.set_health(health.into());
+            }
+            ServiceEvent::Updated(old, s) => {
+                event.set_kind(EventKind::UPDATED);
+                event.set_service(s.into());
+                event.set_old_service(old.into());
+            }
+        }
+        event
+    }
+}
+
+impl From<Health> for HealthState {
+    fn from(h: Health) -> Self {
+        match h {
+            Health::Healthy => HealthState::HEALTHY,
+            Health::Suspect => HealthState::SUSPECT,
+            Health::Down => HealthState::DOWN,
+        }
+    }
+}
+
+impl From<Result<(), RegisterError>> for ReRegisterResponse {
+    fn from(res: Result<(), RegisterError>) -> Self {
+        let mut rsp = ReRegisterResponse::new();
+        let (kind, msg) = match res {
+            Ok(()) => {
+                rsp.set_succeed(true);
+                rsp.set_msg("succeed".to_string());
+                return rsp;
+            }
+            Err(e) => e.into_kind(),
+        };
+        rsp.set_succeed(false);
+        rsp.set_error(kind);
+        rsp.set_msg(msg);
+        rsp
+    }
+}
+
+impl From<Command> for SessionResponse {
+    fn from(c: Command) -> Self {
+        let mut rsp = SessionResponse::new();
+        match c {
+            Command::Accepted(ttl) => {
+                rsp.set_command(SessionCommand::ACCEPTED);
+                rsp.set_lease_ttl_ms(as_millis(ttl));
+            }
+            Command::Refused(e) => {
+                let (kind, msg) = e.into_kind();
+                rsp.set_command(SessionCommand::REFUSED);
+                rsp.set_error(kind);
+                rsp.set_msg(msg);
+            }
+            Command::Evicted => rsp.set_command(SessionCommand::EVICTED),
+        }
+        rsp
+    }
 }
 
 impl From<Session> for RegisterResponse {
@@ -193,98 +1248,301 @@ impl From<Session> for RegisterResponse {
         rsp.set_heartbeat_port(u32::from(s.heartbeat_port));
         rsp.set_service_port(u32::from(s.service_port));
         rsp.set_session_id(s.session_id.0);
+        rsp.set_registry_epoch(s.session_id.epoch());
         rsp
     }
 }
 
-fn bytes_to_host(host: &[u8]) -> Result<IpAddr, io::Error> {
-    if host.len() != 4 && host.len() != 16 {
-        Err(io::Error::new(io::ErrorKind::Other, "invalid ip addr"))
-    } else if host.len() == 4 {
-        let mut a = [0; 4];
-        a.copy_from_slice(host);
-        Ok(a.into())
-    } else if host.len() == 16 {
-        let mut a = [0; 16];
-        a.copy_from_slice(host);
-        Ok(a.into())
-    } else {
-        unreachable!()
-    }
+// saturated, a lease longer than 49 days makes no sense anyway.
+fn as_millis(d: Duration) -> u32 {
+    let ms = d.as_secs() * 1_000 + u64::from(d.subsec_nanos() / 1_000_000);
+    cmp::min(ms, u64::from(u32::max_value())) as u32
+}
+
+// answer a request which can not be handled with an error status.
+fn reject<T>(ctx: &RpcContext, sink: UnarySink<T>, code: RpcStatusCode, msg: String) {
+    warn!("reject request, reason: {}", msg);
+    let status = RpcStatus::new(code, Some(msg));
+    let f = sink.fail(status).map_err(|e| warn!("{:?}", e));
+    ctx.spawn(f);
 }
 
-// TODO should consider ipv6, and add test for it.
-fn extract_host_from_grpc_bytes(addr: &[u8]) -> IpAddr {
-    assert!(addr.contains(&b':'));
-    let addr = addr.split(|c| c == &b':').nth(0).unwrap();
-    let mut host = Vec::with_capacity(16);
-    for t in addr.split(|c| c == &b'.') {
-        let mut sum = 0;
-        for (i, c) in t.iter().enumerate() {
-            sum += (*c - b'0') * 10u8.pow((t.len() - i - 1) as u32);
+// ipv4-mapped ipv6 addresses are reported by a dual stack server for ipv4 peers.
+fn canonical_host(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => {
+            let s = v6.segments();
+            if s[..5].iter().all(|s| *s == 0) && s[5] == 0xffff {
+                IpAddr::V4(v6.to_ipv4().unwrap())
+            } else {
+                IpAddr::V6(v6)
+            }
         }
-        host.push(sum);
+        ip => ip,
     }
+}
 
-    // TODO is unwrap safe here?
-    bytes_to_host(&host).unwrap()
+// grpc addresses look like `1.2.3.4:5`, `[::1]:5`, optionally prefixed by the
+// `ipv4:`/`ipv6:` scheme, brackets may be percent encoded. anything else,
+// e.g. a `unix:` peer, is an error.
+fn extract_host_from_grpc_bytes(addr: &[u8]) -> Result<IpAddr, String> {
+    let invalid = || format!("invalid grpc peer: {:?}", String::from_utf8_lossy(addr));
+    let s = match str::from_utf8(addr) {
+        Ok(s) => s,
+        Err(_) => return Err(invalid()),
+    };
+    let s = s.trim_left_matches("ipv4:").trim_left_matches("ipv6:");
+    let s = s.replace("%5B", "[").replace("%5D", "]");
+    let ip = match s.parse::<SocketAddr>() {
+        Ok(addr) => addr.ip(),
+        Err(_) => s.parse::<IpAddr>().map_err(|_| invalid())?,
+    };
+    Ok(canonical_host(ip))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::thread;
     use std::net::IpAddr;
-    use super::{bytes_to_host, extract_host_from_grpc_bytes, Session, ServiceId};
+    use std::time::Duration;
+    use std::collections::{HashMap, HashSet, BTreeMap};
 
-    #[test]
-    fn test_bytes_to_host() {
-        let a1 = [0; 4];
-        let a2 = [0; 1];
-        let a3 = [0; 5];
-        let a4 = [0; 16];
-        let a5 = [0; 26];
-
-        let r1 = bytes_to_host(&a1);
-        let r2 = bytes_to_host(&a2);
-        let r3 = bytes_to_host(&a3);
-        let r4 = bytes_to_host(&a4);
-        let r5 = bytes_to_host(&a5);
-
-        assert!(r1.is_ok());
-        assert!(r2.is_err());
-        assert!(r3.is_err());
-        assert!(r4.is_ok());
-        assert!(r5.is_err());
-    }
+    use rand::{self, Rng};
+    use grpcio::RpcStatusCode;
+
+    use super::{extract_host_from_grpc_bytes, reap, AllocError, PortKind, PortAllocator,
+                Sequential, Random, Proposed, Ephemeral, Session, SessionId, Sessions, ServiceId,
+                RegisterError};
 
     #[test]
     fn test_extract_host_from_grpc_bytes() {
         let a = b"127.127.127.127:65535";
-        let b = extract_host_from_grpc_bytes(a);
+        let b = extract_host_from_grpc_bytes(a).unwrap();
         let ip: IpAddr = "127.127.127.127".parse().unwrap();
         assert_eq!(b, ip);
 
         let a = b"0.0.0.127:65535";
-        let b = extract_host_from_grpc_bytes(a);
+        let b = extract_host_from_grpc_bytes(a).unwrap();
         let ip: IpAddr = "0.0.0.127".parse().unwrap();
         assert_eq!(b, ip);
 
         let a = b"1.1.1.1:1";
-        let b = extract_host_from_grpc_bytes(a);
+        let b = extract_host_from_grpc_bytes(a).unwrap();
         let ip: IpAddr = "1.1.1.1".parse().unwrap();
         assert_eq!(b, ip);
+
+        let cases: &[(&[u8], &str)] = &[
+            (b"ipv4:10.0.0.1:1234", "10.0.0.1"),
+            (b"[::1]:1234", "::1"),
+            (b"ipv6:[::1]:1234", "::1"),
+            (b"ipv6:%5B2001:db8::8a2e:370:7334%5D:65535", "2001:db8::8a2e:370:7334"),
+            (b"[::ffff:192.168.1.1]:80", "192.168.1.1"),
+            (b"fe80::1", "fe80::1"),
+            (b"127.0.0.1", "127.0.0.1"),
+        ];
+        for &(a, ip) in cases {
+            let ip: IpAddr = ip.parse().unwrap();
+            assert_eq!(extract_host_from_grpc_bytes(a), Ok(ip));
+        }
+    }
+
+    #[test]
+    fn test_extract_host_from_malformed_grpc_bytes() {
+        let cases: &[&[u8]] = &[
+            b"",
+            b":",
+            b"localhost:1234",
+            b"unix:/tmp/registry.sock",
+            b"ipv6:[::1:1234",
+            b"ipv6:%5B::1:1234",
+            b"1.2.3:1",
+            b"256.0.0.1:1",
+            b"1.2.3.4:65536",
+            b"\xff\xfe1.2.3.4:1",
+        ];
+        for a in cases {
+            assert!(extract_host_from_grpc_bytes(a).is_err());
+        }
     }
 
     #[test]
-    fn test_session() {
-        let ip = [0; 4];
-        let mut s = Session::new(ServiceId(1), "".to_string(), ip);
-
-        s.step_heartbeat_port();
-        s.step_service_port();
-        assert_eq!(s.service_port, 20_000 + 1);
-        assert_eq!(s.heartbeat_port, 25_000 + 1);
-        s.step_both();
-        assert_eq!(s.service_port, 20_000 + 2);
-        assert_eq!(s.heartbeat_port, 25_000 + 2);
+    fn test_extract_host_never_panics() {
+        let alphabet = b"0123456789abcdef.:[]%5BDipv46unx/ \xff";
+        let mut rng = rand::thread_rng();
+        for _ in 0..10_000 {
+            let len = rng.gen_range(0, 48);
+            let a = (0..len)
+                .map(|_| alphabet[rng.gen_range(0, alphabet.len())])
+                .collect::<Vec<_>>();
+            let _ = extract_host_from_grpc_bytes(&a);
+
+            let a = (0..len).map(|_| rng.gen::<u8>()).collect::<Vec<_>>();
+            let _ = extract_host_from_grpc_bytes(&a);
+        }
+    }
+
+    fn session<T: Into<IpAddr>>(id: SessionId, sid: ServiceId, host: T) -> Session {
+        Session::new(id, sid, "".to_string(), BTreeMap::new(), host, (20_000, 25_000))
+    }
+
+    #[test]
+    fn test_allocate_ports() {
+        let host: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+        let sessions = Sessions::default()
+            .allocator(Sequential::new(20_000..20_003, 20_002..20_005))
+            .used_ports(move |h| if h == host {
+                vec![20_000, 20_002].into_iter().collect()
+            } else {
+                HashSet::new()
+            });
+        let mut map = HashMap::new();
+
+        assert_eq!(sessions.allocate(&map, other, (0, 0)), Ok((20_000, 20_002)));
+        // the ranges overlap, the service port is not reused as the heartbeat port.
+        assert_eq!(sessions.allocate(&map, host, (0, 0)), Ok((20_001, 20_003)));
+
+        let mut s = session(SessionId::new(1, 0), ServiceId(1), host);
+        s.service_port = 20_001;
+        s.heartbeat_port = 20_003;
+        map.insert(s.session_id, s);
+        assert_eq!(
+            sessions.allocate(&map, host, (0, 0)),
+            Err(AllocError::RangeExhausted(PortKind::Service))
+        );
+        assert_eq!(sessions.allocate(&map, other, (0, 0)), Ok((20_000, 20_002)));
+    }
+
+    #[test]
+    fn test_port_allocators() {
+        let used = vec![20_000, 20_002, 25_000].into_iter().collect::<HashSet<_>>();
+        let service = PortKind::Service;
+        let heartbeat = PortKind::Heartbeat;
+
+        let random = Random::new(20_000..20_004, 25_000..25_002);
+        for _ in 0..100 {
+            let port = random.allocate(service, 0, &used).unwrap();
+            assert!(port == 20_001 || port == 20_003);
+            assert_eq!(random.next(heartbeat, 25_001, &used), Ok(25_001));
+        }
+        let used_all = (25_000..25_002).collect::<HashSet<_>>();
+        let e = random.allocate(heartbeat, 0, &used_all).unwrap_err();
+        assert!(e.is_range_exhausted());
+
+        let proposed = Proposed::new(Sequential::default());
+        assert_eq!(proposed.allocate(service, 8_080, &used), Ok(8_080));
+        assert_eq!(proposed.allocate(service, 0, &used), Ok(20_001));
+        let e = proposed.allocate(heartbeat, 25_000, &used).unwrap_err();
+        assert_eq!(e, AllocError::PortInUse(heartbeat, 25_000));
+        assert!(e.is_port_in_use());
+        assert_eq!(proposed.next(service, 8_080, &used), Ok(20_001));
+
+        assert_eq!(Ephemeral.allocate(service, 8_080, &used), Ok(0));
+        assert_eq!(Ephemeral.next(heartbeat, 0, &used), Ok(0));
+    }
+
+    #[test]
+    fn test_step_session() {
+        let host: IpAddr = "10.0.0.1".parse().unwrap();
+        let sessions = Sessions::default()
+            .used_ports(|_| vec![20_001, 25_002].into_iter().collect());
+        let map = HashMap::new();
+        let mut s = session(SessionId::new(1, 0), ServiceId(1), host);
+
+        sessions.step(&map, &mut s, false, true).unwrap();
+        assert_eq!((s.service_port, s.heartbeat_port), (20_000, 25_001));
+        sessions.step(&map, &mut s, true, false).unwrap();
+        assert_eq!((s.service_port, s.heartbeat_port), (20_002, 25_001));
+        sessions.step(&map, &mut s, true, true).unwrap();
+        assert_eq!((s.service_port, s.heartbeat_port), (20_003, 25_003));
+
+        let sessions = sessions.allocator(Sequential::new(20_000..25_000, 25_000..25_004));
+        let e = sessions.step(&map, &mut s, false, true).unwrap_err();
+        assert!(e.is_range_exhausted());
+    }
+
+    #[test]
+    fn test_advertised_host() {
+        let peer = b"ipv4:10.0.0.1:5000";
+        let peer_ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let nat: IpAddr = "192.168.1.1".parse().unwrap();
+        let sessions = Sessions::default();
+        assert_eq!(sessions.host_of(peer, "").unwrap(), peer_ip);
+        assert_eq!(sessions.host_of(peer, "10.0.0.1").unwrap(), peer_ip);
+        assert_eq!(sessions.host_of(peer, "::ffff:10.0.0.1").unwrap(), peer_ip);
+        // nobody may speak for another host without an allowlist.
+        let (code, _) = sessions.host_of(peer, "192.168.1.1").unwrap_err();
+        assert_eq!(code, RpcStatusCode::PermissionDenied);
+        let (code, _) = sessions.host_of(peer, "example.com").unwrap_err();
+        assert_eq!(code, RpcStatusCode::InvalidArgument);
+        let (code, _) = sessions.host_of(b"unix:/tmp/sock", "").unwrap_err();
+        assert_eq!(code, RpcStatusCode::InvalidArgument);
+
+        let sessions = sessions.advertised_hosts(vec![nat]);
+        assert_eq!(sessions.host_of(peer, "192.168.1.1").unwrap(), nat);
+        assert_eq!(sessions.host_of(peer, "::ffff:192.168.1.1").unwrap(), nat);
+        let (code, _) = sessions.host_of(peer, "192.168.1.2").unwrap_err();
+        assert_eq!(code, RpcStatusCode::PermissionDenied);
+        // the peer address is always allowed.
+        assert_eq!(sessions.host_of(peer, "").unwrap(), peer_ip);
+    }
+
+
+    #[test]
+    fn test_session_id() {
+        let id = SessionId::new(0xdead_beef, 7);
+        assert_eq!(id.epoch(), 0xdead_beef);
+        assert_eq!(id.0 & 0xffff_ffff, 7);
+
+        let sessions = Sessions::new(Duration::from_secs(1));
+        let a = sessions.fresh_id();
+        let b = sessions.fresh_id();
+        assert_ne!(a, b);
+        assert_eq!(a.epoch(), sessions.epoch());
+        assert_eq!(b.epoch(), sessions.epoch());
+    }
+
+    #[test]
+    fn test_reap_sessions() {
+        let ttl = Duration::from_millis(100);
+        let mut map = HashMap::new();
+        let id = SessionId::new(1, 0);
+        let old = session(id, ServiceId(1), [0; 4]);
+        map.insert(old.session_id, old);
+        thread::sleep(ttl);
+        let id = SessionId::new(1, 1);
+        let mut new = session(id, ServiceId(2), [0; 4]);
+        new.touch();
+        map.insert(new.session_id, new.clone());
+
+        assert_eq!(reap(&mut map, ttl), 1);
+        assert_eq!(map.len(), 1);
+        assert!(map.contains_key(&new.session_id));
+    }
+
+    #[test]
+    fn test_sessions_reaper() {
+        let sessions = Sessions::new(Duration::from_millis(100));
+        let id = sessions.fresh_id();
+        let s = session(id, ServiceId(1), [0; 4]);
+        sessions.lock().insert(s.session_id, s);
+        assert_eq!(sessions.pending(), 1);
+
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(sessions.pending(), 0);
+    }
+
+    #[test]
+    fn test_register_error_kind() {
+        let errors = vec![
+            RegisterError::Conflict("a".to_string()),
+            RegisterError::QuotaExceeded("b".to_string()),
+            RegisterError::Unreachable("c".to_string()),
+            RegisterError::Unavailable,
+        ];
+        for e in errors {
+            let (kind, msg) = e.clone().into_kind();
+            assert_eq!(RegisterError::from_kind(kind, msg), e);
+        }
     }
 }
@@ -0,0 +1,217 @@
+use std::str::FromStr;
+use std::collections::{BTreeMap, BTreeSet};
+
+// a label selector is a comma separated list of requirements, all of them must
+// be satisfied:
+//
+//   env=prod           env==prod           env!=prod
+//   zone in (a,b)      zone notin (a,b)
+//   canary             !canary
+//
+// an empty selector matches every label set.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Selector {
+    requirements: Vec<Requirement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Requirement {
+    Equal(String, String),
+    NotEqual(String, String),
+    In(String, BTreeSet<String>),
+    NotIn(String, BTreeSet<String>),
+    Exists(String),
+    NotExists(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub requirement: String,
+}
+
+impl Requirement {
+    fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        match *self {
+            Requirement::Equal(ref k, ref v) => labels.get(k) == Some(v),
+            Requirement::NotEqual(ref k, ref v) => labels.get(k) != Some(v),
+            Requirement::In(ref k, ref vs) => labels.get(k).map_or(false, |v| vs.contains(v)),
+            Requirement::NotIn(ref k, ref vs) => labels.get(k).map_or(true, |v| !vs.contains(v)),
+            Requirement::Exists(ref k) => labels.contains_key(k),
+            Requirement::NotExists(ref k) => !labels.contains_key(k),
+        }
+    }
+
+    fn parse(s: &str) -> Option<Requirement> {
+        if s.starts_with('!') && !s.contains('=') {
+            return parse_key(&s[1..]).map(Requirement::NotExists);
+        }
+        if let Some(pos) = s.find('(') {
+            if !s.ends_with(')') {
+                return None;
+            }
+            let values = s[pos + 1..s.len() - 1]
+                .split(',')
+                .map(parse_value)
+                .collect::<Option<BTreeSet<_>>>();
+            let values = match values {
+                Some(values) => values,
+                None => return None,
+            };
+            let words = s[..pos].split_whitespace().collect::<Vec<_>>();
+            if words.len() != 2 {
+                return None;
+            }
+            let key = match parse_key(words[0]) {
+                Some(key) => key,
+                None => return None,
+            };
+            return match words[1] {
+                "in" => Some(Requirement::In(key, values)),
+                "notin" => Some(Requirement::NotIn(key, values)),
+                _ => None,
+            };
+        }
+        if let Some(pos) = s.find("!=") {
+            return parse_pair(&s[..pos], &s[pos + 2..])
+                .map(|(k, v)| Requirement::NotEqual(k, v));
+        }
+        if let Some(pos) = s.find("==") {
+            return parse_pair(&s[..pos], &s[pos + 2..]).map(|(k, v)| Requirement::Equal(k, v));
+        }
+        if let Some(pos) = s.find('=') {
+            return parse_pair(&s[..pos], &s[pos + 1..]).map(|(k, v)| Requirement::Equal(k, v));
+        }
+        parse_key(s).map(Requirement::Exists)
+    }
+}
+
+fn is_valid(s: &str) -> bool {
+    !s.is_empty() &&
+        s.chars().all(|c| {
+            !c.is_whitespace() && c != ',' && c != '=' && c != '!' && c != '(' && c != ')'
+        })
+}
+
+fn parse_key(s: &str) -> Option<String> {
+    let s = s.trim();
+    if is_valid(s) { Some(s.to_string()) } else { None }
+}
+
+fn parse_value(s: &str) -> Option<String> {
+    parse_key(s)
+}
+
+fn parse_pair(k: &str, v: &str) -> Option<(String, String)> {
+    match (parse_key(k), parse_value(v)) {
+        (Some(k), Some(v)) => Some((k, v)),
+        _ => None,
+    }
+}
+
+// split on the commas which are not inside parentheses.
+fn split_requirements(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+impl Selector {
+    pub fn everything() -> Self {
+        Default::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requirements.is_empty()
+    }
+
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.requirements.iter().all(|r| r.matches(labels))
+    }
+}
+
+impl FromStr for Selector {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Ok(Selector::everything());
+        }
+        let mut requirements = Vec::new();
+        for part in split_requirements(s) {
+            let part = part.trim();
+            match Requirement::parse(part) {
+                Some(r) => requirements.push(r),
+                None => return Err(ParseError { requirement: part.to_string() }),
+            }
+        }
+        Ok(Selector { requirements: requirements })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Selector;
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|&(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_selector_matches() {
+        let prod_a = labels(&[("env", "prod"), ("zone", "a")]);
+        let prod_c = labels(&[("env", "prod"), ("zone", "c"), ("canary", "")]);
+        let dev = labels(&[("env", "dev")]);
+
+        let matches = |s: &str, l: &BTreeMap<String, String>| {
+            s.parse::<Selector>().unwrap().matches(l)
+        };
+        assert!(matches("", &dev));
+        assert!(matches("env=prod", &prod_a));
+        assert!(matches("env == prod", &prod_a));
+        assert!(!matches("env=prod", &dev));
+        assert!(matches("env!=prod", &dev));
+        assert!(matches("env=prod,zone in (a, b)", &prod_a));
+        assert!(!matches("env=prod,zone in (a,b)", &prod_c));
+        assert!(matches("zone notin (a,b)", &prod_c));
+        assert!(matches("zone notin (a,b)", &dev));
+        assert!(matches("canary", &prod_c));
+        assert!(!matches("canary", &prod_a));
+        assert!(matches("!canary,env", &prod_a));
+    }
+
+    #[test]
+    fn test_selector_parse_error() {
+        for s in &[
+            "env=",
+            "=prod",
+            "env=prod,",
+            "zone in (a,b",
+            "zone within (a,b)",
+            "zone in (a,,b)",
+            "my env=prod",
+            "!",
+        ]
+        {
+            let e = s.parse::<Selector>().unwrap_err();
+            assert!(s.contains(e.requirement.as_str()));
+        }
+    }
+}
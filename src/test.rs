@@ -1,23 +1,36 @@
 use std::sync::mpsc;
-use std::sync::Arc;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 
-use grpcio::{ChannelBuilder, Environment};
+use grpcio::{ChannelBuilder, Environment, RpcStatusCode, WriteFlags, Error as GrpcError};
+use futures::{Sink, Stream};
+use futures::sync::mpsc::UnboundedSender;
 
-use rpc_server;
+use rpc_server::{self, Responder};
 use registry_proto::*;
 use registry_proto_grpc::*;
+use {Service, ServiceEvent};
 
 #[test]
 fn test_rpc_server() {
     let (tx, rx) = mpsc::channel();
     let (re_tx, re_rx) = mpsc::channel();
     let register_handle = move |s| tx.send(s).unwrap();
-    let re_register_handle = move |s| re_tx.send(s).unwrap();
+    let re_register_handle = move |s, rsp: Responder| {
+        re_tx.send(s).unwrap();
+        rsp.send(Ok(())).unwrap();
+    };
 
     let port = 11_000;
-    let mut server =
-        rpc_server::create_grpc_server(port, register_handle.clone(), re_register_handle.clone())
-            .unwrap();
+    let mut server = rpc_server::create_grpc_server(
+        port,
+        register_handle.clone(),
+        re_register_handle.clone(),
+        |_| {},
+        |_| {},
+    ).unwrap();
     server.start();
 
 
@@ -57,18 +70,29 @@ fn test_rpc_server() {
 
     let rsp = client.report_status(&req).unwrap();
     assert_eq!(rsp.succeed, false);
+    assert_eq!(rsp.stale_session, false);
 
     //simulate server crash.
     drop(server);
     warn!("end drop server");
-    let mut server =
-        rpc_server::create_grpc_server(port, register_handle.clone(), re_register_handle.clone())
-            .unwrap();
+    let mut server = rpc_server::create_grpc_server(
+        port,
+        register_handle.clone(),
+        re_register_handle.clone(),
+        |_| {},
+        |_| {},
+    ).unwrap();
     server.start();
     let ch = ChannelBuilder::new(Arc::clone(&env)).connect(&addr);
     let client = RegisterClient::new(ch);
     let rsp = client.report_status(&req).unwrap();
     assert_eq!(rsp.succeed, false);
+    // the session belongs to the crashed registry.
+    assert_eq!(rsp.stale_session, true);
+
+    // sessions of the new registry never collide with the old ones.
+    let rsp = client.register(&RegisterRequest::new()).unwrap();
+    assert_ne!(rsp.session_id, session_id);
 
     let mut req = ReRegisterRequest::new();
     req.set_heartbeat_port(21_000);
@@ -87,12 +111,19 @@ fn test_service_meta() {
     let (tx, rx) = mpsc::channel();
     let (re_tx, re_rx) = mpsc::channel();
     let register_handle = move |s| tx.send(s).unwrap();
-    let re_register_handle = move |s| re_tx.send(s).unwrap();
+    let re_register_handle = move |s, rsp: Responder| {
+        re_tx.send(s).unwrap();
+        rsp.send(Ok(())).unwrap();
+    };
 
     let port = 11_004;
-    let mut server =
-        rpc_server::create_grpc_server(port, register_handle.clone(), re_register_handle.clone())
-            .unwrap();
+    let mut server = rpc_server::create_grpc_server(
+        port,
+        register_handle.clone(),
+        re_register_handle.clone(),
+        |_| {},
+        |_| {},
+    ).unwrap();
     server.start();
 
     let service_id = 10;
@@ -106,6 +137,8 @@ fn test_service_meta() {
     let mut req = RegisterRequest::new();
     req.set_service_id(service_id);
     req.set_meta(meta.to_string());
+    req.mut_labels().insert("env".to_string(), "prod".to_string());
+    req.set_instance_id("instance-a".to_string());
     let rsp = client.register(&req).unwrap();
     let session_id = rsp.session_id;
 
@@ -113,11 +146,18 @@ fn test_service_meta() {
     req.set_heartbeat_succeed(true);
     req.set_service_succeed(true);
     req.set_session_id(session_id);
+    req.mut_ports().insert("admin".to_string(), 23_000);
     client.report_status(&req).unwrap();
 
     let service = rx.recv().unwrap();
     assert_eq!(service.service_id(), service_id.into());
     assert_eq!(service.meta(), meta);
+    assert_eq!(service.label("env"), Some("prod"));
+    assert_eq!(service.labels().len(), 1);
+    assert_eq!(service.instance_id().0, "instance-a");
+    let admin = SocketAddr::new(service.host, 23_000);
+    assert_eq!(service.addr("admin"), Some(admin));
+    assert_eq!(service.addr("metrics"), None);
 
 
     // test reregister;
@@ -128,6 +168,9 @@ fn test_service_meta() {
     req.set_heartbeat_port(21_000);
     req.set_service_port(22_000);
     req.set_meta(meta.to_string());
+    req.mut_labels().insert("zone".to_string(), "a".to_string());
+    req.mut_ports().insert("metrics".to_string(), 23_001);
+    req.set_instance_id("instance-a".to_string());
 
     let rsp = client.re_register(&req).unwrap();
     assert_eq!(rsp.succeed, true);
@@ -137,4 +180,512 @@ fn test_service_meta() {
     assert_eq!(service.service_port, 22_000);
     assert_eq!(service.sid, service_id.into());
     assert_eq!(service.meta, meta);
+    assert_eq!(service.label("zone"), Some("a"));
+    assert_eq!(service.label("env"), None);
+    assert_eq!(service.addr("admin"), None);
+    assert_eq!(service.instance_id().0, "instance-a");
+    assert_eq!(service.addr("metrics").map(|a| a.port()), Some(23_001));
+}
+
+fn simple_service(sid: u64) -> Service {
+    Service {
+        sid: sid.into(),
+        instance_id: Default::default(),
+        meta: "".to_string(),
+        labels: Default::default(),
+        host: "127.0.0.1".parse().unwrap(),
+        service_port: 20_000,
+        heartbeat_port: 25_000,
+        ports: Default::default(),
+    }
+}
+
+#[test]
+fn test_watch() {
+    let watch_handle = |tx: UnboundedSender<ServiceEvent>| {
+        tx.unbounded_send(ServiceEvent::Added(simple_service(1)))
+            .unwrap();
+        tx.unbounded_send(ServiceEvent::Added(simple_service(2)))
+            .unwrap();
+        tx.unbounded_send(ServiceEvent::Dropped(simple_service(1)))
+            .unwrap();
+    };
+
+    let port = 11_006;
+    let mut server =
+        rpc_server::create_grpc_server(port, |_| {}, |_, _| {}, watch_handle, |_| {}).unwrap();
+    server.start();
+
+    let addr = "127.0.0.1:".to_string() + &format!("{}", port);
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(Arc::clone(&env)).connect(&addr);
+    let client = RegisterClient::new(ch);
+
+    let mut req = WatchRequest::new();
+    let events = client
+        .watch(&req)
+        .unwrap()
+        .wait()
+        .map(|e| e.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].kind, EventKind::ADDED);
+    assert_eq!(events[0].get_service().service_id, 1);
+    assert_eq!(events[0].get_service().host, "127.0.0.1");
+    assert_eq!(events[0].get_service().service_port, 20_000);
+    assert_eq!(events[0].get_service().heartbeat_port, 25_000);
+    assert_eq!(events[1].kind, EventKind::ADDED);
+    assert_eq!(events[1].get_service().service_id, 2);
+    assert_eq!(events[2].kind, EventKind::DROPPED);
+    assert_eq!(events[2].get_service().service_id, 1);
+
+    // only watch service 1.
+    req.set_service_ids(vec![1]);
+    let events = client
+        .watch(&req)
+        .unwrap()
+        .wait()
+        .map(|e| e.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].kind, EventKind::ADDED);
+    assert_eq!(events[1].kind, EventKind::DROPPED);
+}
+
+#[test]
+fn test_deregister() {
+    let (tx, rx) = mpsc::channel();
+    let deregister_handle = move |s| tx.send(s).unwrap();
+
+    let port = 11_008;
+    let mut server =
+        rpc_server::create_grpc_server(port, |_| {}, |_, _| {}, |_| {}, deregister_handle).unwrap();
+    server.start();
+
+    let addr = "127.0.0.1:".to_string() + &format!("{}", port);
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(Arc::clone(&env)).connect(&addr);
+    let client = RegisterClient::new(ch);
+
+    let mut req = DeregisterRequest::new();
+    req.set_service_id(10);
+    req.set_meta("0x01".to_string());
+    req.set_heartbeat_port(21_000);
+    req.set_service_port(22_000);
+    let rsp = client.deregister(&req).unwrap();
+    assert_eq!(rsp.succeed, true);
+
+    let service = rx.recv().unwrap();
+    assert_eq!(service.sid, 10.into());
+    assert_eq!(service.meta, "0x01");
+    assert_eq!(service.heartbeat_port, 21_000);
+    assert_eq!(service.service_port, 22_000);
+
+    // nobody may deregister the services of another host without an allowlist.
+    req.set_advertised_host("192.168.1.1".to_string());
+    match client.deregister(&req).unwrap_err() {
+        GrpcError::RpcFailure(status) => {
+            assert_eq!(status.status, RpcStatusCode::PermissionDenied);
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn test_ipv6_server() {
+    let (tx, rx) = mpsc::channel();
+    let re_register_handle = move |s, rsp: Responder| {
+        tx.send(s).unwrap();
+        rsp.send(Ok(())).unwrap();
+    };
+
+    let port = 11_014;
+    let addr: SocketAddr = format!("[::1]:{}", port).parse().unwrap();
+    let mut server = rpc_server::create_grpc_server_on(
+        addr,
+        None,
+        rpc_server::Sessions::default(),
+        |_| {},
+        re_register_handle,
+        |_| {},
+        |_| {},
+    ).unwrap();
+    server.start();
+
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(Arc::clone(&env)).connect(&format!("{}", addr));
+    let client = RegisterClient::new(ch);
+
+    let mut req = ReRegisterRequest::new();
+    req.set_service_id(10);
+    req.set_heartbeat_port(21_000);
+    req.set_service_port(22_000);
+    client.re_register(&req).unwrap();
+
+    let service = rx.recv().unwrap();
+    assert_eq!(service.host, "::1".parse::<IpAddr>().unwrap());
+    assert_eq!(service.service_addr(), "[::1]:22000".parse().unwrap());
+
+    // the server only listens on the ipv6 loopback.
+    let ch = ChannelBuilder::new(env).connect(&format!("127.0.0.1:{}", port));
+    let client = RegisterClient::new(ch);
+    assert!(client.re_register(&req).is_err());
+}
+
+#[test]
+fn test_session_expired() {
+    let port = 11_016;
+    let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+    let sessions = rpc_server::Sessions::new(Duration::from_millis(200));
+    let mut server = rpc_server::create_grpc_server_on(
+        addr,
+        None,
+        sessions.clone(),
+        |_| {},
+        |_, _| {},
+        |_| {},
+        |_| {},
+    ).unwrap();
+    server.start();
+
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(env).connect(&format!("{}", addr));
+    let client = RegisterClient::new(ch);
+    let mut req = RegisterRequest::new();
+    req.set_service_id(10);
+    let rsp = client.register(&req).unwrap();
+    assert_eq!(sessions.pending(), 1);
+
+    // the container crashed before it reported the status.
+    thread::sleep(Duration::from_millis(600));
+    assert_eq!(sessions.pending(), 0);
+
+    let mut req = StatusRequest::new();
+    req.set_heartbeat_succeed(true);
+    req.set_service_succeed(true);
+    req.set_session_id(rsp.session_id);
+    let rsp = client.report_status(&req).unwrap();
+    assert_eq!(rsp.succeed, false);
+    assert_eq!(rsp.stale_session, false);
+}
+
+#[test]
+fn test_port_allocation() {
+    let port = 11_020;
+    let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+    let sessions = rpc_server::Sessions::default()
+        .allocator(rpc_server::Sequential::new(20_000..20_002, 25_000..30_000))
+        .used_ports(|_| vec![20_000, 25_000].into_iter().collect());
+    let mut server = rpc_server::create_grpc_server_on(
+        addr,
+        None,
+        sessions.clone(),
+        |_| {},
+        |_, _| {},
+        |_| {},
+        |_| {},
+    ).unwrap();
+    server.start();
+
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(env).connect(&format!("{}", addr));
+    let client = RegisterClient::new(ch);
+    let mut req = RegisterRequest::new();
+    req.set_service_id(10);
+
+    // ports of the registered services are skipped.
+    let rsp = client.register(&req).unwrap();
+    assert_eq!(rsp.service_port, 20_001);
+    assert_eq!(rsp.heartbeat_port, 25_001);
+
+    // so are the ports proposed to the pending sessions.
+    match client.register(&req).unwrap_err() {
+        GrpcError::RpcFailure(status) => {
+            assert_eq!(status.status, RpcStatusCode::ResourceExhausted);
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+    assert_eq!(sessions.pending(), 1);
+}
+
+#[test]
+fn test_advertised_host() {
+    let port = 11_024;
+    let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+    let nat: IpAddr = "192.168.1.1".parse().unwrap();
+    let sessions = rpc_server::Sessions::default().advertised_hosts(vec![nat]);
+    let (tx, rx) = mpsc::channel();
+    let (re_tx, re_rx) = mpsc::channel();
+    let mut server = rpc_server::create_grpc_server_on(
+        addr,
+        None,
+        sessions,
+        move |s| tx.send(s).unwrap(),
+        move |s, rsp: Responder| {
+            re_tx.send(s).unwrap();
+            rsp.send(Ok(())).unwrap();
+        },
+        |_| {},
+        |_| {},
+    ).unwrap();
+    server.start();
+
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(env).connect(&format!("{}", addr));
+    let client = RegisterClient::new(ch);
+
+    let mut req = RegisterRequest::new();
+    req.set_service_id(10);
+    req.set_advertised_host("192.168.1.1".to_string());
+    let rsp = client.register(&req).unwrap();
+
+    let mut req = StatusRequest::new();
+    req.set_heartbeat_succeed(true);
+    req.set_service_succeed(true);
+    req.set_session_id(rsp.session_id);
+    client.report_status(&req).unwrap();
+    let service = rx.recv().unwrap();
+    assert_eq!(service.host, nat);
+    assert_eq!(service.service_addr().ip(), nat);
+
+    // hosts out of the allowlist are rejected.
+    let mut req = ReRegisterRequest::new();
+    req.set_service_id(10);
+    req.set_advertised_host("192.168.1.2".to_string());
+    match client.re_register(&req).unwrap_err() {
+        GrpcError::RpcFailure(status) => {
+            assert_eq!(status.status, RpcStatusCode::PermissionDenied);
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+
+    // the peer address is used if nothing is advertised.
+    req.clear_advertised_host();
+    assert!(client.re_register(&req).unwrap().succeed);
+    let service = re_rx.recv().unwrap();
+    assert_eq!(service.host, "127.0.0.1".parse::<IpAddr>().unwrap());
+}
+
+#[test]
+fn test_re_register_refused() {
+    let port = 11_026;
+    let re_register_handle = move |s: Service, rsp: Responder| {
+        let res = match s.service_id().0 {
+            10 => Ok(()),
+            20 => Err(rpc_server::RegisterError::Conflict("taken".to_string())),
+            // the registry is gone.
+            _ => return,
+        };
+        rsp.send(res).unwrap();
+    };
+    let mut server =
+        rpc_server::create_grpc_server(port, |_| {}, re_register_handle, |_| {}, |_| {}).unwrap();
+    server.start();
+
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(env).connect(&format!("127.0.0.1:{}", port));
+    let client = RegisterClient::new(ch);
+    let mut req = ReRegisterRequest::new();
+
+    req.set_service_id(10);
+    let rsp = client.re_register(&req).unwrap();
+    assert!(rsp.succeed);
+    assert_eq!(rsp.error, RegisterErrorKind::NONE);
+
+    req.set_service_id(20);
+    let rsp = client.re_register(&req).unwrap();
+    assert!(!rsp.succeed);
+    assert_eq!(rsp.error, RegisterErrorKind::CONFLICT);
+    assert_eq!(rsp.msg, "taken");
+
+    req.set_service_id(30);
+    let rsp = client.re_register(&req).unwrap();
+    assert!(!rsp.succeed);
+    assert_eq!(rsp.error, RegisterErrorKind::UNAVAILABLE);
+}
+
+#[test]
+fn test_named_port_taken() {
+    let port = 11_028;
+    let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+    let sessions = rpc_server::Sessions::default()
+        .allocator(rpc_server::Sequential::new(20_000..21_000, 25_000..26_000));
+    let (tx, rx) = mpsc::channel();
+    let mut server = rpc_server::create_grpc_server_on(
+        addr,
+        None,
+        sessions.clone(),
+        move |s| tx.send(s).unwrap(),
+        |_, _| {},
+        |_| {},
+        |_| {},
+    ).unwrap();
+    server.start();
+
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(env).connect(&format!("{}", addr));
+    let client = RegisterClient::new(ch);
+    let mut req = RegisterRequest::new();
+    req.set_service_id(10);
+    let a = client.register(&req).unwrap();
+    let b = client.register(&req).unwrap();
+
+    // a is still stepping its service port, its named port is held meanwhile.
+    let mut req = StatusRequest::new();
+    req.set_heartbeat_succeed(true);
+    req.set_session_id(a.session_id);
+    req.mut_ports().insert("admin".to_string(), 23_000);
+    let rsp = client.report_status(&req).unwrap();
+    assert!(rsp.succeed);
+    assert_eq!(rsp.service_port, 20_002);
+
+    let mut req = StatusRequest::new();
+    req.set_heartbeat_succeed(true);
+    req.set_service_succeed(true);
+    req.set_session_id(b.session_id);
+    req.mut_ports().insert("admin".to_string(), 23_000);
+    match client.report_status(&req).unwrap_err() {
+        GrpcError::RpcFailure(status) => {
+            assert_eq!(status.status, RpcStatusCode::AlreadyExists);
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+    assert_eq!(sessions.pending(), 1);
+
+    // nor may a named port reuse a port the session was given.
+    let mut req = StatusRequest::new();
+    req.set_heartbeat_succeed(true);
+    req.set_service_succeed(true);
+    req.set_session_id(rsp.session_id);
+    req.mut_ports().insert("admin".to_string(), 23_000);
+    req.mut_ports().insert("metrics".to_string(), rsp.heartbeat_port);
+    assert!(client.report_status(&req).is_err());
+    assert_eq!(sessions.pending(), 0);
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn test_keep_alive_host() {
+    let port = 11_030;
+    let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+    let nat: IpAddr = "192.168.1.1".parse().unwrap();
+    let local: IpAddr = "127.0.0.1".parse().unwrap();
+    let sessions = rpc_server::Sessions::default()
+        .advertised_hosts(vec![nat])
+        .leases(Duration::from_secs(1), move |_, _, host| host == local);
+    let mut server = rpc_server::create_grpc_server_on(
+        addr,
+        None,
+        sessions,
+        |_| {},
+        |_, _| {},
+        |_| {},
+        |_| {},
+    ).unwrap();
+    server.start();
+
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(env).connect(&format!("{}", addr));
+    let client = RegisterClient::new(ch);
+    let mut req = KeepAliveRequest::new();
+    req.set_service_id(10);
+    req.set_instance_id("instance-a".to_string());
+    assert!(client.keep_alive(&req).unwrap().succeed);
+
+    // the lease is looked up on the advertised host.
+    req.set_advertised_host("192.168.1.1".to_string());
+    assert!(!client.keep_alive(&req).unwrap().succeed);
+
+    req.set_advertised_host("192.168.1.2".to_string());
+    match client.keep_alive(&req).unwrap_err() {
+        GrpcError::RpcFailure(status) => {
+            assert_eq!(status.status, RpcStatusCode::PermissionDenied);
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn test_session_instance_id() {
+    let port = 11_032;
+    let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+    let (tx, rx) = mpsc::channel();
+    let tx = Mutex::new(tx);
+    let sessions = rpc_server::Sessions::default()
+        .streams(move |e| tx.lock().unwrap().send(e).unwrap());
+    let mut server = rpc_server::create_grpc_server_on(
+        addr,
+        None,
+        sessions,
+        |_| {},
+        |_, _| {},
+        |_| {},
+        |_| {},
+    ).unwrap();
+    server.start();
+
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(env).connect(&format!("{}", addr));
+    let client = RegisterClient::new(ch);
+    let (sender, receiver) = client.open_session().unwrap();
+    let mut req = SessionRequest::new();
+    req.set_service_id(10);
+    req.set_service_port(23_000);
+    let mut sender = sender.wait();
+    sender.send((req, WriteFlags::default())).unwrap();
+    sender.flush().unwrap();
+
+    // the stream services on a host could not be told apart without it.
+    match receiver.wait().next() {
+        Some(Err(GrpcError::RpcFailure(status))) => {
+            assert_eq!(status.status, RpcStatusCode::InvalidArgument);
+        }
+        r => panic!("unexpected response {:?}", r),
+    }
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn test_lease_instance_id() {
+    let port = 11_036;
+    let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+    let sessions = rpc_server::Sessions::default().leases(Duration::from_secs(1), |_, _, _| true);
+    let mut server = rpc_server::create_grpc_server_on(
+        addr,
+        None,
+        sessions,
+        |_| {},
+        |_, rsp: Responder| rsp.send(Ok(())).unwrap(),
+        |_| {},
+        |_| {},
+    ).unwrap();
+    server.start();
+
+    let env = Arc::new(Environment::new(4));
+    let ch = ChannelBuilder::new(env).connect(&format!("{}", addr));
+    let client = RegisterClient::new(ch);
+
+    // the lease could never be renewed without an instance id.
+    let mut req = RegisterRequest::new();
+    req.set_service_id(10);
+    match client.register(&req).unwrap_err() {
+        GrpcError::RpcFailure(status) => {
+            assert_eq!(status.status, RpcStatusCode::InvalidArgument);
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+    req.set_instance_id("instance-a".to_string());
+    assert!(client.register(&req).unwrap().lease_ttl_ms > 0);
+
+    let mut req = ReRegisterRequest::new();
+    req.set_service_id(10);
+    match client.re_register(&req).unwrap_err() {
+        GrpcError::RpcFailure(status) => {
+            assert_eq!(status.status, RpcStatusCode::InvalidArgument);
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+    req.set_instance_id("instance-a".to_string());
+    assert!(client.re_register(&req).unwrap().succeed);
 }
@@ -0,0 +1,88 @@
+use std::io::{self, Read};
+use std::fs::File;
+use std::path::Path;
+
+use grpcio::{ServerCredentials, ServerCredentialsBuilder, ChannelCredentials,
+             ChannelCredentialsBuilder};
+
+fn read_pem<T: AsRef<Path>>(path: T) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    File::open(path)?.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+// grpcio credentials can not be cloned, so the pem files are kept and
+// credentials are built whenever a server or a channel is created.
+#[derive(Clone, Debug)]
+pub struct ServerTls {
+    cert: Vec<u8>,
+    private_key: Vec<u8>,
+    client_ca: Option<Vec<u8>>,
+}
+
+impl ServerTls {
+    pub fn new(cert: Vec<u8>, private_key: Vec<u8>) -> Self {
+        ServerTls {
+            cert: cert,
+            private_key: private_key,
+            client_ca: None,
+        }
+    }
+
+    pub fn from_pem_files<T: AsRef<Path>>(cert: T, private_key: T) -> io::Result<Self> {
+        Ok(ServerTls::new(read_pem(cert)?, read_pem(private_key)?))
+    }
+
+    // mutual tls, clients must present a certificate signed by `client_ca`.
+    pub fn client_auth(mut self, client_ca: Vec<u8>) -> Self {
+        self.client_ca = Some(client_ca);
+        self
+    }
+
+    pub fn requires_client_auth(&self) -> bool {
+        self.client_ca.is_some()
+    }
+
+    pub fn credentials(&self) -> ServerCredentials {
+        let mut builder = ServerCredentialsBuilder::new()
+            .add_cert(self.cert.clone(), self.private_key.clone());
+        if let Some(ref ca) = self.client_ca {
+            builder = builder.root_cert(ca.clone(), true);
+        }
+        builder.build()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ClientTls {
+    ca: Vec<u8>,
+    identity: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl ClientTls {
+    // `ca` is used to verify the certificate of the registry.
+    pub fn new(ca: Vec<u8>) -> Self {
+        ClientTls {
+            ca: ca,
+            identity: None,
+        }
+    }
+
+    pub fn from_pem_file<T: AsRef<Path>>(ca: T) -> io::Result<Self> {
+        Ok(ClientTls::new(read_pem(ca)?))
+    }
+
+    // the client certificate presented to a registry which requires client auth.
+    pub fn identity(mut self, cert: Vec<u8>, private_key: Vec<u8>) -> Self {
+        self.identity = Some((cert, private_key));
+        self
+    }
+
+    pub fn credentials(&self) -> ChannelCredentials {
+        let mut builder = ChannelCredentialsBuilder::new().root_cert(self.ca.clone());
+        if let Some((ref cert, ref key)) = self.identity {
+            builder = builder.cert(cert.clone(), key.clone());
+        }
+        builder.build()
+    }
+}
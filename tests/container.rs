@@ -7,7 +7,7 @@ extern crate bytes;
 mod util;
 
 use std::sync::mpsc;
-use std::net::{TcpStream, SocketAddr};
+use std::net::{TcpStream, TcpListener, SocketAddr};
 use std::io::{Read, Write};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -18,7 +18,9 @@ use protobuf::stream::CodedOutputStream;
 use bytes::{BigEndian, ByteOrder};
 
 use service_registry::{ServiceId, rpc_server};
-use service_registry::container::{Container, Executor};
+use service_registry::rpc_server::Responder;
+use service_registry::container::{Container, Executor, RpcError};
+use service_registry::heartbeat::{Hub, TargetBuilder};
 use service_registry::heartbeat::heartbeat_proto::*;
 
 struct Exe;
@@ -50,10 +52,11 @@ fn test_register_and_run() {
     assert!(res.is_err());
 
     let register_handle = move |_| {};
-    let re_register_handle = move |_| {};
+    let re_register_handle = move |_, _| {};
 
-    let mut server = rpc_server::create_grpc_server(port, register_handle, re_register_handle)
-        .unwrap();
+    let mut server =
+        rpc_server::create_grpc_server(port, register_handle, re_register_handle, |_| {}, |_| {})
+            .unwrap();
     server.start();
 
 
@@ -71,10 +74,14 @@ fn test_loop() {
     let (tx, rx) = mpsc::channel();
     let register_handle = move |s| tx.send(s).unwrap();
     let (re_tx, re_rx) = mpsc::channel();
-    let re_register_handle = move |s| re_tx.send(s).unwrap();
+    let re_register_handle = move |s, rsp: Responder| {
+        re_tx.send(s).unwrap();
+        rsp.send(Ok(())).unwrap();
+    };
 
-    let mut server = rpc_server::create_grpc_server(port, register_handle, re_register_handle)
-        .unwrap();
+    let mut server =
+        rpc_server::create_grpc_server(port, register_handle, re_register_handle, |_| {}, |_| {})
+            .unwrap();
     server.start();
     let interval = Duration::from_secs(1);
     let gen_rsp = |_| util::simple_heartbeat_response();
@@ -93,7 +100,7 @@ fn test_loop() {
 
     for _ in 0..20 {
         thread::sleep(Duration::from_millis(100));
-        send_req(service.heartbeat_addr());
+        send_req(service.heartbeat_addr().unwrap());
     }
 
     let res = re_rx.try_recv();
@@ -121,3 +128,140 @@ fn send_req(addr: SocketAddr) {
     let response: HeartbeatResponse = parse_from_bytes(&v2[4..]).unwrap();
     assert_eq!(response, util::simple_heartbeat_response());
 }
+
+#[test]
+fn test_registry_epoch_changed() {
+    let port = 11_018;
+    let addr: SocketAddr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let register_handle = move |s| tx.send(s).unwrap();
+    let (re_tx, re_rx) = mpsc::channel();
+    let re_register_handle = move |s, rsp: Responder| {
+        re_tx.send(s).unwrap();
+        rsp.send(Ok(())).unwrap();
+    };
+
+    let sessions = rpc_server::Sessions::default();
+    let epoch = sessions.epoch();
+    let mut server = rpc_server::create_grpc_server_on(
+        addr,
+        None,
+        sessions,
+        register_handle,
+        re_register_handle,
+        |_| {},
+        |_| {},
+    ).unwrap();
+    server.start();
+    // long enough that the timeout never fires in this test.
+    let interval = Duration::from_secs(60);
+    let gen_rsp = |_| util::simple_heartbeat_response();
+
+    let mut container =
+        Container::<HeartbeatRequest, HeartbeatResponse, Exe>::new(addr, interval, gen_rsp, Exe);
+    container.start().unwrap();
+    let service = rx.recv().unwrap();
+
+    let hub = Hub::<HeartbeatRequest, HeartbeatResponse>::new(util::simple_heartbeat_request())
+        .unwrap();
+
+    // heartbeats from the same registry.
+    let target = TargetBuilder::new(&service.heartbeat_addr().unwrap())
+        .epoch(epoch)
+        .build()
+        .unwrap();
+    let id = hub.add_target(target);
+    let res = re_rx.recv_timeout(Duration::from_secs(2));
+    assert!(res.is_err());
+    hub.remove_target(id).unwrap();
+
+    // heartbeats from a restarted registry.
+    let target = TargetBuilder::new(&service.heartbeat_addr().unwrap())
+        .epoch(epoch.wrapping_add(1))
+        .build()
+        .unwrap();
+    hub.add_target(target);
+    let re_service = re_rx.recv_timeout(Duration::from_secs(2)).unwrap();
+    assert_eq!(service, re_service);
+}
+
+struct EphemeralExe {
+    listener: Option<TcpListener>,
+}
+
+impl Executor for EphemeralExe {
+    fn service_id(&self) -> ServiceId {
+        100_u64.into()
+    }
+
+    fn run(&mut self, port: u16) -> bool {
+        self.run_on(port).is_some()
+    }
+
+    fn run_on(&mut self, port: u16) -> Option<u16> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        self.listener = Some(listener);
+        Some(port)
+    }
+}
+
+#[test]
+fn test_ephemeral_ports() {
+    let port = 11_022;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let register_handle = move |s| tx.send(s).unwrap();
+    let mut server =
+        rpc_server::create_grpc_server(port, register_handle, |_, _| {}, |_| {}, |_| {}).unwrap();
+    server.start();
+
+    let gen_rsp = |_| util::simple_heartbeat_response();
+    let exe = EphemeralExe { listener: None };
+    let mut container: Container<HeartbeatRequest, HeartbeatResponse, EphemeralExe> =
+        Container::new(addr, Duration::from_secs(1), gen_rsp, exe).ephemeral_ports();
+    container.start().unwrap();
+
+    // the registry proposed 20_000/25_000, the ports bound are registered instead.
+    let service = rx.recv().unwrap();
+    let service_port = service.service_addr().port();
+    assert!(service_port != 20_000 && service_port != 0);
+    let heartbeat_port = service.heartbeat_addr().unwrap().port();
+    assert!(heartbeat_port != 25_000 && heartbeat_port != 0);
+    TcpStream::connect(service.service_addr()).unwrap();
+    send_req(service.heartbeat_addr().unwrap());
+}
+
+#[test]
+fn test_ephemeral_port_unknown() {
+    let port = 11_034;
+    let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+    let sessions = rpc_server::Sessions::default().allocator(rpc_server::Ephemeral);
+    let (tx, rx) = mpsc::channel();
+    let mut server = rpc_server::create_grpc_server_on(
+        addr,
+        None,
+        sessions,
+        move |s| tx.send(s).unwrap(),
+        |_, _| {},
+        |_| {},
+        |_| {},
+    ).unwrap();
+    server.start();
+
+    // `Exe` keeps the default `run_on`, it can not tell the port the os would choose.
+    let gen_rsp = |_| util::simple_heartbeat_response();
+    let mut container: Container<HeartbeatRequest, HeartbeatResponse, Exe> =
+        Container::new(addr, Duration::from_secs(1), gen_rsp, Exe);
+    match container.start() {
+        Err(RpcError::RunFailed(0)) => {}
+        r => panic!("unexpected result {:?}", r),
+    }
+    assert!(rx.try_recv().is_err());
+}
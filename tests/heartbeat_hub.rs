@@ -7,11 +7,12 @@ mod util;
 
 use std::thread;
 use std::time::{Duration, Instant};
+use std::sync::Mutex;
 use std::sync::mpsc::{self, RecvTimeoutError};
 
 use protobuf::Message;
 
-use service_registry::heartbeat::{Hub, Target, HubBuilder, TargetBuilder, Server};
+use service_registry::heartbeat::{Hub, Target, HubBuilder, TargetBuilder, Server, epoch_of};
 use service_registry::heartbeat::heartbeat_proto::*;
 
 use self::util::{simple_heartbeat_request, simple_heartbeat_response, create_server};
@@ -39,6 +40,9 @@ fn test_hub_target_construct() {
 
     assert_eq!(target.get_interval(), Some(interval));
     assert_eq!(target.get_timeout(), Some(timeout));
+    assert_eq!(target.get_max_failures(), None);
+    assert_eq!(target.get_failures(), 0);
+    assert_eq!(target.get_epoch(), None);
     let payload = target.get_payload().clone().unwrap();
     assert_eq!(
         simple_heartbeat_request().write_to_bytes().unwrap(),
@@ -319,3 +323,89 @@ fn test_hub_target_payload() {
         assert_eq!(res.unwrap().msg, 11);
     }
 }
+
+#[test]
+fn test_hub_max_failures() {
+    // nothing listens on this port.
+    let port = 10_016;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+    let interval = Duration::from_millis(200);
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(interval)
+        .max_failures(2)
+        .build()
+        .unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    // hub threshold.
+    {
+        let tx = tx.clone();
+        let target = TargetBuilder::new(&addr)
+            .cb(move |uuid, res| { tx.send((uuid, res)).unwrap(); })
+            .build()
+            .unwrap();
+        let id = hub.add_target(target);
+        for _ in 0..2 {
+            let (uuid, res) = rx.recv().unwrap();
+            assert_eq!(id, uuid);
+            assert!(res.unwrap_err().is_io_error());
+        }
+        let e = rx.recv_timeout(Duration::from_secs(1)).unwrap_err();
+        assert_eq!(e, RecvTimeoutError::Timeout);
+        assert!(hub.remove_target(id).is_none());
+    }
+
+    // target threshold overrides the hub's.
+    {
+        let target = TargetBuilder::new(&addr)
+            .max_failures(4)
+            .cb(move |uuid, res| { tx.send((uuid, res)).unwrap(); })
+            .build()
+            .unwrap();
+        let id = hub.add_target(target);
+        for _ in 0..4 {
+            let (uuid, res) = rx.recv().unwrap();
+            assert_eq!(id, uuid);
+            assert!(res.is_err());
+        }
+        let e = rx.recv_timeout(Duration::from_secs(1)).unwrap_err();
+        assert_eq!(e, RecvTimeoutError::Timeout);
+    }
+}
+
+#[test]
+fn test_hub_target_epoch() {
+    let port = 10_018;
+    let (epoch_tx, epoch_rx) = mpsc::channel();
+    let epoch_tx = Mutex::new(epoch_tx);
+    let mut server = Server::<HeartbeatRequest, HeartbeatResponse>::new(
+        "test_hub_target_epoch",
+        move |req| {
+            epoch_tx.lock().unwrap().send((req.msg, epoch_of(&req))).unwrap();
+            simple_heartbeat_response()
+        },
+    );
+    server.start(port).unwrap();
+
+    //wait for server thread to fully start up;
+    thread::sleep(Duration::from_millis(10));
+
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+    let hub = Hub::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request()).unwrap();
+
+    let target = TargetBuilder::new(&addr).build().unwrap();
+    assert_eq!(target.get_epoch(), None);
+    let id = hub.add_target(target);
+    assert_eq!(epoch_rx.recv().unwrap(), (simple_heartbeat_request().msg, None));
+    hub.remove_target(id).unwrap();
+
+    // the epoch does not disturb the request.
+    let target = TargetBuilder::new(&addr).epoch(42).build().unwrap();
+    assert_eq!(target.get_epoch(), Some(42));
+    hub.add_target(target);
+    assert_eq!(epoch_rx.recv().unwrap(), (simple_heartbeat_request().msg, Some(42)));
+}
@@ -25,7 +25,9 @@ use self::util::{simple_heartbeat_request, simple_heartbeat_response, create_ser
 fn test_server() {
     let port = 10_000;
     let mut server = create_server("test_server");
+    assert_eq!(server.local_addr(), None);
     server.start(port).unwrap();
+    assert_eq!(server.local_addr().unwrap().port(), port);
 
     // when server was started, then call start will return Error;
     server.start(port).unwrap_err();
@@ -92,3 +94,29 @@ fn test_server() {
         assert_eq!(v2.len(), 0);
     }
 }
+
+#[test]
+fn test_server_ephemeral_port() {
+    let mut server = create_server("test_server_ephemeral_port");
+    server.start(0).unwrap();
+    let port = server.local_addr().unwrap().port();
+    assert!(port != 0);
+
+    let addr: SocketAddr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+    TcpStream::connect(addr).unwrap();
+}
+
+#[test]
+fn test_server_dual_stack() {
+    let mut server = create_server("test_server_dual_stack");
+    server.start(0).unwrap();
+    let port = server.local_addr().unwrap().port();
+
+    // ipv4 peers are accepted whatever `net.ipv6.bindv6only` says.
+    let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+    TcpStream::connect(addr).unwrap();
+    let addr: SocketAddr = format!("[::1]:{}", port).parse().unwrap();
+    TcpStream::connect(addr).unwrap();
+}
@@ -1,18 +1,30 @@
 extern crate service_registry;
+extern crate futures;
 
 mod util;
 
-use std::sync::mpsc;
+use std::env;
+use std::fs;
+use std::collections::BTreeMap;
+use std::sync::{mpsc, Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::net::SocketAddr;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use service_registry::registry::Registry;
+use service_registry::registry::{Registry, RegistryBuilder, RegistryHandle, ConflictPolicy,
+                                 Mode};
 use service_registry::heartbeat::HubBuilder;
-use service_registry::ServiceId;
-use service_registry::container::{Container, Executor};
+use service_registry::resolver::{Resolver, Strategy};
+use service_registry::selector::Selector;
+use service_registry::tls::{ServerTls, ClientTls};
+use service_registry::rpc_server::{Proposed, Sequential};
+use futures::Stream;
+
+use service_registry::{ServiceId, ServiceEvent, Health};
+use service_registry::container::{Container, Executor, RpcError};
 use service_registry::heartbeat::heartbeat_proto::*;
-use util::{simple_heartbeat_response, simple_heartbeat_request};
+use util::{simple_heartbeat_response, simple_heartbeat_request, Proxy};
 
 static META: &str = "exe:meta";
 
@@ -59,7 +71,7 @@ fn test_registry() {
     let (a_tx, a_rx) = mpsc::channel();
     let service_available_handle = move |s| { a_tx.send(s).unwrap(); };
     let (d_tx, d_rx) = mpsc::channel();
-    let service_drop_handle = move |s| { d_tx.send(s).unwrap(); };
+    let service_drop_handle = move |s, _| { d_tx.send(s).unwrap(); };
 
     let registry = Registry::new(port, hub, service_available_handle, service_drop_handle).unwrap();
 
@@ -106,6 +118,16 @@ fn test_registry() {
     let ids = gen_all_ids();
     assert_eq!(ids, vec![sida, sidb, sidc, sidd]);
 
+    assert_eq!(registry.get_services_by_id(sida), vec![sa.clone()]);
+    assert_eq!(registry.get_services_by_id(sidc).len(), 2);
+    assert_eq!(registry.get_services_by_id(40_u64.into()), vec![]);
+    assert_eq!(registry.get_service_by_addr(sb.service_addr()), Some(sb.clone()));
+    assert_eq!(registry.get_services_by_meta(META).len(), 4);
+    assert_eq!(
+        registry.filter_services(|s| s.service_id() == sidb),
+        vec![sb.clone()]
+    );
+
     drop(container_a);
     let dsa = d_rx.recv().unwrap();
     assert_eq!(sida, dsa.service_id());
@@ -139,3 +161,899 @@ fn test_registry() {
     assert_eq!(ids, vec![sidb, sidd, side, sidf]);
 
 }
+
+#[test]
+fn test_registry_watch() {
+    let port = 12_002;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let registry = Registry::new(port, hub, |_| {}, |_, _| {}).unwrap();
+
+    let sida = 10_u64.into();
+    let mut container_a = create_simple_container(addr, Duration::from_secs(2), sida);
+    container_a.start().unwrap();
+    thread::sleep(Duration::from_millis(500));
+
+    let mut events = registry.watch().wait();
+    // snapshot
+    let e = events.next().unwrap().unwrap();
+    assert_eq!(e, ServiceEvent::Added(registry.get_all_services().pop().unwrap()));
+
+    let sidb = 20_u64.into();
+    let mut container_b = create_simple_container(addr, Duration::from_secs(2), sidb);
+    container_b.start().unwrap();
+    let e = events.next().unwrap().unwrap();
+    assert!(e.is_added());
+    assert_eq!(e.service().service_id(), sidb);
+
+    drop(container_a);
+    let e = events.next().unwrap().unwrap();
+    assert!(e.is_dropped());
+    assert_eq!(e.service().service_id(), sida);
+}
+
+#[test]
+fn test_registry_deregister() {
+    let port = 12_004;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let service_available_handle = move |s| { a_tx.send(s).unwrap(); };
+    let (d_tx, d_rx) = mpsc::channel();
+    let service_drop_handle = move |s, reason| { d_tx.send((s, reason)).unwrap(); };
+    let registry = Registry::new(port, hub, service_available_handle, service_drop_handle).unwrap();
+
+    let sid = 10_u64.into();
+    let mut container = create_simple_container(addr, Duration::from_secs(2), sid);
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+
+    let start = Instant::now();
+    drop(container);
+    let (ds, reason) = d_rx.recv().unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(s, ds);
+    assert!(reason.is_graceful());
+    assert!(registry.get_all_services().is_empty());
+}
+
+#[test]
+fn test_registry_snapshot() {
+    let port = 12_006;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+    let path = env::temp_dir().join("service_registry_test_registry_snapshot.json");
+    let _ = fs::remove_file(&path);
+
+    let create_registry = || {
+        let hub =
+            HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+                .interval(Duration::from_secs(1))
+                .build()
+                .unwrap();
+        let (a_tx, a_rx) = mpsc::channel();
+        let registry = RegistryBuilder::new(port, hub)
+            .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+            .snapshot(path.clone())
+            .build()
+            .unwrap();
+        (registry, a_rx)
+    };
+
+    let (registry, a_rx) = create_registry();
+    let sid = 10_u64.into();
+    let mut container = create_simple_container(addr, Duration::from_secs(2), sid);
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    drop(registry);
+
+    // the restarted registry restores the service before the container re-registers.
+    let start = Instant::now();
+    let (registry, a_rx) = create_registry();
+    let rs = a_rx.recv().unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(s, rs);
+    assert_eq!(registry.get_all_services(), vec![s.clone()]);
+
+    // it is heartbeated like any other service.
+    thread::sleep(Duration::from_secs(3));
+    assert_eq!(registry.get_all_services(), vec![s]);
+
+    drop(container);
+    thread::sleep(Duration::from_millis(500));
+    assert!(registry.get_all_services().is_empty());
+    drop(registry);
+
+    let (registry, _a_rx) = create_registry();
+    thread::sleep(Duration::from_millis(500));
+    assert!(registry.get_all_services().is_empty());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_registry_health() {
+    let port = 12_008;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_millis(200))
+        .timeout(Duration::from_millis(200))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let (h_tx, h_rx) = mpsc::channel();
+    let (d_tx, d_rx) = mpsc::channel();
+    let registry = RegistryBuilder::new(port, hub)
+        .max_failures(5)
+        .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+        .health_changed_handle(move |s, change| { h_tx.send((s, change)).unwrap(); })
+        .service_droped_handle(move |s, reason| { d_tx.send((s, reason)).unwrap(); })
+        .build()
+        .unwrap();
+
+    // heartbeat responses are delayed beyond the hub timeout while `slow` is set.
+    let slow = Arc::new(AtomicBool::new(false));
+    let gen_rsp = {
+        let slow = Arc::clone(&slow);
+        move |_: HeartbeatRequest| {
+            if slow.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(400));
+            }
+            simple_heartbeat_response()
+        }
+    };
+    let sid = 10_u64.into();
+    let mut container: Container<HeartbeatRequest, HeartbeatResponse, Exe> =
+        Container::new(addr, Duration::from_secs(10), gen_rsp, Exe { sid: sid });
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    assert_eq!(registry.get_service_health(&s), Some(Health::Healthy));
+
+    slow.store(true, Ordering::SeqCst);
+    let (hs, change) = h_rx.recv().unwrap();
+    assert_eq!(hs, s);
+    assert_eq!(change.from, Health::Healthy);
+    assert_eq!(change.to, Health::Suspect);
+    assert!(change.cause.unwrap().is_timeout());
+    assert_eq!(registry.get_service_health(&s), Some(Health::Suspect));
+
+    slow.store(false, Ordering::SeqCst);
+    let (hs, change) = h_rx.recv().unwrap();
+    assert_eq!(hs, s);
+    assert_eq!(change.from, Health::Suspect);
+    assert_eq!(change.to, Health::Healthy);
+    assert!(change.cause.is_none());
+    assert_eq!(registry.get_service_health(&s), Some(Health::Healthy));
+
+    slow.store(true, Ordering::SeqCst);
+    let (_, change) = h_rx.recv().unwrap();
+    assert_eq!(change.to, Health::Suspect);
+    let (hs, change) = h_rx.recv().unwrap();
+    assert_eq!(hs, s);
+    assert_eq!(change.from, Health::Suspect);
+    assert_eq!(change.to, Health::Down);
+    assert!(change.cause.unwrap().is_timeout());
+    let (ds, reason) = d_rx.recv().unwrap();
+    assert_eq!(ds, s);
+    assert!(reason.is_heartbeat_failed());
+    assert_eq!(registry.get_service_health(&s), None);
+}
+
+#[test]
+fn test_registry_resolver() {
+    let port = 12_010;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let local = Resolver::new(Strategy::RoundRobin);
+    let (a_tx, a_rx) = mpsc::channel();
+    let registry = {
+        let (r1, r2) = (local.clone(), local.clone());
+        RegistryBuilder::new(port, hub)
+            .service_available_handle(move |s| {
+                r1.add(s.clone());
+                a_tx.send(s).unwrap();
+            })
+            .service_droped_handle(move |s, _| r2.remove(&s))
+            .build()
+            .unwrap()
+    };
+    let remote = Resolver::new(Strategy::ConsistentHash);
+    let _watch = remote.watch_remote(addr, vec![10_u64.into()]).unwrap();
+
+    let sid = 10_u64.into();
+    let mut container_a = create_simple_container(addr, Duration::from_secs(2), sid);
+    container_a.start().unwrap();
+    let sa = a_rx.recv().unwrap();
+    let mut container_b = create_simple_container(addr, Duration::from_secs(2), sid);
+    container_b.start().unwrap();
+    let sb = a_rx.recv().unwrap();
+    let mut container_c = create_simple_container(addr, Duration::from_secs(2), 20_u64.into());
+    container_c.start().unwrap();
+    a_rx.recv().unwrap();
+    thread::sleep(Duration::from_millis(500));
+
+    let mut picked = vec![local.pick(sid).unwrap(), local.pick(sid).unwrap()];
+    picked.sort_by_key(|s| s.heartbeat_addr());
+    let mut expected = vec![sa.clone(), sb.clone()];
+    expected.sort_by_key(|s| s.heartbeat_addr());
+    assert_eq!(picked, expected);
+
+    let s = remote.pick_by_key(sid, &"key").unwrap();
+    assert!(s == sa || s == sb);
+    assert_eq!(remote.pick_by_key(sid, &"key"), Some(s));
+    assert_eq!(remote.pick(20_u64.into()), None);
+
+    drop(container_a);
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(local.services(sid), vec![sb.clone()]);
+    assert_eq!(remote.services(sid), vec![sb]);
+    assert_eq!(registry.get_all_services().len(), 2);
+}
+
+#[test]
+fn test_registry_resolver_reconnect() {
+    let port = 12_032;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+    let path = env::temp_dir().join("service_registry_test_resolver_reconnect.json");
+    let _ = fs::remove_file(&path);
+
+    let create_registry = || {
+        let hub =
+            HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+                .interval(Duration::from_secs(1))
+                .build()
+                .unwrap();
+        let (a_tx, a_rx) = mpsc::channel();
+        let registry = RegistryBuilder::new(port, hub)
+            .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+            .snapshot(path.clone())
+            .build()
+            .unwrap();
+        (registry, a_rx)
+    };
+
+    let (registry, a_rx) = create_registry();
+    let remote = Resolver::new(Strategy::RoundRobin);
+    let _watch = remote.watch_remote(addr, vec![]).unwrap();
+    let sid = 10_u64.into();
+    let mut container = create_simple_container(addr, Duration::from_secs(2), sid);
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(remote.services(sid), vec![s.clone()]);
+
+    // the view is stale once the stream ends.
+    drop(registry);
+    thread::sleep(Duration::from_millis(500));
+    assert!(remote.services(sid).is_empty());
+
+    // and rebuilt from the snapshot of the restarted registry.
+    let (_registry, _a_rx) = create_registry();
+    let start = Instant::now();
+    while remote.services(sid).is_empty() && start.elapsed() < Duration::from_secs(10) {
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert_eq!(remote.services(sid), vec![s]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_registry_ipv6() {
+    let addr: SocketAddr = "[::1]:12034".parse().unwrap();
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_millis(500))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let (d_tx, d_rx) = mpsc::channel();
+    let registry = RegistryBuilder::bind(addr, hub)
+        .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+        .service_droped_handle(move |s, _| { d_tx.send(s).unwrap(); })
+        .build()
+        .unwrap();
+
+    let mut container = create_simple_container(addr, Duration::from_secs(1), 10_u64.into());
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    assert_eq!(s.heartbeat_addr().unwrap().ip(), addr.ip());
+
+    // the heartbeats reach the container over ipv6.
+    thread::sleep(Duration::from_secs(3));
+    assert!(d_rx.try_recv().is_err());
+    assert_eq!(registry.get_all_services(), vec![s.clone()]);
+    assert_eq!(registry.get_service_health(&s), Some(Health::Healthy));
+}
+
+struct LabeledExe {
+    sid: ServiceId,
+    labels: Vec<(&'static str, &'static str)>,
+}
+
+impl Executor for LabeledExe {
+    fn service_id(&self) -> ServiceId {
+        self.sid
+    }
+
+    fn labels(&self) -> BTreeMap<String, String> {
+        self.labels
+            .iter()
+            .map(|&(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn run(&mut self, _: u16) -> bool {
+        true
+    }
+}
+
+#[test]
+fn test_registry_labels() {
+    let port = 12_012;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let service_available_handle = move |s| { a_tx.send(s).unwrap(); };
+    let registry = Registry::new(port, hub, service_available_handle, |_, _| {}).unwrap();
+
+    let create_and_start = |sid: u64, labels| {
+        let gen_rsp = |_| simple_heartbeat_response();
+        let exe = LabeledExe {
+            sid: sid.into(),
+            labels: labels,
+        };
+        let mut container: Container<HeartbeatRequest, HeartbeatResponse, LabeledExe> =
+            Container::new(addr, Duration::from_secs(2), gen_rsp, exe);
+        container.start().unwrap();
+        let s = a_rx.recv().unwrap();
+        (container, s)
+    };
+    let (_ca, sa) = create_and_start(10, vec![("env", "prod"), ("zone", "a")]);
+    let (_cb, sb) = create_and_start(10, vec![("env", "prod"), ("zone", "c")]);
+    let (_cc, sc) = create_and_start(20, vec![("env", "dev")]);
+    assert_eq!(sa.label("zone"), Some("a"));
+
+    let select = |s: &str| {
+        let mut services = registry.get_services_by_selector(&s.parse::<Selector>().unwrap());
+        services.sort_by_key(|s| s.heartbeat_addr());
+        services
+    };
+    assert_eq!(select("env=prod,zone in (a,b)"), vec![sa.clone()]);
+    assert_eq!(select("env=prod").len(), 2);
+    assert_eq!(select("env!=prod"), vec![sc]);
+    assert_eq!(select("env=prod,zone notin (a)"), vec![sb]);
+    assert_eq!(select("").len(), 3);
+}
+
+fn read_cert(name: &str) -> Vec<u8> {
+    use std::io::Read;
+    let path = format!("{}/tests/certs/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let mut buf = Vec::new();
+    fs::File::open(path).unwrap().read_to_end(&mut buf).unwrap();
+    buf
+}
+
+#[test]
+fn test_registry_tls() {
+    let port = 12_014;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let tls = ServerTls::new(read_cert("server.pem"), read_cert("server.key"))
+        .client_auth(read_cert("ca.pem"));
+    assert!(tls.requires_client_auth());
+    let registry = RegistryBuilder::new(port, hub)
+        .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+        .tls(tls)
+        .build()
+        .unwrap();
+
+    // plain text and a client without certificate are both rejected.
+    let mut plain = create_simple_container(addr, Duration::from_secs(2), 10_u64.into());
+    assert!(plain.start().is_err());
+    let mut anonymous = create_simple_container(addr, Duration::from_secs(2), 10_u64.into())
+        .tls(ClientTls::new(read_cert("ca.pem")));
+    assert!(anonymous.start().is_err());
+
+    let client_tls = ClientTls::new(read_cert("ca.pem"))
+        .identity(read_cert("client.pem"), read_cert("client.key"));
+    let sid = 20_u64.into();
+    let mut container = create_simple_container(addr, Duration::from_secs(2), sid)
+        .tls(client_tls.clone());
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    assert_eq!(s.service_id(), sid);
+
+    let resolver = Resolver::new(Strategy::RoundRobin);
+    let _watch = resolver.watch_remote_tls(addr, vec![], &client_tls).unwrap();
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(resolver.pick(sid), Some(s));
+    assert_eq!(registry.get_all_services().len(), 1);
+}
+
+struct ProposingExe {
+    port: u16,
+}
+
+impl Executor for ProposingExe {
+    fn service_id(&self) -> ServiceId {
+        30_u64.into()
+    }
+
+    fn port(&self) -> Option<u16> {
+        Some(self.port)
+    }
+
+    fn run(&mut self, port: u16) -> bool {
+        port == self.port
+    }
+}
+
+#[test]
+fn test_registry_port_allocator() {
+    let port = 12_016;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let _registry = RegistryBuilder::new(port, hub)
+        .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+        .port_allocator(Proposed::new(Sequential::new(22_000..22_010, 27_000..27_010)))
+        .build()
+        .unwrap();
+
+    // the container proposes both ports.
+    let gen_rsp = |_| simple_heartbeat_response();
+    let mut proposing: Container<HeartbeatRequest, HeartbeatResponse, ProposingExe> =
+        Container::new(addr, Duration::from_secs(2), gen_rsp, ProposingExe { port: 23_456 })
+            .heartbeat_port(28_765);
+    proposing.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    assert_eq!(s.service_addr().port(), 23_456);
+    assert_eq!(s.heartbeat_addr().unwrap().port(), 28_765);
+
+    // the fallback decides for the one which proposes nothing.
+    let mut container = create_simple_container(addr, Duration::from_secs(2), 10_u64.into());
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    assert_eq!(s.service_addr().port(), 22_000);
+    assert_eq!(s.heartbeat_addr().unwrap().port(), 27_000);
+}
+
+#[test]
+fn test_query_registry_in_callback() {
+    let port = 12_018;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    // the handle is only known once the registry is built.
+    let handle: Arc<Mutex<Option<RegistryHandle>>> = Arc::new(Mutex::new(None));
+    let (tx, rx) = mpsc::channel();
+    let available_handle = {
+        let handle = Arc::clone(&handle);
+        let tx = tx.clone();
+        move |s| {
+            let handle = handle.lock().unwrap().clone().unwrap();
+            let health = handle.get_service_health(&s);
+            tx.send(("available", handle.get_all_services(), health)).unwrap();
+        }
+    };
+    let droped_handle = {
+        let handle = Arc::clone(&handle);
+        move |s, _| {
+            let handle = handle.lock().unwrap().clone().unwrap();
+            let health = handle.get_service_health(&s);
+            tx.send(("droped", handle.get_all_services(), health)).unwrap();
+        }
+    };
+    let registry = RegistryBuilder::new(port, hub)
+        .service_available_handle(available_handle)
+        .service_droped_handle(droped_handle)
+        .build()
+        .unwrap();
+    *handle.lock().unwrap() = Some(registry.get_handle());
+
+    let mut container = create_simple_container(addr, Duration::from_secs(2), 10_u64.into());
+    container.start().unwrap();
+    let (event, services, health) = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(event, "available");
+    assert_eq!(services, registry.get_all_services());
+    assert_eq!(health, Some(Health::Healthy));
+
+    // callbacks of a service arrive in order.
+    drop(container);
+    let (event, services, health) = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(event, "droped");
+    assert!(services.is_empty());
+    assert_eq!(health, None);
+}
+
+#[test]
+fn test_registry_conflict_policy() {
+    let create_registry = |port, policy| {
+        let hub =
+            HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+                .interval(Duration::from_secs(1))
+                .build()
+                .unwrap();
+        let (a_tx, a_rx) = mpsc::channel();
+        let (d_tx, d_rx) = mpsc::channel();
+        let registry = RegistryBuilder::new(port, hub)
+            .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+            .service_droped_handle(move |s, reason| { d_tx.send((s, reason)).unwrap(); })
+            .conflict_policy(policy)
+            .build()
+            .unwrap();
+        let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().unwrap();
+        (registry, addr, a_rx, d_rx)
+    };
+    let interval = Duration::from_secs(2);
+
+    // the newer instance is refused.
+    let (registry, addr, a_rx, _d_rx) = create_registry(12_020, ConflictPolicy::Reject);
+    let mut older = create_simple_container(addr, interval, 10_u64.into());
+    older.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    let mut newer = create_simple_container(addr, interval, 10_u64.into());
+    assert!(newer.start().is_err());
+    let mut other = create_simple_container(addr, interval, 20_u64.into());
+    other.start().unwrap();
+    a_rx.recv().unwrap();
+    assert_eq!(registry.get_services_by_id(10_u64.into()), vec![s]);
+    drop((older, newer, other));
+    drop(registry);
+
+    // the older instance is dropped.
+    let (registry, addr, a_rx, d_rx) = create_registry(12_022, ConflictPolicy::ReplaceOlder);
+    let mut older = create_simple_container(addr, interval, 10_u64.into());
+    older.start().unwrap();
+    let old = a_rx.recv().unwrap();
+    let mut newer = create_simple_container(addr, interval, 10_u64.into());
+    newer.start().unwrap();
+    let new = a_rx.recv().unwrap();
+    let (dropped, reason) = d_rx.recv().unwrap();
+    assert_eq!(dropped, old);
+    assert!(reason.is_replaced());
+    assert_eq!(registry.get_services_by_id(10_u64.into()), vec![new]);
+}
+
+#[test]
+fn test_registry_instance_id() {
+    let port = 12_024;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let (d_tx, d_rx) = mpsc::channel();
+    let (u_tx, u_rx) = mpsc::channel();
+    let registry = RegistryBuilder::new(port, hub)
+        .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+        .service_droped_handle(move |s, _| { d_tx.send(s).unwrap(); })
+        .service_updated_handle(move |old, s| { u_tx.send((old, s)).unwrap(); })
+        .conflict_policy(ConflictPolicy::Reject)
+        .build()
+        .unwrap();
+    let remote = Resolver::new(Strategy::RoundRobin);
+    let _watch = remote.watch_remote(addr, vec![10_u64.into()]).unwrap();
+    let mut events = registry.watch().wait();
+
+    let mut a = create_simple_container(addr, Duration::from_secs(2), 10_u64.into());
+    a.start().unwrap();
+    let sa = a_rx.recv().unwrap();
+    assert_eq!(events.next().unwrap().unwrap(), ServiceEvent::Added(sa.clone()));
+    assert_eq!(sa.instance_id(), a.instance_id());
+    assert!(!sa.instance_id().is_empty());
+    let b = create_simple_container(addr, Duration::from_secs(2), 10_u64.into());
+    assert!(a.instance_id() != b.instance_id());
+
+    assert_eq!(registry.get_service_by_instance_id(a.instance_id()), Some(sa.clone()));
+    assert_eq!(registry.get_service_by_instance_id(b.instance_id()), None);
+
+    // the instance comes back on other ports, its entry is updated in place and
+    // the watchers follow it.
+    let mut c = create_simple_container(addr, Duration::from_secs(2), 10_u64.into())
+        .with_instance_id(a.instance_id().clone());
+    c.start().unwrap();
+    let (old, sc) = u_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(old, sa);
+    assert!(sc.heartbeat_addr() != sa.heartbeat_addr());
+    let e = events.next().unwrap().unwrap();
+    assert_eq!(e, ServiceEvent::Updated(sa.clone(), sc.clone()));
+    assert_eq!(registry.get_service_by_instance_id(a.instance_id()), Some(sc.clone()));
+    assert_eq!(registry.get_all_services(), vec![sc.clone()]);
+    assert_eq!(registry.get_service_by_addr(sa.service_addr()), None);
+    assert_eq!(registry.get_service_by_addr(sc.service_addr()), Some(sc.clone()));
+
+    // the heartbeats follow it.
+    drop(a);
+    thread::sleep(Duration::from_secs(3));
+    assert!(a_rx.try_recv().is_err());
+    assert!(d_rx.try_recv().is_err());
+    assert_eq!(registry.get_all_services(), vec![sc.clone()]);
+    assert_eq!(registry.get_service_health(&sc), Some(Health::Healthy));
+    assert_eq!(remote.services(10_u64.into()), vec![sc.clone()]);
+
+    // the resolvers forget the new service once it leaves.
+    drop(c);
+    assert_eq!(d_rx.recv_timeout(Duration::from_secs(5)).unwrap(), sc);
+    let e = events.next().unwrap().unwrap();
+    assert_eq!(e, ServiceEvent::Dropped(sc.clone()));
+    thread::sleep(Duration::from_millis(500));
+    assert!(remote.services(10_u64.into()).is_empty());
+}
+
+#[test]
+fn test_registry_refuses_re_register() {
+    let port = 12_026;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+    let create_registry = || {
+        let hub =
+            HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+                .interval(Duration::from_millis(200))
+                .build()
+                .unwrap();
+        let (a_tx, a_rx) = mpsc::channel();
+        let registry = RegistryBuilder::new(port, hub)
+            .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+            .conflict_policy(ConflictPolicy::Reject)
+            .build()
+            .unwrap();
+        (registry, a_rx)
+    };
+
+    let (registry, a_rx) = create_registry();
+    let mut older = create_simple_container(addr, Duration::from_secs(1), 10_u64.into());
+    older.start().unwrap();
+    a_rx.recv().unwrap();
+
+    // a restarted registry without snapshot, another instance comes first.
+    drop(registry);
+    let (registry, a_rx) = create_registry();
+    let mut newer = create_simple_container(addr, Duration::from_secs(1), 10_u64.into());
+    newer.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    assert_eq!(s.instance_id(), newer.instance_id());
+
+    // the older one re-registers once it misses the heartbeats, and gives up.
+    let start = Instant::now();
+    while older.rejection().is_none() {
+        assert!(start.elapsed() < Duration::from_secs(5));
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert!(older.rejection().unwrap().is_conflict());
+    assert!(newer.rejection().is_none());
+    assert_eq!(registry.get_all_services(), vec![s]);
+}
+
+#[test]
+fn test_registry_push_mode() {
+    let port = 12_028;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let (d_tx, d_rx) = mpsc::channel();
+    let registry = RegistryBuilder::new(port, hub)
+        .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+        .service_droped_handle(move |s, reason| { d_tx.send((s, reason)).unwrap(); })
+        .mode(Mode::Push(Duration::from_millis(600)))
+        .build()
+        .unwrap();
+
+    let mut container = create_simple_container(addr, Duration::from_secs(2), 10_u64.into());
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+
+    // no heartbeat comes, the keep alives hold the lease.
+    assert!(d_rx.recv_timeout(Duration::from_secs(2)).is_err());
+    assert_eq!(registry.get_all_services(), vec![s.clone()]);
+
+    drop(container);
+    let (dropped, reason) = d_rx.recv().unwrap();
+    assert_eq!(dropped, s);
+    assert!(reason.is_graceful());
+    assert!(registry.get_all_services().is_empty());
+}
+
+#[test]
+fn test_registry_lease_expired() {
+    let port = 12_036;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+    let proxy_addr = "127.0.0.1:12038".parse().unwrap();
+    let proxy = Proxy::new(proxy_addr, addr);
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let (d_tx, d_rx) = mpsc::channel();
+    let registry = RegistryBuilder::new(port, hub)
+        .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+        .service_droped_handle(move |s, reason| { d_tx.send((s, reason)).unwrap(); })
+        .mode(Mode::Push(Duration::from_millis(600)))
+        .build()
+        .unwrap();
+    let mut events = registry.watch().wait();
+
+    let mut container = create_simple_container(proxy_addr, Duration::from_secs(2), 10_u64.into());
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    assert_eq!(events.next().unwrap().unwrap(), ServiceEvent::Added(s.clone()));
+
+    // the keep alives are lost on the way, the lease lapses.
+    proxy.cut();
+    let (dropped, reason) = d_rx.recv_timeout(Duration::from_secs(3)).unwrap();
+    assert_eq!(dropped, s);
+    assert!(reason.is_lease_expired());
+    let e = events.next().unwrap().unwrap();
+    assert_eq!(e, ServiceEvent::HealthChanged(s.clone(), Health::Down));
+    let e = events.next().unwrap().unwrap();
+    assert_eq!(e, ServiceEvent::Dropped(s));
+    assert!(registry.get_all_services().is_empty());
+}
+
+#[test]
+fn test_registry_session_stream() {
+    let port = 12_030;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let (d_tx, d_rx) = mpsc::channel();
+    let registry = RegistryBuilder::new(port, hub)
+        .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+        .service_droped_handle(move |s, reason| { d_tx.send((s, reason)).unwrap(); })
+        .conflict_policy(ConflictPolicy::Reject)
+        .session_streams(Duration::from_millis(600))
+        .build()
+        .unwrap();
+
+    let gen_rsp = |_| simple_heartbeat_response();
+    let mut container: Container<HeartbeatRequest, HeartbeatResponse, ProposingExe> =
+        Container::new(addr, Duration::from_secs(1), gen_rsp, ProposingExe { port: 23_460 })
+            .session_stream();
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    assert_eq!(s.service_addr().port(), 23_460);
+    assert_eq!(s.heartbeat_addr(), None);
+    assert_eq!(s.instance_id(), container.instance_id());
+
+    // the pings hold the lease, another instance is refused down its stream.
+    let mut other: Container<HeartbeatRequest, HeartbeatResponse, ProposingExe> =
+        Container::new(addr, Duration::from_secs(1), gen_rsp, ProposingExe { port: 23_461 })
+            .session_stream();
+    other.start().unwrap();
+    let start = Instant::now();
+    while other.rejection().is_none() {
+        assert!(start.elapsed() < Duration::from_secs(5));
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert!(other.rejection().unwrap().is_conflict());
+    assert!(d_rx.recv_timeout(Duration::from_secs(1)).is_err());
+    assert_eq!(registry.get_all_services(), vec![s.clone()]);
+
+    // closing the stream deregisters the service.
+    drop(container);
+    let (dropped, reason) = d_rx.recv().unwrap();
+    assert_eq!(dropped, s);
+    assert!(reason.is_graceful());
+    assert!(registry.get_all_services().is_empty());
+}
+
+#[test]
+fn test_registry_session_stream_broken() {
+    let port = 12_040;
+    let addr = ("127.0.0.1:".to_string() + &format!("{}", port))
+        .parse()
+        .unwrap();
+    let proxy_addr = "127.0.0.1:12042".parse().unwrap();
+    let proxy = Proxy::new(proxy_addr, addr);
+
+    let hub = HubBuilder::<HeartbeatRequest, HeartbeatResponse>::new(simple_heartbeat_request())
+        .interval(Duration::from_secs(1))
+        .build()
+        .unwrap();
+    let (a_tx, a_rx) = mpsc::channel();
+    let (d_tx, d_rx) = mpsc::channel();
+    let registry = RegistryBuilder::new(port, hub)
+        .service_available_handle(move |s| { a_tx.send(s).unwrap(); })
+        .service_droped_handle(move |s, reason| { d_tx.send((s, reason)).unwrap(); })
+        .session_streams(Duration::from_millis(600))
+        .build()
+        .unwrap();
+    let mut events = registry.watch().wait();
+
+    // `Exe` proposes no port and can not tell the one the os would choose.
+    let gen_rsp = |_| simple_heartbeat_response();
+    let mut blind: Container<HeartbeatRequest, HeartbeatResponse, Exe> =
+        Container::new(proxy_addr, Duration::from_secs(1), gen_rsp, Exe { sid: 30_u64.into() })
+            .session_stream();
+    match blind.start() {
+        Err(RpcError::RunFailed(0)) => {}
+        r => panic!("unexpected result {:?}", r),
+    }
+
+    let mut container: Container<HeartbeatRequest, HeartbeatResponse, ProposingExe> =
+        Container::new(proxy_addr, Duration::from_secs(1), gen_rsp, ProposingExe { port: 23_470 })
+            .session_stream();
+    container.start().unwrap();
+    let s = a_rx.recv().unwrap();
+    assert_eq!(events.next().unwrap().unwrap(), ServiceEvent::Added(s.clone()));
+
+    // the connection breaks before the container closed its stream.
+    proxy.cut();
+    let (dropped, reason) = d_rx.recv_timeout(Duration::from_secs(3)).unwrap();
+    assert_eq!(dropped, s);
+    assert!(reason.is_stream_broken());
+    let e = events.next().unwrap().unwrap();
+    assert_eq!(e, ServiceEvent::HealthChanged(s.clone(), Health::Down));
+    let e = events.next().unwrap().unwrap();
+    assert_eq!(e, ServiceEvent::Dropped(s));
+    assert!(registry.get_all_services().is_empty());
+}
+
@@ -1,4 +1,10 @@
 #![allow(dead_code)]
+use std::io;
+use std::net::{TcpListener, TcpStream, SocketAddr, Shutdown};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
 use service_registry::heartbeat::Server;
 use service_registry::heartbeat::heartbeat_proto::*;
 
@@ -19,3 +25,56 @@ pub fn simple_heartbeat_response() -> HeartbeatResponse {
 pub fn create_server<N: Into<String>>(n: N) -> TestServer {
     Server::<HeartbeatRequest, HeartbeatResponse>::new(n, |_| simple_heartbeat_response())
 }
+
+// forwards the connections to `upstream` until it is cut, the peers then see
+// their connections break as if the network went down.
+pub struct Proxy {
+    cut: Arc<AtomicBool>,
+    conns: Arc<Mutex<Vec<TcpStream>>>,
+}
+
+impl Proxy {
+    pub fn new(addr: SocketAddr, upstream: SocketAddr) -> Proxy {
+        let listener = TcpListener::bind(addr).unwrap();
+        let cut = Arc::new(AtomicBool::new(false));
+        let conns: Arc<Mutex<Vec<TcpStream>>> = Default::default();
+        {
+            let (cut, conns) = (Arc::clone(&cut), Arc::clone(&conns));
+            thread::spawn(move || for conn in listener.incoming() {
+                // a connection after the cut is closed right away.
+                let conn = match conn {
+                    Ok(_) if cut.load(Ordering::SeqCst) => continue,
+                    Ok(conn) => conn,
+                    Err(_) => continue,
+                };
+                let upstream = match TcpStream::connect(upstream) {
+                    Ok(upstream) => upstream,
+                    Err(_) => continue,
+                };
+                let mut conns = conns.lock().unwrap();
+                conns.push(conn.try_clone().unwrap());
+                conns.push(upstream.try_clone().unwrap());
+                pipe(conn.try_clone().unwrap(), upstream.try_clone().unwrap());
+                pipe(upstream, conn);
+            });
+        }
+        Proxy {
+            cut: cut,
+            conns: conns,
+        }
+    }
+
+    pub fn cut(&self) {
+        self.cut.store(true, Ordering::SeqCst);
+        for conn in self.conns.lock().unwrap().drain(..) {
+            let _ = conn.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+fn pipe(mut from: TcpStream, mut to: TcpStream) {
+    thread::spawn(move || {
+        let _ = io::copy(&mut from, &mut to);
+        let _ = to.shutdown(Shutdown::Both);
+    });
+}